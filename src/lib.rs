@@ -1,14 +1,24 @@
-#![doc = include_str!("../README.md")]
-
+// the README examples parse SHT codes, so they are only included, and run as
+// doctests, with the `parse` feature
+#![cfg_attr(feature = "parse", doc = include_str!("../README.md"))]
+#![cfg_attr(
+    not(feature = "parse"),
+    doc = "Conversion and formatting for SHT colour codes. Enable the `parse` feature for parsing and the full crate documentation."
+)]
 #![warn(missing_docs)]
 #![warn(clippy::all)]
 #![warn(clippy::missing_docs_in_private_items)]
 
-
-use ::num::{checked_pow, CheckedMul, Integer, Unsigned};
+use ::num::{checked_pow, CheckedMul, FromPrimitive, Integer, One, ToPrimitive, Unsigned};
+use ::std::{
+    cmp::Ordering,
+    fmt::{Alignment, Formatter, Result as FMTResult},
+};
 
 /// Re-export from `num` crate, represents the ratio between two numbers.
 pub use ::num::rational::Ratio;
+#[doc(hidden)]
+pub use macros::{valid_hex_literal, valid_sht_literal};
 pub use sht::{ChannelRatios, ColourChannel, SecondaryColour, SHT};
 
 /// Support for RGB colour codes in hex format.
@@ -16,9 +26,114 @@ pub mod rgb;
 /// Support for SHT colour codes in SHT format.
 pub mod sht;
 
+/// Macros for constructing colours from literals checked at compile time.
+mod macros;
+
 #[cfg(test)]
 mod lib_tests;
 
+/// Chooses how to round a number that lies between two values that can be
+/// written at some precision, such as a channel between two hex codes.
+///
+/// Conversions and formatters that do not take a [`RoundingMode`] use
+/// [`RoundingMode::HalfUp`].
+///
+/// # Example
+/// ```
+/// # #[cfg(feature = "parse")]
+/// # {
+/// use sht_colour::{rgb::HexRGB, RoundingMode, SHT};
+///
+/// // a shade of 2/12 has a red channel of 2.5 over 0xF
+/// let dark_red = "2r".parse::<SHT<u32>>().unwrap();
+///
+/// let round = |mode| dark_red.to_rgb_with_rounding(1, mode);
+/// assert_eq!(round(RoundingMode::HalfUp), "#300".parse::<HexRGB<u32>>().unwrap());
+/// assert_eq!(round(RoundingMode::HalfEven), "#200".parse::<HexRGB<u32>>().unwrap());
+/// assert_eq!(round(RoundingMode::Truncate), "#200".parse::<HexRGB<u32>>().unwrap());
+/// # }
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash, Default)]
+#[non_exhaustive]
+pub enum RoundingMode {
+    /// Round to the nearest value, rounding values exactly halfway between
+    /// two up to the larger one.
+    #[default]
+    HalfUp,
+    /// Round to the nearest value, rounding values exactly halfway between
+    /// two to whichever ends in an even digit, also known as banker's
+    /// rounding. Unlike [`RoundingMode::HalfUp`], this does not make halfway
+    /// values drift upwards on average.
+    HalfEven,
+    /// Round down to the next smallest value, discarding any digits past the
+    /// precision.
+    Truncate,
+}
+
+impl RoundingMode {
+    /// Decides whether to round up a number, given how the part to be
+    /// discarded compares with one half of the last digit kept, and whether
+    /// that last digit is odd.
+    fn rounds_up(self, remainder: Ordering, odd: bool) -> bool {
+        match self {
+            RoundingMode::HalfUp => remainder != Ordering::Less,
+            RoundingMode::HalfEven => {
+                remainder == Ordering::Greater || (remainder == Ordering::Equal && odd)
+            }
+            RoundingMode::Truncate => false,
+        }
+    }
+}
+
+/// Writes a colour code, padded to at least the formatter's width with its
+/// fill character, and aligned to the left unless specified otherwise, as for
+/// strings.
+fn write_padded(formatter: &mut Formatter, code: &str) -> FMTResult {
+    // `Formatter::pad` would also truncate the code to the precision
+    let padding = formatter
+        .width()
+        .unwrap_or(0)
+        .saturating_sub(code.chars().count());
+    let (before, after) = match formatter.align() {
+        Some(Alignment::Right) => (padding, 0),
+        Some(Alignment::Center) => (padding / 2, padding - padding / 2),
+        Some(Alignment::Left) | None => (0, padding),
+    };
+    let fill = formatter.fill().to_string();
+    write!(
+        formatter,
+        "{}{}{}",
+        fill.repeat(before),
+        code,
+        fill.repeat(after)
+    )
+}
+
+/// Represents an integer overflow while computing a denominator with
+/// [`safe_denominator`].
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+struct OverflowError;
+
+/// Compute `base^exponent` for use as the denominator of a [`Ratio<T>`],
+/// such as `12^digits` for SHT components or `16^digits` for hex codes.
+///
+/// Denominators built from a power of a number base go through this function,
+/// so that they treat overflow in the same way. The one exception is the SHT
+/// parser, which divides by the base one digit at a time, so that a number
+/// whose reduced fraction fits in the integer type is not rounded just
+/// because the full power of 12 would overflow.
+///
+/// # Errors
+/// Will return `Err` if the power does not fit in the integer type.
+///
+/// [`Ratio<T>`]: num::rational::Ratio
+fn safe_denominator<T>(base: T, exponent: usize) -> Result<T, OverflowError>
+where
+    T: Integer + CheckedMul + Clone,
+{
+    checked_pow(base, exponent).ok_or(OverflowError)
+}
+
 /// Round a ratio to a simpler approximation, in a given base.
 ///
 /// # Arguments
@@ -31,8 +146,11 @@ mod lib_tests;
 ///   fraction over 0xFF rather than over 0x100, meaning they have one less
 ///   representable value than normal.
 ///
-/// # Panics
-/// Will panic if the exponentiation overflows the integer type.
+/// Returns `None` if the exponentiation overflows the integer type, or if
+/// scaling the ratio by the new denominator does.
+///
+/// Halves are rounded up; use [`round_denominator_with`] to round them
+/// another way.
 ///
 /// [`Ratio<T>`]: num::rational::Ratio
 fn round_denominator<T>(
@@ -40,12 +158,113 @@ fn round_denominator<T>(
     base: T,
     exponent: usize,
     negative_offset: T,
-) -> Ratio<T>
+) -> Option<Ratio<T>>
+where
+    T: Integer + Unsigned + CheckedMul + Clone + From<u8>,
+{
+    round_denominator_with(
+        ratio_on_unit_interval,
+        base,
+        exponent,
+        negative_offset,
+        RoundingMode::HalfUp,
+    )
+}
+
+/// Round a ratio to a simpler approximation, in a given base, as in
+/// [`round_denominator`], but with a chosen [`RoundingMode`].
+fn round_denominator_with<T>(
+    ratio_on_unit_interval: Ratio<T>,
+    base: T,
+    exponent: usize,
+    negative_offset: T,
+    mode: RoundingMode,
+) -> Option<Ratio<T>>
 where
     T: Integer + Unsigned + CheckedMul + Clone + From<u8>,
 {
-    let half = Ratio::new(1.into(), 2.into());
-    let new_denominator =
-        checked_pow(base, exponent).expect("Overflow calculating denominator") - negative_offset;
-    ((ratio_on_unit_interval * new_denominator.clone() + half).trunc()) / new_denominator
+    let new_denominator = safe_denominator(base, exponent).ok()? - negative_offset;
+    let scaled =
+        ratio_on_unit_interval.checked_mul(&Ratio::from_integer(new_denominator.clone()))?;
+    let (whole, fraction) = (scaled.to_integer(), scaled.fract());
+    // compare the fraction with one half without doubling it, which could
+    // overflow
+    let remainder = fraction
+        .numer()
+        .cmp(&(fraction.denom().clone() - fraction.numer().clone()));
+    let numerator = if mode.rounds_up(remainder, whole.is_odd()) {
+        whole + T::one()
+    } else {
+        whole
+    };
+    Some(Ratio::new(numerator, new_denominator))
+}
+
+/// Subtract one ratio from another that is no smaller, returning `None` if
+/// the arithmetic overflows the integer type, or if the result would be
+/// negative.
+fn checked_ratio_sub<T>(minuend: &Ratio<T>, subtrahend: &Ratio<T>) -> Option<Ratio<T>>
+where
+    T: Integer + CheckedMul + Clone,
+{
+    if minuend < subtrahend {
+        return None;
+    }
+    let gcd = minuend.denom().gcd(subtrahend.denom());
+    let minuend_scale = subtrahend.denom().clone() / gcd.clone();
+    let subtrahend_scale = minuend.denom().clone() / gcd;
+    let denominator = minuend.denom().checked_mul(&minuend_scale)?;
+    let numerator = minuend.numer().checked_mul(&minuend_scale)?
+        - subtrahend.numer().checked_mul(&subtrahend_scale)?;
+    Some(Ratio::new(numerator, denominator))
+}
+
+/// Clamp a non-negative ratio onto the unit interval, the range of every
+/// colour channel and SHT component, by replacing values above 1 with 1.
+fn clamp_unit<T>(ratio: Ratio<T>) -> Ratio<T>
+where
+    T: Integer + Clone,
+{
+    ratio.min(Ratio::one())
+}
+
+/// Convert a ratio to the nearest `f64`.
+///
+/// Returns NaN if the numerator or denominator cannot be represented as an
+/// `f64`, which does not happen for primitive integer types.
+fn ratio_to_f64<T>(ratio: &Ratio<T>) -> f64
+where
+    T: Clone + Integer + ToPrimitive,
+{
+    match (ratio.numer().to_f64(), ratio.denom().to_f64()) {
+        (Some(numerator), Some(denominator)) => numerator / denominator,
+        _ => f64::NAN,
+    }
+}
+
+/// Convert an `f64` on the unit interval to a ratio, rounding to the nearest
+/// fraction over a denominator of the form `0xF`, `0xFF`, `0xFFFF` or
+/// `0xFFFFFFFF`.
+///
+/// The largest of these denominators is chosen whose square still fits in
+/// `T`, leaving headroom for later arithmetic on the ratio. Values outside the
+/// unit interval are clamped into it, and NaN is treated as 0.
+fn f64_to_ratio<T>(value: f64) -> Ratio<T>
+where
+    T: Clone + Integer + FromPrimitive,
+{
+    let denominator = [0xFFFF_FFFF_u64, 0xFFFF, 0xFF, 0xF]
+        .iter()
+        .copied()
+        .find(|&denominator| T::from_u64(denominator * denominator).is_some())
+        .expect("Integer type too small to hold a denominator");
+    let clamped = if value.is_nan() {
+        0.0
+    } else {
+        value.clamp(0.0, 1.0)
+    };
+    let numerator = T::from_f64((clamped * denominator as f64).round())
+        .expect("Numerator should be no larger than denominator");
+    let denominator = T::from_u64(denominator).expect("Denominator should fit in type");
+    Ratio::new(numerator, denominator)
 }
@@ -1,3 +1,4 @@
+#[cfg(feature = "parse")]
 #[test]
 fn rgb_to_sht() {
     use super::{rgb::HexRGB, sht::SHT};
@@ -22,6 +23,7 @@ fn rgb_to_sht() {
     }
 }
 
+#[cfg(feature = "parse")]
 #[test]
 fn sht_to_rgb() {
     use super::{rgb::HexRGB, sht::SHT};
@@ -58,15 +60,15 @@ fn rounding_up_no_offset() {
     use ::num::rational::Ratio;
     assert_eq!(
         round_denominator::<u8>(Ratio::new(2, 3), 2, 2, 0),
-        Ratio::new(3, 4)
+        Some(Ratio::new(3, 4))
     );
     assert_eq!(
         round_denominator::<u8>(Ratio::new(50, 100), 100, 0, 0),
-        Ratio::new(1, 1)
+        Some(Ratio::new(1, 1))
     );
     assert_eq!(
         round_denominator::<u32>(Ratio::new(100, 100), 100, 2, 0),
-        Ratio::new(1, 1)
+        Some(Ratio::new(1, 1))
     );
 }
 
@@ -76,19 +78,19 @@ fn rounding_down_no_offset() {
     use ::num::rational::Ratio;
     assert_eq!(
         round_denominator::<u8>(Ratio::new(1, 100), 2, 1, 0),
-        Ratio::new(0, 1)
+        Some(Ratio::new(0, 1))
     );
     assert_eq!(
         round_denominator::<u8>(Ratio::new(22, 100), 3, 2, 0),
-        Ratio::new(2, 9)
+        Some(Ratio::new(2, 9))
     );
     assert_eq!(
         round_denominator::<u8>(Ratio::new(49, 100), 100, 0, 0),
-        Ratio::new(0, 1)
+        Some(Ratio::new(0, 1))
     );
     assert_eq!(
         round_denominator::<u32>(Ratio::new(0, 100), 100, 2, 0),
-        Ratio::new(0, 1)
+        Some(Ratio::new(0, 1))
     );
 }
 
@@ -98,14 +100,254 @@ fn rounding_above_threshold() {
     use ::num::rational::Ratio;
     assert_eq!(
         round_denominator::<u32>(Ratio::new(1, 3), 10, 1, 0),
-        Ratio::new(3, 10)
+        Some(Ratio::new(3, 10))
     );
     assert_eq!(
         round_denominator::<u32>(Ratio::new(101, 300), 10, 1, 0),
-        Ratio::new(3, 10)
+        Some(Ratio::new(3, 10))
     );
     assert_eq!(
         round_denominator::<u32>(Ratio::new(101, 300), 10, 1, 0),
-        Ratio::new(3, 10)
+        Some(Ratio::new(3, 10))
+    );
+}
+
+#[test]
+fn rounding_overflow() {
+    use super::round_denominator;
+    use ::num::rational::Ratio;
+    // 16^2 does not fit in a `u8`
+    assert_eq!(round_denominator::<u8>(Ratio::new(1, 3), 16, 2, 1), None);
+    assert_eq!(
+        round_denominator::<u8>(Ratio::new(1, 3), 16, 1, 1),
+        Some(Ratio::new(5, 15))
+    );
+    // 12^2 fits, but 2/7 of it is 288/7
+    assert_eq!(round_denominator::<u8>(Ratio::new(2, 7), 12, 2, 0), None);
+}
+
+#[test]
+fn ratio_to_f64_exact() {
+    use super::ratio_to_f64;
+    use ::num::rational::Ratio;
+    assert_eq!(ratio_to_f64(&Ratio::new(0_u8, 1)), 0.0);
+    assert_eq!(ratio_to_f64(&Ratio::new(1_u8, 2)), 0.5);
+    assert_eq!(ratio_to_f64(&Ratio::new(255_u32, 255)), 1.0);
+}
+
+#[test]
+fn f64_to_ratio_denominators() {
+    use super::f64_to_ratio;
+    use ::num::rational::Ratio;
+    assert_eq!(f64_to_ratio::<u8>(0.5), Ratio::new(8, 15));
+    assert_eq!(f64_to_ratio::<u16>(0.5), Ratio::new(128, 255));
+    assert_eq!(f64_to_ratio::<u32>(0.5), Ratio::new(32768, 65535));
+    assert_eq!(
+        f64_to_ratio::<u64>(0.5),
+        Ratio::new(2_147_483_648, 4_294_967_295)
+    );
+}
+
+#[test]
+fn f64_to_ratio_clamps() {
+    use super::f64_to_ratio;
+    use ::num::rational::Ratio;
+    assert_eq!(f64_to_ratio::<u16>(-1.0), Ratio::new(0, 1));
+    assert_eq!(f64_to_ratio::<u16>(2.0), Ratio::new(1, 1));
+    assert_eq!(f64_to_ratio::<u16>(f64::NAN), Ratio::new(0, 1));
+}
+
+#[cfg(feature = "parse")]
+#[test]
+fn sht_to_hex_string() {
+    use super::sht::SHT;
+    for (input, output) in &[
+        // Test cases stolen from spec, without adjusting for rounding
+        ("r", "#FF0000"),
+        ("8r", "#AA0000"),
+        ("r3", "#FF4040"),
+        ("8r3", "#C04040"),
+        ("r6g", "#FF8000"),
+        ("8r6g", "#AA5500"),
+        ("8r6g3", "#C08040"),
+        ("8y3", "#C0C040"),
+        ("6", "#808080"),
+        ("0", "#000000"),
+        ("W", "#FFFFFF"),
+    ] {
+        assert_eq!(&input.parse::<SHT<u32>>().unwrap().to_hex_string(2), output)
+    }
+}
+
+#[cfg(feature = "parse")]
+#[test]
+fn sht_literal_matches_parser() {
+    use super::{sht::SHT, valid_sht_literal};
+    let alphabet = [
+        "", "0", "1", "6", "E", "X", "e", "r", "g", "b", "R", "c", "y", "M", "W", "w", ".",
+    ];
+    for first in &alphabet {
+        for second in &alphabet {
+            for third in &alphabet {
+                for fourth in &alphabet {
+                    let code = [*first, second, third, fourth].concat();
+                    assert_eq!(
+                        valid_sht_literal(&code),
+                        code.parse::<SHT<u32>>().is_ok(),
+                        "{:?}",
+                        code
+                    );
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn hex_literal_matches_parser() {
+    use super::{rgb::HexRGB, valid_hex_literal};
+    for code in &[
+        "#F00",
+        "#ff8811",
+        "#123456789",
+        "",
+        "#",
+        "F00",
+        "#F0",
+        "#G00",
+        "#F00F",
+        "##00",
+    ] {
+        assert_eq!(
+            valid_hex_literal(code),
+            code.parse::<HexRGB<u64>>().is_ok(),
+            "{:?}",
+            code
+        );
+    }
+}
+
+#[cfg(feature = "parse")]
+#[test]
+fn literal_macros() {
+    use crate::{hex, rgb::HexRGB, sht, SHT};
+    let colour: SHT<u8> = sht!("8r6g3");
+    assert_eq!(colour, "8r6g3".parse().unwrap());
+    let colour: HexRGB<u16> = hex!("#FF8811");
+    assert_eq!(colour, "#FF8811".parse().unwrap());
+}
+
+#[test]
+fn safe_denominator_limits() {
+    use super::{safe_denominator, OverflowError};
+    assert_eq!(safe_denominator(2_u8, 0), Ok(1));
+    assert_eq!(safe_denominator(2_u8, 7), Ok(128));
+    assert_eq!(safe_denominator(2_u8, 8), Err(OverflowError));
+    assert_eq!(safe_denominator(12_u8, 2), Ok(144));
+    assert_eq!(safe_denominator(12_u8, 3), Err(OverflowError));
+    assert_eq!(safe_denominator(16_u8, 1), Ok(16));
+    assert_eq!(safe_denominator(16_u8, 2), Err(OverflowError));
+
+    assert_eq!(safe_denominator(2_u16, 15), Ok(0x8000));
+    assert_eq!(safe_denominator(2_u16, 16), Err(OverflowError));
+    assert_eq!(safe_denominator(12_u16, 4), Ok(20736));
+    assert_eq!(safe_denominator(12_u16, 5), Err(OverflowError));
+    assert_eq!(safe_denominator(16_u16, 3), Ok(0x1000));
+    assert_eq!(safe_denominator(16_u16, 4), Err(OverflowError));
+
+    assert_eq!(safe_denominator(2_u32, 31), Ok(1 << 31));
+    assert_eq!(safe_denominator(2_u32, 32), Err(OverflowError));
+    assert_eq!(safe_denominator(12_u32, 8), Ok(429_981_696));
+    assert_eq!(safe_denominator(12_u32, 9), Err(OverflowError));
+    assert_eq!(safe_denominator(16_u32, 7), Ok(1 << 28));
+    assert_eq!(safe_denominator(16_u32, 8), Err(OverflowError));
+
+    assert_eq!(safe_denominator(2_u64, 63), Ok(1 << 63));
+    assert_eq!(safe_denominator(2_u64, 64), Err(OverflowError));
+    assert_eq!(safe_denominator(12_u64, 17), Ok(12_u64.pow(17)));
+    assert_eq!(safe_denominator(12_u64, 18), Err(OverflowError));
+    assert_eq!(safe_denominator(16_u64, 15), Ok(1 << 60));
+    assert_eq!(safe_denominator(16_u64, 16), Err(OverflowError));
+}
+
+#[test]
+fn hex_digit_limits_match_denominator() {
+    use super::rgb::{HexRGB, HexRGBA, ParseHexError};
+    // `0xFF` fits in a `u8`, even though `0x100` does not
+    let magenta = "#FF00FF".parse::<HexRGB<u8>>().unwrap();
+    assert_eq!(magenta, HexRGB::from_bytes([0xFF, 0x00, 0xFF]));
+    assert_eq!(magenta.to_string(), "#FF00FF");
+    for byte in 0..=u8::MAX {
+        let code = format!("#{:02X}{:02X}{:02X}", byte, 0, u8::MAX - byte);
+        assert_eq!(code.parse::<HexRGB<u8>>().unwrap().to_string(), code);
+    }
+    let translucent = "#FF00FF80".parse::<HexRGBA<u8>>().unwrap();
+    assert_eq!(translucent.to_string(), "#FF00FF80");
+    assert_eq!(
+        "#FFF000FFF".parse::<HexRGB<u8>>(),
+        Err(ParseHexError::Overflow)
+    );
+    assert!("#FFFF0000FFFF".parse::<HexRGB<u16>>().is_ok());
+    assert_eq!(
+        "#FFFFF00000FFFFF".parse::<HexRGB<u16>>(),
+        Err(ParseHexError::Overflow)
+    );
+    assert_eq!(magenta.lossless_sht_precision(), Some(1));
+}
+
+#[test]
+fn clamp_to_unit_interval() {
+    use super::clamp_unit;
+    use ::num::rational::Ratio;
+    assert_eq!(clamp_unit(Ratio::new(0_u32, 1)), Ratio::new(0, 1));
+    assert_eq!(clamp_unit(Ratio::new(2_u32, 3)), Ratio::new(2, 3));
+    assert_eq!(clamp_unit(Ratio::new(1_u32, 1)), Ratio::new(1, 1));
+    assert_eq!(clamp_unit(Ratio::new(7_u32, 3)), Ratio::new(1, 1));
+}
+
+#[test]
+fn rounding_modes_on_halfway_values() {
+    use super::{round_denominator_with, RoundingMode};
+    use ::num::rational::Ratio;
+    // 1/2 scaled to 0xF is 7.5, and 17/30 is 8.5
+    let round = |ratio, mode| round_denominator_with::<u32>(ratio, 16, 1, 1, mode);
+    let (seven_and_half, eight_and_half) = (Ratio::new(1, 2), Ratio::new(17, 30));
+    assert_eq!(
+        round(seven_and_half, RoundingMode::HalfUp),
+        Some(Ratio::new(8, 15))
+    );
+    assert_eq!(
+        round(eight_and_half, RoundingMode::HalfUp),
+        Some(Ratio::new(9, 15))
+    );
+    assert_eq!(
+        round(seven_and_half, RoundingMode::HalfEven),
+        Some(Ratio::new(8, 15))
+    );
+    assert_eq!(
+        round(eight_and_half, RoundingMode::HalfEven),
+        Some(Ratio::new(8, 15))
+    );
+    assert_eq!(
+        round(seven_and_half, RoundingMode::Truncate),
+        Some(Ratio::new(7, 15))
+    );
+    assert_eq!(
+        round(eight_and_half, RoundingMode::Truncate),
+        Some(Ratio::new(8, 15))
+    );
+    // values that are not halfway round to nearest, except when truncating
+    let just_above = Ratio::new(86, 150);
+    assert_eq!(
+        round(just_above, RoundingMode::HalfEven),
+        Some(Ratio::new(9, 15))
+    );
+    assert_eq!(
+        round(just_above, RoundingMode::Truncate),
+        Some(Ratio::new(8, 15))
+    );
+    assert_eq!(
+        round(Ratio::from_integer(1), RoundingMode::Truncate),
+        Some(Ratio::from_integer(1))
     );
 }
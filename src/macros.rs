@@ -0,0 +1,164 @@
+/// Constructs an [`SHT`](crate::SHT) from a string literal, checking at
+/// compile time that the literal is a well-formed SHT code.
+///
+/// This avoids writing `"8r6g3".parse().unwrap()` for colours that are known
+/// in advance, and turns a typo in the literal into a compile error instead of
+/// a panic.
+///
+/// The check at compile time covers the grammar of the code and the
+/// constraints that do not depend on the integer type, such as a primary
+/// colour with a shade of zero or a blend equal to the primary colour. If the
+/// check fails, compilation stops with an error while evaluating a constant,
+/// mentioning `"invalid SHT literal"`.
+///
+/// Some codes only become invalid once their digits are rounded to fit the
+/// integer type, for instance a tint so close to 1 that it rounds up to 1.
+/// Those are only detected at runtime, where the macro panics.
+///
+/// # Example
+/// ```
+/// use sht_colour::{sht, SHT};
+///
+/// let colour: SHT<u8> = sht!("8r6g3");
+///
+/// assert_eq!(colour, "8r6g3".parse().unwrap());
+/// ```
+///
+/// An invalid literal does not compile:
+/// ```compile_fail
+/// use sht_colour::{sht, SHT};
+///
+/// let colour: SHT<u8> = sht!("8r6r3");
+/// ```
+#[cfg(feature = "parse")]
+#[macro_export]
+macro_rules! sht {
+    ($code:literal) => {{
+        const _: () = ::core::assert!($crate::valid_sht_literal($code), "invalid SHT literal");
+        $code
+            .parse::<$crate::SHT<_>>()
+            .expect("SHT literal could not be represented by the integer type")
+    }};
+}
+
+/// Constructs a [`HexRGB`](crate::rgb::HexRGB) from a string literal,
+/// checking at compile time that the literal is a well-formed hex code.
+///
+/// The literal must be a `'#'` followed by a nonzero multiple of three hex
+/// digits. If not, compilation stops with an error while evaluating a
+/// constant, mentioning `"invalid hex literal"`.
+///
+/// Codes with too many digits for the integer type are only detected at
+/// runtime, where the macro panics.
+///
+/// # Example
+/// ```
+/// use sht_colour::{hex, rgb::HexRGB};
+///
+/// let colour: HexRGB<u16> = hex!("#FF8811");
+///
+/// assert_eq!(colour, "#FF8811".parse().unwrap());
+/// ```
+///
+/// An invalid literal does not compile:
+/// ```compile_fail
+/// use sht_colour::{hex, rgb::HexRGB};
+///
+/// let colour: HexRGB<u16> = hex!("#FF88");
+/// ```
+#[macro_export]
+macro_rules! hex {
+    ($code:literal) => {{
+        const _: () = ::core::assert!($crate::valid_hex_literal($code), "invalid hex literal");
+        $code
+            .parse::<$crate::rgb::HexRGB<_>>()
+            .expect("Hex literal could not be represented by the integer type")
+    }};
+}
+
+/// Checks at compile time whether a string is a well-formed hex code: a `'#'`
+/// followed by a nonzero multiple of three hex digits.
+pub const fn valid_hex_literal(code: &str) -> bool {
+    let bytes = code.as_bytes();
+    if bytes.len() < 4 || bytes[0] != b'#' || !(bytes.len() - 1).is_multiple_of(3) {
+        return false;
+    }
+    let mut index = 1;
+    while index < bytes.len() {
+        if !bytes[index].is_ascii_hexdigit() {
+            return false;
+        }
+        index += 1;
+    }
+    true
+}
+
+/// Checks at compile time whether a string is a well-formed SHT code, following
+/// the same grammar as the parser and rejecting any components that are
+/// invalid regardless of the integer type.
+pub const fn valid_sht_literal(code: &str) -> bool {
+    let bytes = code.as_bytes();
+    if bytes.len() == 1 && (bytes[0] == b'W' || bytes[0] == b'w') {
+        return true;
+    }
+    let (shade_end, shade_zero) = digit_run(bytes, 0);
+    let has_shade = shade_end > 0;
+    if shade_end == bytes.len() {
+        // a lone number is a tint, or a shade if it is zero
+        return has_shade;
+    }
+    let mut index = shade_end;
+    if let Some(primary) = primary_colour(bytes[index]) {
+        if has_shade && shade_zero {
+            return false;
+        }
+        index += 1;
+        let (blend_end, blend_zero) = digit_run(bytes, index);
+        if blend_end > index && blend_end < bytes.len() {
+            if let Some(direction) = primary_colour(bytes[blend_end]) {
+                if blend_zero || direction == primary {
+                    return false;
+                }
+                index = blend_end + 1;
+            }
+        }
+    } else if is_secondary_colour(bytes[index]) {
+        if has_shade && shade_zero {
+            return false;
+        }
+        index += 1;
+    } else {
+        return false;
+    }
+    let (tint_end, tint_zero) = digit_run(bytes, index);
+    tint_end == bytes.len() && !(tint_end > index && tint_zero)
+}
+
+/// Finds the end of a run of base-12 digits starting at an index, and whether
+/// every digit in the run is zero.
+const fn digit_run(bytes: &[u8], start: usize) -> (usize, bool) {
+    let mut index = start;
+    let mut all_zero = true;
+    while index < bytes.len()
+        && (bytes[index].is_ascii_digit() || matches!(bytes[index], b'X' | b'E' | b'x' | b'e'))
+    {
+        if bytes[index] != b'0' {
+            all_zero = false;
+        }
+        index += 1;
+    }
+    (index, all_zero)
+}
+
+/// Returns the lowercase letter for a primary colour, ignoring case.
+const fn primary_colour(byte: u8) -> Option<u8> {
+    match byte.to_ascii_lowercase() {
+        letter @ (b'r' | b'g' | b'b') => Some(letter),
+        _ => None,
+    }
+}
+
+/// Checks whether a byte is a letter for a secondary colour, ignoring case.
+const fn is_secondary_colour(byte: u8) -> bool {
+    matches!(byte.to_ascii_lowercase(), b'c' | b'y' | b'm')
+}
@@ -0,0 +1,184 @@
+use super::HexRGB;
+use ::num::{CheckedMul, Integer, ToPrimitive, Unsigned};
+
+/// Brightness of each of the six levels along each axis of the xterm
+/// 256-colour cube.
+const CUBE_LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+/// Finds the index of the colour cube level nearest an 8-bit channel.
+fn nearest_cube_level(channel: u8) -> u8 {
+    (0..CUBE_LEVELS.len() as u8)
+        .min_by_key(|&index| {
+            (i16::from(CUBE_LEVELS[usize::from(index)]) - i16::from(channel)).abs()
+        })
+        .expect("Colour cube has levels")
+}
+
+/// Escape sequence that resets all terminal text attributes, including
+/// colours.
+const RESET: &str = "\x1b[0m";
+
+/// Measures the squared distance between two 8-bit colours.
+pub(super) fn distance_squared(first: [u8; 3], second: [u8; 3]) -> u32 {
+    first
+        .iter()
+        .zip(second.iter())
+        .map(|(&a, &b)| (i32::from(a) - i32::from(b)).pow(2) as u32)
+        .sum()
+}
+
+impl<T> HexRGB<T>
+where
+    T: Unsigned + Integer + Clone + CheckedMul,
+{
+    /// Converts a [`HexRGB`] value to an escape sequence that sets the colour
+    /// of text in a terminal with 24-bit "truecolor" support.
+    ///
+    /// The sequence is `ESC [ 38 ; 2 ; R ; G ; B m` to set the foreground
+    /// colour, or `ESC [ 48 ; 2 ; R ; G ; B m` to set the background colour,
+    /// where `R`, `G` and `B` are the channels rounded to the nearest 8-bit
+    /// value, written in decimal.
+    ///
+    /// # Arguments
+    /// * `foreground` - Whether to set the colour of the text itself, rather
+    ///   than the background behind it.
+    ///
+    /// # Example
+    /// ```
+    /// use ::sht_colour::rgb::HexRGB;
+    ///
+    /// let orange = "#FF8811".parse::<HexRGB<u16>>().unwrap();
+    ///
+    /// assert_eq!(orange.to_ansi_truecolor(true), "\x1b[38;2;255;136;17m");
+    /// assert_eq!(orange.to_ansi_truecolor(false), "\x1b[48;2;255;136;17m");
+    /// ```
+    pub fn to_ansi_truecolor(self, foreground: bool) -> String
+    where
+        T: From<u8> + ToPrimitive,
+    {
+        let [red, green, blue] = self.to_bytes();
+        let layer = if foreground { 38 } else { 48 };
+        format!("\x1b[{};2;{};{};{}m", layer, red, green, blue)
+    }
+
+    /// Finds the nearest colour in the xterm 256-colour palette, for
+    /// terminals without truecolor support.
+    ///
+    /// Only the last 240 colours are considered, since the first 16 vary
+    /// between terminals. Colours 16 to 231 form a 6×6×6 cube, and colours
+    /// 232 to 255 form a ramp of greys. The nearest colour from the cube and
+    /// from the ramp are compared by their distance in 8-bit RGB, and the
+    /// nearer is returned, preferring the cube if they tie.
+    ///
+    /// The index is used in the escape sequence `ESC [ 38 ; 5 ; N m` to set
+    /// the foreground colour, or `ESC [ 48 ; 5 ; N m` for the background.
+    ///
+    /// # Example
+    /// ```
+    /// use ::sht_colour::rgb::HexRGB;
+    ///
+    /// let red = "#F00".parse::<HexRGB<u16>>().unwrap();
+    /// let grey = "#808080".parse::<HexRGB<u16>>().unwrap();
+    ///
+    /// assert_eq!(red.to_ansi_256(), 196);
+    /// assert_eq!(grey.to_ansi_256(), 244);
+    /// ```
+    pub fn to_ansi_256(self) -> u8
+    where
+        T: From<u8> + ToPrimitive,
+    {
+        let channels = self.to_bytes();
+
+        let [red, green, blue] = [
+            nearest_cube_level(channels[0]),
+            nearest_cube_level(channels[1]),
+            nearest_cube_level(channels[2]),
+        ];
+        let cube_colour = [
+            CUBE_LEVELS[usize::from(red)],
+            CUBE_LEVELS[usize::from(green)],
+            CUBE_LEVELS[usize::from(blue)],
+        ];
+        let cube_index = 16 + 36 * red + 6 * green + blue;
+
+        // the ramp runs from 8 to 238 in steps of 10
+        let average = channels
+            .iter()
+            .map(|&channel| u16::from(channel))
+            .sum::<u16>()
+            / 3;
+        let step = (average.saturating_sub(3) / 10).min(23) as u8;
+        let grey = 8 + 10 * step;
+        let grey_index = 232 + step;
+
+        if distance_squared(channels, [grey; 3]) < distance_squared(channels, cube_colour) {
+            grey_index
+        } else {
+            cube_index
+        }
+    }
+
+    /// Wraps text in escape sequences that colour it in a terminal, followed
+    /// by a sequence that resets the colour afterwards.
+    ///
+    /// This uses the foreground sequence from [`to_ansi_truecolor`], so it
+    /// assumes the terminal supports 24-bit "truecolor". Other terminals may
+    /// show the wrong colour or the raw escape sequence. Use [`paint_on`] to
+    /// also set the background colour.
+    ///
+    /// # Example
+    /// ```
+    /// use ::sht_colour::rgb::HexRGB;
+    ///
+    /// let red = "#F00".parse::<HexRGB<u16>>().unwrap();
+    ///
+    /// assert_eq!(red.paint("error"), "\x1b[38;2;255;0;0merror\x1b[0m");
+    /// ```
+    ///
+    /// [`to_ansi_truecolor`]: HexRGB::to_ansi_truecolor
+    /// [`paint_on`]: HexRGB::paint_on
+    pub fn paint(self, text: &str) -> String
+    where
+        T: From<u8> + ToPrimitive,
+    {
+        format!("{}{}{}", self.to_ansi_truecolor(true), text, RESET)
+    }
+
+    /// Wraps text in escape sequences that colour it in a terminal, against a
+    /// background of another colour, followed by a sequence that resets both
+    /// colours afterwards.
+    ///
+    /// As with [`paint`], this assumes the terminal supports 24-bit
+    /// "truecolor".
+    ///
+    /// # Arguments
+    /// * `text` - The text to colour.
+    /// * `background` - The colour behind the text.
+    ///
+    /// # Example
+    /// ```
+    /// use ::sht_colour::rgb::HexRGB;
+    ///
+    /// let white = "#FFF".parse::<HexRGB<u16>>().unwrap();
+    /// let blue = "#00F".parse::<HexRGB<u16>>().unwrap();
+    ///
+    /// assert_eq!(
+    ///     white.paint_on("note", blue),
+    ///     "\x1b[38;2;255;255;255m\x1b[48;2;0;0;255mnote\x1b[0m"
+    /// );
+    /// ```
+    ///
+    /// [`paint`]: HexRGB::paint
+    pub fn paint_on(self, text: &str, background: HexRGB<T>) -> String
+    where
+        T: From<u8> + ToPrimitive,
+    {
+        format!(
+            "{}{}{}{}",
+            self.to_ansi_truecolor(true),
+            background.to_ansi_truecolor(false),
+            text,
+            RESET
+        )
+    }
+}
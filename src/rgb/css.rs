@@ -0,0 +1,49 @@
+use super::{HexRGB, ParseHexError};
+use ::cssparser::{ParseError, Parser, Token};
+use ::num::{CheckedMul, Integer, Unsigned};
+use ::std::str::FromStr;
+
+/// Parses a [`HexRGB`] value from the next token of a CSS token stream, using
+/// the [`cssparser`] crate.
+///
+/// The token must be a hash token, such as `#FF8811` or `#f81`, which is parsed
+/// the same way as [`HexRGB::from_str`](FromStr::from_str). Other colour
+/// syntaxes, such as named colours or `rgb()` functions, are not supported.
+/// This lets hex colours be read from within larger CSS values, such as the
+/// value of a `border` property.
+///
+/// Only available with the `cssparser` feature.
+///
+/// # Example
+/// ```
+/// use ::cssparser::{Parser, ParserInput};
+/// use ::sht_colour::rgb::{parse_css_hex, HexRGB};
+///
+/// let mut input = ParserInput::new("solid #FF8811");
+/// let mut parser = Parser::new(&mut input);
+///
+/// parser.expect_ident_matching("solid").unwrap();
+/// let colour = parse_css_hex::<u16>(&mut parser).unwrap();
+///
+/// assert_eq!(colour, "#FF8811".parse().unwrap());
+/// assert!(parser.is_exhausted());
+/// ```
+///
+/// # Errors
+/// Will return `Err` if the next token is not a hash token, or if the hash
+/// token is not a valid hex code, in which case the [`ParseHexError`] is
+/// returned as a custom error.
+pub fn parse_css_hex<'i, T>(
+    input: &mut Parser<'i, '_>,
+) -> Result<HexRGB<T>, ParseError<'i, ParseHexError>>
+where
+    T: Unsigned + Integer + FromStr + From<u8> + Clone + CheckedMul,
+{
+    let location = input.current_source_location();
+    match input.next()? {
+        Token::Hash(value) | Token::IDHash(value) => format!("#{}", value)
+            .parse()
+            .map_err(|error| location.new_custom_error(error)),
+        token => Err(location.new_unexpected_token_error(token.clone())),
+    }
+}
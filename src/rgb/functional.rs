@@ -0,0 +1,248 @@
+use super::{clamp_unit, safe_denominator, HexRGB, HexRGBA};
+use ::num::{rational::Ratio, CheckedMul, Integer, One, ToPrimitive, Unsigned, Zero};
+use ::std::fmt::UpperHex;
+
+/// Represents possible errors parsing a [`HexRGB`] from CSS functional
+/// notation, such as `rgb(255, 136, 17)`.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+#[non_exhaustive]
+pub enum ParseCssError {
+    /// The string was not a call to the `rgb()` or `rgba()` function.
+    NotRgbFunction,
+    /// The function did not have three channels and an optional alpha
+    /// value, either all separated by commas, or separated by spaces with a
+    /// `'/'` before the alpha value.
+    InvalidArguments,
+    /// A channel or alpha value was not a number or percentage.
+    InvalidNumber,
+    /// A number had too many digits to be stored in the integer type.
+    Overflow,
+}
+
+/// Parses a CSS number or percentage into a ratio between 0 and 1, where a
+/// number is divided by `scale` and a percentage by 100.
+///
+/// Values outside the range 0 to 1 are clamped into it, as in CSS.
+///
+/// # Errors
+/// Will return `Err` if the value is not a number or percentage, or if it has
+/// too many digits for the integer type.
+fn parse_value<T>(value: &str, scale: u8) -> Result<Ratio<T>, ParseCssError>
+where
+    T: Unsigned + Integer + Clone + CheckedMul + From<u8>,
+{
+    let (number, scale) = match value.strip_suffix('%') {
+        Some(number) => (number, 100),
+        None => (value, scale),
+    };
+    let (negative, number) = match number.strip_prefix('-') {
+        Some(number) => (true, number),
+        None => (false, number.strip_prefix('+').unwrap_or(number)),
+    };
+    let (whole, fraction) = number.split_once('.').unwrap_or((number, ""));
+    let digits = format!("{}{}", whole, fraction);
+    if digits.is_empty() || !digits.bytes().all(|digit| digit.is_ascii_digit()) {
+        return Err(ParseCssError::InvalidNumber);
+    }
+    let numerator = T::from_str_radix(&digits, 10).map_err(|_| ParseCssError::Overflow)?;
+    let denominator = safe_denominator(T::from(10), fraction.len())
+        .ok()
+        .and_then(|power| power.checked_mul(&T::from(scale)))
+        .ok_or(ParseCssError::Overflow)?;
+    let ratio = Ratio::new(numerator, denominator);
+    Ok(if negative {
+        Ratio::zero()
+    } else {
+        clamp_unit(ratio)
+    })
+}
+
+/// Parses the channels and alpha value of a CSS `rgb()` or `rgba()` function,
+/// defaulting to an opaque colour if the alpha value is left out.
+///
+/// # Errors
+/// Will return `Err` if the string is not a valid function call, as described
+/// by [`ParseCssError`].
+fn parse_rgb_function<T>(s: &str) -> Result<HexRGBA<T>, ParseCssError>
+where
+    T: Unsigned + Integer + Clone + CheckedMul + From<u8>,
+{
+    let s = s.trim();
+    let name_end = s.find('(').ok_or(ParseCssError::NotRgbFunction)?;
+    let name = &s[..name_end];
+    if !(name.eq_ignore_ascii_case("rgb") || name.eq_ignore_ascii_case("rgba")) {
+        return Err(ParseCssError::NotRgbFunction);
+    }
+    let arguments = s[name_end + 1..]
+        .strip_suffix(')')
+        .ok_or(ParseCssError::NotRgbFunction)?;
+
+    // legacy syntax separates every value with commas, and modern syntax
+    // separates the channels with spaces and the alpha value with a slash
+    let (channels, alpha) = if arguments.contains(',') {
+        let mut values = arguments.split(',').map(str::trim).collect::<Vec<_>>();
+        let alpha = if values.len() == 4 {
+            values.pop()
+        } else {
+            None
+        };
+        (values, alpha)
+    } else {
+        let (channels, alpha) = match arguments.split_once('/') {
+            Some((channels, alpha)) => (channels, Some(alpha.trim())),
+            None => (arguments, None),
+        };
+        (channels.split_whitespace().collect(), alpha)
+    };
+    match channels[..] {
+        [red, green, blue] => Ok(HexRGBA::new(
+            parse_value(red, 255)?,
+            parse_value(green, 255)?,
+            parse_value(blue, 255)?,
+            alpha.map_or_else(|| Ok(Ratio::one()), |alpha| parse_value(alpha, 1))?,
+        )),
+        _ => Err(ParseCssError::InvalidArguments),
+    }
+}
+
+impl<T> HexRGB<T>
+where
+    T: Unsigned + Integer + Clone + CheckedMul,
+{
+    /// Parses a [`HexRGB`] value from CSS functional notation, such as
+    /// `rgb(255, 136, 17)`.
+    ///
+    /// Both the `rgb()` and `rgba()` functions are accepted, with either the
+    /// legacy syntax, where values are separated by commas, or the modern
+    /// syntax, where the channels are separated by spaces and the alpha value
+    /// follows a `'/'`, as in `rgb(255 136 17 / 50%)`. Any alpha value is
+    /// checked and then discarded; use [`HexRGBA::from_css`] to keep it.
+    ///
+    /// Each channel is a number out of 255, or a percentage, which may have a
+    /// decimal part. Numbers and percentages may be mixed. As in CSS, values
+    /// outside the range are clamped into it. Whitespace around values is
+    /// ignored, as is the case of the function name.
+    ///
+    /// # Example
+    /// ```
+    /// use ::sht_colour::{rgb::HexRGB, Ratio};
+    ///
+    /// let orange = "#FF8811".parse::<HexRGB<u32>>().unwrap();
+    ///
+    /// assert_eq!(HexRGB::from_css("rgb(255, 136, 17)"), Ok(orange));
+    /// assert_eq!(HexRGB::from_css("rgba(100%,136,17,0.5)"), Ok(orange));
+    /// assert_eq!(HexRGB::from_css("rgb(255 136 17 / 50%)"), Ok(orange));
+    /// assert_eq!(
+    ///     HexRGB::<u32>::from_css("rgb(100%, 50%, 0%)"),
+    ///     Ok(HexRGB::new(
+    ///         Ratio::from_integer(1),
+    ///         Ratio::new(1, 2),
+    ///         Ratio::from_integer(0)
+    ///     ))
+    /// );
+    /// ```
+    ///
+    /// # Errors
+    /// Will return `Err` if the string is not a valid `rgb()` or `rgba()`
+    /// function, as described by [`ParseCssError`].
+    pub fn from_css(s: &str) -> Result<HexRGB<T>, ParseCssError>
+    where
+        T: From<u8>,
+    {
+        parse_rgb_function(s).map(HexRGBA::to_hex_rgb)
+    }
+
+    /// Writes a [`HexRGB`] value as a CSS hex code, such as `#ff8812`.
+    ///
+    /// Each channel is rounded to the nearest 8-bit value, as in
+    /// [`HexRGB::to_bytes`]. The code is shortened to three digits, such as
+    /// `#fa0`, only when that loses nothing, as checked by
+    /// [`HexRGB::can_shorten`].
+    ///
+    /// The digits are lowercase, following the usual CSS style, as when
+    /// formatting with the alternate flag of the [`Display`] implementation.
+    /// Use [`str::to_uppercase`] on the result for uppercase digits.
+    ///
+    /// # Example
+    /// ```
+    /// use ::sht_colour::rgb::HexRGB;
+    ///
+    /// let orange = "#FF8812".parse::<HexRGB<u32>>().unwrap();
+    /// let amber = "#FFAA00".parse::<HexRGB<u32>>().unwrap();
+    ///
+    /// assert_eq!(orange.to_css_hex(), "#ff8812");
+    /// assert_eq!(amber.to_css_hex(), "#fa0");
+    /// ```
+    ///
+    /// [`Display`]: HexRGB#impl-Display
+    pub fn to_css_hex(&self) -> String
+    where
+        T: From<u8> + ToPrimitive + UpperHex,
+    {
+        let rounded = HexRGB::<T>::from_bytes(self.clone().to_bytes());
+        if rounded.can_shorten() {
+            format!("{:#.1}", rounded)
+        } else {
+            format!("{:#}", rounded)
+        }
+    }
+
+    /// Writes a [`HexRGB`] value in CSS functional notation, such as
+    /// `rgb(255, 136, 17)`.
+    ///
+    /// Each channel is rounded to the nearest 8-bit value, as in
+    /// [`HexRGB::to_rgb8`], and written with the legacy comma-separated
+    /// syntax, which every browser understands. The result can be read back
+    /// with [`HexRGB::from_css`].
+    ///
+    /// # Example
+    /// ```
+    /// use ::sht_colour::rgb::HexRGB;
+    ///
+    /// let orange = "#FF8811".parse::<HexRGB<u32>>().unwrap();
+    ///
+    /// assert_eq!(orange.to_css_rgb(), "rgb(255, 136, 17)");
+    /// assert_eq!(HexRGB::from_css(&orange.to_css_rgb()), Ok(orange));
+    /// ```
+    pub fn to_css_rgb(&self) -> String
+    where
+        T: ToPrimitive,
+    {
+        let (red, green, blue) = self.to_rgb8();
+        format!("rgb({}, {}, {})", red, green, blue)
+    }
+}
+
+impl<T> HexRGBA<T>
+where
+    T: Unsigned + Integer + Clone + CheckedMul,
+{
+    /// Parses a [`HexRGBA`] value from CSS functional notation, such as
+    /// `rgba(255, 136, 17, 0.5)`, in the same way as [`HexRGB::from_css`].
+    ///
+    /// The alpha value is a number from 0 to 1, or a percentage. If it is
+    /// left out, the colour is opaque.
+    ///
+    /// # Example
+    /// ```
+    /// use ::sht_colour::{rgb::HexRGBA, Ratio};
+    ///
+    /// let colour = HexRGBA::<u32>::from_css("rgb(255 136 17 / 25%)").unwrap();
+    ///
+    /// assert_eq!(colour.alpha(), Ratio::new(1, 4));
+    /// assert_eq!(
+    ///     HexRGBA::<u32>::from_css("rgb(255, 136, 17)").unwrap().alpha(),
+    ///     Ratio::from_integer(1)
+    /// );
+    /// ```
+    ///
+    /// # Errors
+    /// Will return `Err` if the string is not a valid `rgb()` or `rgba()`
+    /// function, as described by [`ParseCssError`].
+    pub fn from_css(s: &str) -> Result<HexRGBA<T>, ParseCssError>
+    where
+        T: From<u8>,
+    {
+        parse_rgb_function(s)
+    }
+}
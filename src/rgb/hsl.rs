@@ -0,0 +1,275 @@
+use super::HexRGB;
+use ::num::{CheckedMul, FromPrimitive, Integer, ToPrimitive, Unsigned};
+
+/// Converts sRGB channels on the unit interval to `[hue, saturation,
+/// lightness]`, with the hue in degrees in the range `[0, 360)` and the other
+/// two on the unit interval.
+///
+/// Greys, which have no hue, are given a hue and saturation of zero.
+pub(super) fn rgb_to_hsl([red, green, blue]: [f64; 3]) -> [f64; 3] {
+    let max = red.max(green).max(blue);
+    let min = red.min(green).min(blue);
+    let lightness = (max + min) / 2.0;
+    let chroma = max - min;
+    if chroma <= 0.0 {
+        return [0.0, 0.0, lightness];
+    }
+    let saturation = chroma / (1.0 - (2.0 * lightness - 1.0).abs());
+    let sextant = if max == red {
+        ((green - blue) / chroma).rem_euclid(6.0)
+    } else if max == green {
+        (blue - red) / chroma + 2.0
+    } else {
+        (red - green) / chroma + 4.0
+    };
+    [sextant * 60.0, saturation, lightness]
+}
+
+/// Converts `[hue, saturation, lightness]` to sRGB channels on the unit
+/// interval, the inverse of [`rgb_to_hsl`].
+///
+/// The hue is in degrees, and may lie outside `[0, 360)`.
+pub(super) fn hsl_to_rgb([hue, saturation, lightness]: [f64; 3]) -> [f64; 3] {
+    let chroma = (1.0 - (2.0 * lightness - 1.0).abs()) * saturation;
+    let sextant = (hue / 60.0).rem_euclid(6.0);
+    let second = chroma * (1.0 - (sextant % 2.0 - 1.0).abs());
+    let [red, green, blue] = match sextant as u8 {
+        0 => [chroma, second, 0.0],
+        1 => [second, chroma, 0.0],
+        2 => [0.0, chroma, second],
+        3 => [0.0, second, chroma],
+        4 => [second, 0.0, chroma],
+        _ => [chroma, 0.0, second],
+    };
+    let offset = lightness - chroma / 2.0;
+    [red + offset, green + offset, blue + offset]
+}
+
+/// Finds the average HSL hue of some colours, in degrees in the range
+/// `[0, 360)`.
+///
+/// Hues wrap around at 360 degrees, so they are averaged circularly: each hue
+/// is treated as a unit vector at that angle, and the result is the angle of
+/// their sum. So the average of 350 and 10 degrees is 0 degrees, rather than
+/// the 180 degrees of an ordinary average. Every colour counts equally,
+/// however saturated, except that greys have no hue and are left out.
+///
+/// Returns `None` if there are no colours other than greys, or if the hues
+/// cancel out so that there is no average, as for two opposite hues.
+///
+/// # Example
+/// ```
+/// use ::sht_colour::rgb::{mean_hue, HexRGB};
+///
+/// let colours = ["#F00", "#FF0", "#888"]
+///     .iter()
+///     .map(|s| s.parse::<HexRGB<u16>>().unwrap())
+///     .collect::<Vec<_>>();
+///
+/// assert!((mean_hue(&colours).unwrap() - 30.0).abs() < 1e-9);
+/// ```
+pub fn mean_hue<T>(colours: &[HexRGB<T>]) -> Option<f64>
+where
+    T: Unsigned + Integer + Clone + CheckedMul + ToPrimitive,
+{
+    let (cos_sum, sin_sum) = colours
+        .iter()
+        .map(|colour| rgb_to_hsl(colour.to_f64_channels()))
+        .filter(|[_, saturation, _]| *saturation > 0.0)
+        .map(|[hue, _, _]| hue.to_radians().sin_cos())
+        .fold((0.0, 0.0), |(cos_sum, sin_sum), (sin, cos)| {
+            (cos_sum + cos, sin_sum + sin)
+        });
+    // allow for rounding error in hues that should cancel exactly
+    if cos_sum.hypot(sin_sum) < 1e-9 {
+        return None;
+    }
+    Some(sin_sum.atan2(cos_sum).to_degrees().rem_euclid(360.0))
+}
+
+/// HSL saturation below which a colour is named as a grey, rather than by
+/// its hue.
+const GRAY_SATURATION: f64 = 0.1;
+
+/// HSL saturation below which a colour is called "muted".
+const MUTED_SATURATION: f64 = 0.4;
+
+/// HSL lightness below which a colour is called "dark".
+const DARK_LIGHTNESS: f64 = 0.3;
+
+/// HSL lightness above which a colour is called "light".
+const LIGHT_LIGHTNESS: f64 = 0.7;
+
+/// HSL lightness below which a grey is called "black".
+const BLACK_LIGHTNESS: f64 = 0.1;
+
+/// HSL lightness above which a grey is called "white".
+const WHITE_LIGHTNESS: f64 = 0.9;
+
+/// Names of hue families, each paired with the hue in degrees at which the
+/// next family begins.
+const HUE_FAMILIES: [(f64, &str); 8] = [
+    (15.0, "red"),
+    (45.0, "orange"),
+    (70.0, "yellow"),
+    (165.0, "green"),
+    (195.0, "cyan"),
+    (255.0, "blue"),
+    (285.0, "purple"),
+    (345.0, "magenta"),
+];
+
+/// Names the family of an HSL hue in degrees in the range `[0, 360)`.
+fn hue_family(hue: f64) -> &'static str {
+    HUE_FAMILIES
+        .iter()
+        .find(|(end, _)| hue < *end)
+        .map_or("red", |(_, name)| name)
+}
+
+impl<T> HexRGB<T>
+where
+    T: Unsigned + Integer + Clone + CheckedMul,
+{
+    /// Finds the colour halfway between two [`HexRGB`] values in HSL space,
+    /// travelling around the colour wheel rather than through it.
+    ///
+    /// The hue of the result is halfway along the shorter arc between the two
+    /// hues, so the midpoint of red and blue is magenta rather than green.
+    /// When the hues are exactly opposite, both arcs are equally short, and
+    /// the arc running forwards from the hue of `self` (from red towards
+    /// yellow) is chosen. Saturation and lightness are averaged directly.
+    ///
+    /// Greys have no hue, so when one colour is grey the hue of the other is
+    /// kept, and when both are grey the result is grey.
+    ///
+    /// The result is computed with floating point numbers, so it is only
+    /// approximately accurate.
+    ///
+    /// # Example
+    /// ```
+    /// use ::sht_colour::rgb::HexRGB;
+    ///
+    /// let red = "#F00".parse::<HexRGB<u16>>().unwrap();
+    /// let blue = "#00F".parse::<HexRGB<u16>>().unwrap();
+    ///
+    /// assert_eq!(red.hue_midpoint(blue).to_string(), "#FF00FF");
+    /// ```
+    pub fn hue_midpoint(self, other: HexRGB<T>) -> HexRGB<T>
+    where
+        T: ToPrimitive + FromPrimitive,
+    {
+        let [first_hue, first_saturation, first_lightness] = rgb_to_hsl(self.to_f64_channels());
+        let [second_hue, second_saturation, second_lightness] = rgb_to_hsl(other.to_f64_channels());
+        let hue = if first_saturation <= 0.0 {
+            second_hue
+        } else if second_saturation <= 0.0 {
+            first_hue
+        } else {
+            // signed difference in the range [-180, 180), favouring +180
+            let difference = (second_hue - first_hue + 180.0).rem_euclid(360.0) - 180.0;
+            let difference = if difference <= -180.0 {
+                180.0
+            } else {
+                difference
+            };
+            first_hue + difference / 2.0
+        };
+        HexRGB::from_f64_channels(hsl_to_rgb([
+            hue,
+            (first_saturation + second_saturation) / 2.0,
+            (first_lightness + second_lightness) / 2.0,
+        ]))
+    }
+
+    /// Finds where a [`HexRGB`] value lies on a circular colour wheel, as `x`
+    /// and `y` coordinates within the unit disc.
+    ///
+    /// The angle is the HSL hue, measured counterclockwise from red at the
+    /// right (the positive `x` axis), with `y` pointing upwards, so yellow is
+    /// at 60 degrees, green at 120 degrees, and blue at 240 degrees. The
+    /// distance from the centre is the HSL saturation, so fully saturated
+    /// colours lie on the edge of the wheel, and greys lie at the centre.
+    /// Lightness is not represented.
+    ///
+    /// # Example
+    /// ```
+    /// use ::sht_colour::rgb::HexRGB;
+    ///
+    /// let red = "#F00".parse::<HexRGB<u16>>().unwrap();
+    /// let grey = "#888".parse::<HexRGB<u16>>().unwrap();
+    ///
+    /// assert_eq!(red.wheel_position(), (1.0, 0.0));
+    /// assert_eq!(grey.wheel_position(), (0.0, 0.0));
+    /// ```
+    pub fn wheel_position(self) -> (f64, f64)
+    where
+        T: ToPrimitive,
+    {
+        let [hue, saturation, _] = rgb_to_hsl(self.to_f64_channels());
+        let (sin, cos) = hue.to_radians().sin_cos();
+        (saturation * cos, saturation * sin)
+    }
+
+    /// Describes a [`HexRGB`] value with an approximate name, such as
+    /// `"dark orange"` or `"muted blue"`, for use in alt text and tooltips.
+    ///
+    /// The name is built from the HSL hue, saturation and lightness of the
+    /// colour:
+    /// * Colours with a saturation below 0.1 are named as greys: `"black"`
+    ///   below a lightness of 0.1, `"white"` above 0.9, and otherwise
+    ///   `"gray"`, with the lightness adjectives below.
+    /// * Other colours are named by the family of their hue: `"red"` from 345
+    ///   to 15 degrees, then `"orange"` to 45, `"yellow"` to 70, `"green"` to
+    ///   165, `"cyan"` to 195, `"blue"` to 255, `"purple"` to 285, and
+    ///   `"magenta"` to 345.
+    /// * The hue is preceded by `"muted"` if the saturation is below 0.4.
+    /// * The name begins with `"dark"` if the lightness is below 0.3, or
+    ///   `"light"` if it is above 0.7.
+    ///
+    /// The thresholds are arbitrary, and the names are only a rough guide.
+    ///
+    /// # Example
+    /// ```
+    /// use ::sht_colour::rgb::HexRGB;
+    ///
+    /// let orange = "#FF8811".parse::<HexRGB<u16>>().unwrap();
+    /// let navy = "#224".parse::<HexRGB<u16>>().unwrap();
+    /// let grey = "#888".parse::<HexRGB<u16>>().unwrap();
+    ///
+    /// assert_eq!(orange.approximate_name(), "orange");
+    /// assert_eq!(navy.approximate_name(), "dark muted blue");
+    /// assert_eq!(grey.approximate_name(), "gray");
+    /// ```
+    pub fn approximate_name(self) -> String
+    where
+        T: ToPrimitive,
+    {
+        let [hue, saturation, lightness] = rgb_to_hsl(self.to_f64_channels());
+        let (hue_name, saturation_name) = if saturation < GRAY_SATURATION {
+            if lightness < BLACK_LIGHTNESS {
+                return "black".to_owned();
+            } else if lightness > WHITE_LIGHTNESS {
+                return "white".to_owned();
+            }
+            ("gray", None)
+        } else if saturation < MUTED_SATURATION {
+            (hue_family(hue), Some("muted"))
+        } else {
+            (hue_family(hue), None)
+        };
+        let lightness_name = if lightness < DARK_LIGHTNESS {
+            Some("dark")
+        } else if lightness > LIGHT_LIGHTNESS {
+            Some("light")
+        } else {
+            None
+        };
+        lightness_name
+            .into_iter()
+            .chain(saturation_name)
+            .chain(Some(hue_name))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
@@ -0,0 +1,136 @@
+use super::{sht::ColourChannel, HexRGB};
+use ::num::{rational::Ratio, CheckedMul, Integer, One, Unsigned, Zero};
+
+impl<T> HexRGB<T>
+where
+    T: Unsigned + Integer + Clone + CheckedMul,
+{
+    /// Converts a [`HexRGB`] value to the hue, whiteness and blackness of the
+    /// HWB colour model from CSS Color 4, in that order.
+    ///
+    /// The hue is measured in turns rather than degrees, so it lies in the
+    /// range `[0, 1)`, with red at 0, green at 1/3 and blue at 2/3. Multiply
+    /// it by 360 for the hue of a CSS `hwb()` colour. Greys have no hue, and
+    /// are given a hue of 0.
+    ///
+    /// Whiteness is the brightness of the dimmest channel, and blackness is
+    /// how far the brightest channel is from full brightness. These are
+    /// closely related to [`SHT`] components: whiteness is exactly the tint,
+    /// and blackness is `(1 - shade) * (1 - tint)` for colours other than
+    /// greys. See [`SHT::to_hwb_exact`] for details.
+    ///
+    /// # Example
+    /// ```
+    /// use ::sht_colour::{rgb::HexRGB, Ratio};
+    ///
+    /// let red = "#F00".parse::<HexRGB<u32>>().unwrap();
+    /// let zero = Ratio::from_integer(0);
+    ///
+    /// assert_eq!(red.to_hwb(), (zero, zero, zero));
+    /// ```
+    ///
+    /// [`SHT`]: super::sht::SHT
+    /// [`SHT::to_hwb_exact`]: super::sht::SHT::to_hwb_exact
+    pub fn to_hwb(self) -> (Ratio<T>, Ratio<T>, Ratio<T>)
+    where
+        T: From<u8>,
+    {
+        let [(minimum, _), (middle, mid_channel), (maximum, max_channel)] = self.sorted_channels();
+        let whiteness = minimum.clone();
+        let blackness = <Ratio<T>>::one() - maximum.clone();
+        if maximum == minimum {
+            return (Ratio::zero(), whiteness, blackness);
+        }
+
+        // position of each primary in sixths of a turn
+        let position = |channel| -> T {
+            match channel {
+                ColourChannel::Red => 0.into(),
+                ColourChannel::Green => 2.into(),
+                ColourChannel::Blue => 4.into(),
+            }
+        };
+        let offset = (middle - minimum.clone()) / (maximum - minimum);
+        let primary = Ratio::from_integer(position(max_channel));
+        // the middle channel is either the next primary going forwards around
+        // the wheel (red to green to blue), or the previous one
+        let forwards = position(mid_channel) == (position(max_channel) + 2.into()) % 6.into();
+        let sixths = if forwards || offset.is_zero() {
+            primary + offset
+        } else if primary.is_zero() {
+            Ratio::from_integer(6.into()) - offset
+        } else {
+            primary - offset
+        };
+        (sixths / T::from(6), whiteness, blackness)
+    }
+
+    /// Constructs a [`HexRGB`] value from the hue, whiteness and blackness of
+    /// the HWB colour model from CSS Color 4.
+    ///
+    /// As in [`to_hwb`], the hue is measured in turns, and only its fractional
+    /// part is used. If the whiteness and blackness add to 1 or more, then the
+    /// colour is a grey, and they are scaled down proportionally until they
+    /// add to 1, as required by the CSS spec.
+    ///
+    /// # Arguments
+    /// * `hue` - The hue, as a fraction of a turn starting from red.
+    /// * `whiteness` - The amount of white mixed into the colour.
+    /// * `blackness` - The amount of black mixed into the colour.
+    ///
+    /// # Example
+    /// ```
+    /// use ::sht_colour::{rgb::HexRGB, Ratio};
+    ///
+    /// let pale_blue = <HexRGB<u32>>::from_hwb(
+    ///     Ratio::new(2, 3),
+    ///     Ratio::new(2, 5),
+    ///     Ratio::from_integer(0),
+    /// );
+    /// assert_eq!(pale_blue, "#6666FF".parse().unwrap());
+    ///
+    /// let grey = <HexRGB<u32>>::from_hwb(
+    ///     Ratio::from_integer(0),
+    ///     Ratio::new(3, 5),
+    ///     Ratio::new(9, 10),
+    /// );
+    /// assert_eq!(grey, "#666666".parse().unwrap());
+    /// ```
+    ///
+    /// [`to_hwb`]: HexRGB::to_hwb
+    pub fn from_hwb(hue: Ratio<T>, whiteness: Ratio<T>, blackness: Ratio<T>) -> HexRGB<T>
+    where
+        T: From<u8>,
+    {
+        let total = whiteness.clone() + blackness.clone();
+        if total >= Ratio::one() {
+            let grey = whiteness / total;
+            return HexRGB::new(grey.clone(), grey.clone(), grey);
+        }
+
+        let sixths = hue.fract() * T::from(6);
+        let sector = sixths.to_integer();
+        let rising = sixths.fract();
+        let falling = <Ratio<T>>::one() - rising.clone();
+        let (full, none) = (Ratio::one(), Ratio::zero());
+        let sector_is = |n: u8| sector == T::from(n);
+        let (red, green, blue) = if sector_is(0) {
+            (full, rising, none)
+        } else if sector_is(1) {
+            (falling, full, none)
+        } else if sector_is(2) {
+            (none, full, rising)
+        } else if sector_is(3) {
+            (none, falling, full)
+        } else if sector_is(4) {
+            (rising, none, full)
+        } else {
+            (full, none, falling)
+        };
+
+        // scale the pure hue between the whiteness and the blackness
+        let range = <Ratio<T>>::one() - total;
+        let channel = |pure: Ratio<T>| pure * range.clone() + whiteness.clone();
+        HexRGB::new(channel(red), channel(green), channel(blue))
+    }
+}
@@ -0,0 +1,1158 @@
+use super::{f64_to_ratio, ratio_to_f64, sht, HexRGB, HexRGBA, RGB};
+use ::num::{rational::Ratio, CheckedMul, FromPrimitive, Integer, ToPrimitive, Unsigned};
+
+/// Reference white of the D65 standard illuminant, in CIE XYZ coordinates
+/// scaled so that its luminance `Y` is 1.
+pub(super) const D65_WHITE: [f64; 3] = [0.950_47, 1.0, 1.088_83];
+
+/// Decodes a gamma-encoded sRGB channel into linear light, using the sRGB
+/// transfer function (including its linear segment near black).
+pub(super) fn srgb_to_linear(channel: f64) -> f64 {
+    if channel <= 0.040_45 {
+        channel / 12.92
+    } else {
+        ((channel + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+/// Encodes a linear-light channel with the sRGB transfer function, the
+/// inverse of [`srgb_to_linear`].
+pub(super) fn linear_to_srgb(channel: f64) -> f64 {
+    if channel <= 0.003_130_8 {
+        channel * 12.92
+    } else {
+        1.055 * channel.powf(1.0 / 2.4) - 0.055
+    }
+}
+
+/// Converts linear-light sRGB channels to CIE XYZ coordinates, relative to a
+/// D65 white point.
+pub(super) fn linear_to_xyz([red, green, blue]: [f64; 3]) -> [f64; 3] {
+    [
+        0.412_456_4 * red + 0.357_576_1 * green + 0.180_437_5 * blue,
+        0.212_672_9 * red + 0.715_152_2 * green + 0.072_175_0 * blue,
+        0.019_333_9 * red + 0.119_192_0 * green + 0.950_304_1 * blue,
+    ]
+}
+
+/// Converts CIE XYZ coordinates (relative to a D65 white point) to
+/// linear-light sRGB channels, the inverse of [`linear_to_xyz`].
+///
+/// Colours outside the sRGB gamut give channels outside the unit interval.
+pub(super) fn xyz_to_linear([x, y, z]: [f64; 3]) -> [f64; 3] {
+    [
+        3.240_454_836 * x - 1.537_138_850 * y - 0.498_531_547 * z,
+        -0.969_266_390 * x + 1.876_010_929 * y + 0.041_556_082 * z,
+        0.055_643_420 * x - 0.204_025_854 * y + 1.057_225_162 * z,
+    ]
+}
+
+/// Threshold between the cube-root and linear segments of the CIELAB
+/// companding function.
+const LAB_EPSILON: f64 = 6.0 / 29.0;
+
+/// Converts CIE XYZ coordinates to CIELAB `[L*, a*, b*]`, relative to a D65
+/// white point.
+pub(super) fn xyz_to_lab(xyz: [f64; 3]) -> [f64; 3] {
+    let compand = |t: f64| {
+        if t > LAB_EPSILON.powi(3) {
+            t.cbrt()
+        } else {
+            t / (3.0 * LAB_EPSILON.powi(2)) + 4.0 / 29.0
+        }
+    };
+    let [x, y, z] = [
+        compand(xyz[0] / D65_WHITE[0]),
+        compand(xyz[1] / D65_WHITE[1]),
+        compand(xyz[2] / D65_WHITE[2]),
+    ];
+    [116.0 * y - 16.0, 500.0 * (x - y), 200.0 * (y - z)]
+}
+
+/// Converts CIELAB `[L*, a*, b*]` to CIE XYZ coordinates, relative to a D65
+/// white point. The inverse of [`xyz_to_lab`].
+pub(super) fn lab_to_xyz([lightness, a, b]: [f64; 3]) -> [f64; 3] {
+    let expand = |t: f64| {
+        if t > LAB_EPSILON {
+            t.powi(3)
+        } else {
+            3.0 * LAB_EPSILON.powi(2) * (t - 4.0 / 29.0)
+        }
+    };
+    let y = (lightness + 16.0) / 116.0;
+    [
+        D65_WHITE[0] * expand(y + a / 500.0),
+        D65_WHITE[1] * expand(y),
+        D65_WHITE[2] * expand(y - b / 200.0),
+    ]
+}
+
+/// Converts gamma-encoded sRGB channels to CIELAB.
+pub(super) fn srgb_to_lab([red, green, blue]: [f64; 3]) -> [f64; 3] {
+    xyz_to_lab(linear_to_xyz([
+        srgb_to_linear(red),
+        srgb_to_linear(green),
+        srgb_to_linear(blue),
+    ]))
+}
+
+/// Converts CIELAB to gamma-encoded sRGB channels.
+///
+/// Colours outside the sRGB gamut give channels outside the unit interval.
+pub(super) fn lab_to_srgb(lab: [f64; 3]) -> [f64; 3] {
+    let [red, green, blue] = xyz_to_linear(lab_to_xyz(lab));
+    [
+        linear_to_srgb(red),
+        linear_to_srgb(green),
+        linear_to_srgb(blue),
+    ]
+}
+
+/// Measures the CIE 1976 colour difference, `ΔE*ab`, between two CIELAB
+/// colours, which is their Euclidean distance.
+pub(super) fn delta_e_1976(first: [f64; 3], second: [f64; 3]) -> f64 {
+    first
+        .iter()
+        .zip(second.iter())
+        .map(|(a, b)| (a - b).powi(2))
+        .sum::<f64>()
+        .sqrt()
+}
+
+/// Measures the CIEDE2000 colour difference, `ΔE00`, between two CIELAB
+/// colours, with the parametric weights `kL`, `kC` and `kH` all 1.
+///
+/// This follows the formula as presented by Sharma, Wu and Dalal in "The
+/// CIEDE2000 Color-Difference Formula: Implementation Notes, Supplementary
+/// Test Data, and Mathematical Observations" (2005), including their
+/// treatment of hue angles for colours with no chroma.
+pub(super) fn delta_e_2000(
+    [lightness_1, a_1, b_1]: [f64; 3],
+    [lightness_2, a_2, b_2]: [f64; 3],
+) -> f64 {
+    let pow_25_7 = 25.0_f64.powi(7);
+    let mean_chroma = (a_1.hypot(b_1) + a_2.hypot(b_2)) / 2.0;
+    let g = 0.5 * (1.0 - (mean_chroma.powi(7) / (mean_chroma.powi(7) + pow_25_7)).sqrt());
+    // adjust a* so that neutral colours have less effect on hue
+    let (a_1, a_2) = ((1.0 + g) * a_1, (1.0 + g) * a_2);
+    let (chroma_1, chroma_2) = (a_1.hypot(b_1), a_2.hypot(b_2));
+    let hue = |a: f64, b: f64| {
+        if a == 0.0 && b == 0.0 {
+            0.0
+        } else {
+            b.atan2(a).to_degrees().rem_euclid(360.0)
+        }
+    };
+    let (hue_1, hue_2) = (hue(a_1, b_1), hue(a_2, b_2));
+    let achromatic = chroma_1 * chroma_2 == 0.0;
+
+    let delta_lightness = lightness_2 - lightness_1;
+    let delta_chroma = chroma_2 - chroma_1;
+    let delta_hue_angle = if achromatic {
+        0.0
+    } else if (hue_2 - hue_1).abs() <= 180.0 {
+        hue_2 - hue_1
+    } else if hue_2 > hue_1 {
+        hue_2 - hue_1 - 360.0
+    } else {
+        hue_2 - hue_1 + 360.0
+    };
+    let delta_hue = 2.0 * (chroma_1 * chroma_2).sqrt() * (delta_hue_angle / 2.0).to_radians().sin();
+
+    let mean_lightness = (lightness_1 + lightness_2) / 2.0;
+    let mean_chroma = (chroma_1 + chroma_2) / 2.0;
+    let mean_hue = if achromatic {
+        hue_1 + hue_2
+    } else if (hue_1 - hue_2).abs() <= 180.0 {
+        (hue_1 + hue_2) / 2.0
+    } else if hue_1 + hue_2 < 360.0 {
+        (hue_1 + hue_2 + 360.0) / 2.0
+    } else {
+        (hue_1 + hue_2 - 360.0) / 2.0
+    };
+
+    let cos_degrees = |angle: f64| angle.to_radians().cos();
+    let t = 1.0 - 0.17 * cos_degrees(mean_hue - 30.0)
+        + 0.24 * cos_degrees(2.0 * mean_hue)
+        + 0.32 * cos_degrees(3.0 * mean_hue + 6.0)
+        - 0.20 * cos_degrees(4.0 * mean_hue - 63.0);
+    let weight_lightness = 1.0
+        + 0.015 * (mean_lightness - 50.0).powi(2) / (20.0 + (mean_lightness - 50.0).powi(2)).sqrt();
+    let weight_chroma = 1.0 + 0.045 * mean_chroma;
+    let weight_hue = 1.0 + 0.015 * mean_chroma * t;
+    // rotate the ellipses of equal difference in the blue region
+    let delta_theta = 30.0 * (-((mean_hue - 275.0) / 25.0).powi(2)).exp();
+    let rotation_chroma = 2.0 * (mean_chroma.powi(7) / (mean_chroma.powi(7) + pow_25_7)).sqrt();
+    let rotation = -(2.0 * delta_theta).to_radians().sin() * rotation_chroma;
+
+    let (lightness_term, chroma_term, hue_term) = (
+        delta_lightness / weight_lightness,
+        delta_chroma / weight_chroma,
+        delta_hue / weight_hue,
+    );
+    (lightness_term.powi(2)
+        + chroma_term.powi(2)
+        + hue_term.powi(2)
+        + rotation * chroma_term * hue_term)
+        .sqrt()
+}
+
+/// Checks whether gamma-encoded sRGB channels lie within the unit interval,
+/// allowing for a little floating point error.
+fn in_gamut(channels: [f64; 3]) -> bool {
+    let tolerance = 1e-9;
+    channels
+        .iter()
+        .all(|&channel| (-tolerance..=1.0 + tolerance).contains(&channel))
+}
+
+/// Changes the lightness of a CIELAB colour, keeping its hue, and keeping its
+/// chroma unless that would leave the sRGB gamut, in which case the chroma is
+/// reduced as little as possible. Returns gamma-encoded sRGB channels.
+fn with_lightness([_, a, b]: [f64; 3], lightness: f64) -> [f64; 3] {
+    let (chroma, hue) = (a.hypot(b), b.atan2(a));
+    let at_chroma = |chroma: f64| lab_to_srgb([lightness, chroma * hue.cos(), chroma * hue.sin()]);
+    if in_gamut(at_chroma(chroma)) {
+        return at_chroma(chroma);
+    }
+    // binary search for the largest chroma still in gamut
+    let (mut low, mut high) = (0.0, chroma);
+    for _ in 0..48 {
+        let middle = (low + high) / 2.0;
+        if in_gamut(at_chroma(middle)) {
+            low = middle;
+        } else {
+            high = middle;
+        }
+    }
+    at_chroma(low)
+}
+
+/// Calculates the WCAG contrast ratio between two relative luminances, from
+/// 1 for identical luminances to 21 for black against white.
+fn contrast_ratio(first: f64, second: f64) -> f64 {
+    (first.max(second) + 0.05) / (first.min(second) + 0.05)
+}
+
+/// How the channels of a colour are encoded as floating point numbers, when
+/// converting a [`HexRGB`] to or from an array with [`HexRGB::to_array`] and
+/// [`HexRGB::from_array`].
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+#[non_exhaustive]
+pub enum Encoding {
+    /// Channels encoded with the sRGB transfer function, exactly as in a hex
+    /// code. Equal steps in these channels look roughly like equal steps in
+    /// brightness, which suits storing and displaying colours, but blending
+    /// or averaging them gives results that are too dark.
+    Srgb,
+    /// Channels proportional to the intensity of the light, decoded with the
+    /// exact sRGB transfer function, including its linear segment near black.
+    /// This suits blending, lighting and other arithmetic on light.
+    Linear,
+}
+
+/// Finds the mean of some colours in linear light, as if their light were
+/// physically mixed, such as when shrinking an image.
+///
+/// Each colour is decoded from sRGB into linear light, the channels are
+/// averaged, and the result is encoded back into sRGB. Averaging the sRGB
+/// channels directly instead would weight darker colours too heavily, since
+/// sRGB spends more of its range on dark shades, so the average would come
+/// out too dark. For instance, the linear mean of black and white is
+/// `#BCBCBC`, whereas averaging their channels gives `#808080`.
+///
+/// Returns `None` if there are no colours.
+///
+/// # Example
+/// ```
+/// use ::sht_colour::rgb::{average_linear, HexRGB};
+///
+/// let colours = ["#000", "#FFF"]
+///     .iter()
+///     .map(|s| s.parse::<HexRGB<u32>>().unwrap())
+///     .collect::<Vec<_>>();
+///
+/// assert_eq!(average_linear(&colours).unwrap().to_bytes(), [0xBC; 3]);
+/// ```
+pub fn average_linear<T>(colours: &[HexRGB<T>]) -> Option<HexRGB<T>>
+where
+    T: Unsigned + Integer + Clone + CheckedMul + ToPrimitive + FromPrimitive,
+{
+    if colours.is_empty() {
+        return None;
+    }
+    let sums = colours.iter().map(|colour| colour.to_f64_channels()).fold(
+        [0.0; 3],
+        |[red, green, blue], channels| {
+            [
+                red + srgb_to_linear(channels[0]),
+                green + srgb_to_linear(channels[1]),
+                blue + srgb_to_linear(channels[2]),
+            ]
+        },
+    );
+    let mean = |sum: f64| linear_to_srgb(sum / colours.len() as f64);
+    Some(HexRGB::from_f64_channels([
+        mean(sums[0]),
+        mean(sums[1]),
+        mean(sums[2]),
+    ]))
+}
+
+/// Difference in CIELAB lightness, `L*`, between the background of a
+/// [`ChipTheme`] and its border.
+const BORDER_LIGHTNESS_STEP: f64 = 10.0;
+
+/// The colours for a "chip" or badge, all derived from one colour, as created
+/// by [`HexRGB::chip_theme`].
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub struct ChipTheme<T>
+where
+    T: Unsigned + Integer + Clone + CheckedMul,
+{
+    /// The colour filling the chip.
+    pub background: HexRGB<T>,
+    /// The colour of text on the chip, either black or white.
+    pub text: HexRGB<T>,
+    /// The colour of the chip's outline, slightly darker or lighter than the
+    /// background.
+    pub border: HexRGB<T>,
+}
+
+impl<T> HexRGB<T>
+where
+    T: Unsigned + Integer + Clone + CheckedMul,
+{
+    /// Generates a ramp of colours with the same hue as this one, whose
+    /// lightnesses are evenly spaced to the human eye.
+    ///
+    /// Lightness is measured as `L*` in the CIELAB colour space (with a D65
+    /// white point), which is designed so that equal steps in `L*` look like
+    /// equal steps in lightness. The ramp runs from lightest to darkest, with
+    /// `L*` values evenly spaced strictly between 0 (black) and 100 (white).
+    ///
+    /// Hue is held constant, and so is chroma (colourfulness) wherever the
+    /// sRGB gamut allows it. Very light or dark colours often cannot be as
+    /// colourful as the original, so chroma is reduced as little as possible
+    /// to keep them displayable.
+    ///
+    /// # Arguments
+    /// * `steps` - How many colours to generate.
+    ///
+    /// # Example
+    /// ```
+    /// use ::sht_colour::rgb::HexRGB;
+    ///
+    /// let red = "#F00".parse::<HexRGB<u32>>().unwrap();
+    /// let ramp = red.lightness_ramp(3);
+    ///
+    /// assert_eq!(ramp.len(), 3);
+    /// // The middle of the ramp is a slightly darker red
+    /// assert_eq!(format!("{:1}", ramp[1]), "#E00");
+    /// ```
+    pub fn lightness_ramp(self, steps: usize) -> Vec<HexRGB<T>>
+    where
+        T: ToPrimitive + FromPrimitive,
+    {
+        let lab = srgb_to_lab(self.to_f64_channels());
+        (0..steps)
+            .map(|index| {
+                let lightness = 100.0 * (steps - index) as f64 / (steps + 1) as f64;
+                HexRGB::from_f64_channels(with_lightness(lab, lightness))
+            })
+            .collect()
+    }
+
+    /// Generates a named scale of colours with the same hue as this one, as
+    /// used for design tokens, where stop 50 is the lightest and stop 900 the
+    /// darkest.
+    ///
+    /// Each stop is mapped to a lightness `L*` in the CIELAB colour space of
+    /// `100 - stop / 10`, so stop 50 has an `L*` of 95, stop 500 has an `L*`
+    /// of 50, and stop 900 has an `L*` of 10. Stops above 1000 are treated as
+    /// 1000, which is black. Since equal steps in `L*` look like equal steps
+    /// in lightness, evenly spaced stops give an evenly spaced scale.
+    ///
+    /// As in [`lightness_ramp`], hue is held constant, and chroma is reduced
+    /// as little as possible to keep each colour within the sRGB gamut.
+    ///
+    /// # Arguments
+    /// * `stops` - The names of the stops to generate, in any order.
+    ///
+    /// # Example
+    /// ```
+    /// use ::sht_colour::rgb::HexRGB;
+    ///
+    /// let red = "#F00".parse::<HexRGB<u32>>().unwrap();
+    /// let scale = red.tonal_scale(&[50, 500, 900]);
+    ///
+    /// let stops = scale.iter().map(|(stop, _)| *stop).collect::<Vec<_>>();
+    /// assert_eq!(stops, [50, 500, 900]);
+    /// assert_eq!(scale[1].1.to_bytes(), [0xEF, 0x00, 0x00]);
+    /// ```
+    ///
+    /// [`lightness_ramp`]: HexRGB::lightness_ramp
+    pub fn tonal_scale(self, stops: &[u16]) -> Vec<(u16, HexRGB<T>)>
+    where
+        T: ToPrimitive + FromPrimitive,
+    {
+        let lab = srgb_to_lab(self.to_f64_channels());
+        stops
+            .iter()
+            .map(|&stop| {
+                let lightness = 100.0 - f64::from(stop.min(1000)) / 10.0;
+                (
+                    stop,
+                    HexRGB::from_f64_channels(with_lightness(lab, lightness)),
+                )
+            })
+            .collect()
+    }
+
+    /// Calculates the relative luminance of a [`HexRGB`] value, assuming it is
+    /// encoded as sRGB, which is the case for almost all colours on the web.
+    ///
+    /// Relative luminance is the `Y` coordinate of the CIE XYZ colour space,
+    /// from 0 for black to 1 for white. The channels are decoded into linear
+    /// light with the sRGB transfer function, then weighted by how bright each
+    /// primary appears. This is the luminance used by the WCAG contrast
+    /// ratio.
+    ///
+    /// # Example
+    /// ```
+    /// use ::sht_colour::rgb::HexRGB;
+    ///
+    /// let white = "#FFF".parse::<HexRGB<u16>>().unwrap();
+    /// let green = "#0F0".parse::<HexRGB<u16>>().unwrap();
+    ///
+    /// assert!((white.relative_luminance() - 1.0).abs() < 1e-6);
+    /// assert!((green.relative_luminance() - 0.7152).abs() < 1e-4);
+    /// ```
+    pub fn relative_luminance(self) -> f64
+    where
+        T: ToPrimitive,
+    {
+        let [red, green, blue] = self.to_f64_channels();
+        let [_, luminance, _] = linear_to_xyz([
+            srgb_to_linear(red),
+            srgb_to_linear(green),
+            srgb_to_linear(blue),
+        ]);
+        luminance
+    }
+
+    /// Calculates the relative luminance of a [`HexRGB`] value encoded with a
+    /// pure power-law transfer function, instead of sRGB.
+    ///
+    /// Each channel is decoded into linear light by raising it to the power
+    /// of `gamma`, then the channels are weighted the same way as in
+    /// [`relative_luminance`]. Use this for content mastered with a plain
+    /// gamma curve, such as gamma 2.2 or the 2.4 of BT.1886 displays, or use a
+    /// `gamma` of 1 if the channels already store linear light. Use
+    /// [`relative_luminance`] for ordinary sRGB colours, since its transfer
+    /// function has a linear segment near black that no power curve matches.
+    ///
+    /// # Arguments
+    /// * `gamma` - The exponent of the transfer function.
+    ///
+    /// # Example
+    /// ```
+    /// use ::sht_colour::rgb::HexRGB;
+    ///
+    /// let grey = "#808080".parse::<HexRGB<u16>>().unwrap();
+    ///
+    /// let linear = grey.relative_luminance_with_gamma(1.0);
+    /// assert!((linear - 128.0 / 255.0).abs() < 1e-6);
+    /// assert!(grey.relative_luminance_with_gamma(2.2) < linear);
+    /// ```
+    ///
+    /// [`relative_luminance`]: HexRGB::relative_luminance
+    pub fn relative_luminance_with_gamma(self, gamma: f64) -> f64
+    where
+        T: ToPrimitive,
+    {
+        let [red, green, blue] = self.to_f64_channels();
+        let [_, luminance, _] =
+            linear_to_xyz([red.powf(gamma), green.powf(gamma), blue.powf(gamma)]);
+        luminance
+    }
+
+    /// Measures the straight-line distance between two [`HexRGB`] values in
+    /// the RGB cube, with each channel from 0 to 1.
+    ///
+    /// The distance ranges from 0 for identical colours to the square root of
+    /// 3 for black and white. It is quick to compute, but does not match how
+    /// different colours look; use [`distance_ciede2000`] for that.
+    ///
+    /// # Example
+    /// ```
+    /// use ::sht_colour::rgb::HexRGB;
+    ///
+    /// let black = "#000".parse::<HexRGB<u16>>().unwrap();
+    /// let white = "#FFF".parse::<HexRGB<u16>>().unwrap();
+    /// let red = "#F00".parse::<HexRGB<u16>>().unwrap();
+    ///
+    /// assert_eq!(black.distance(&red), 1.0);
+    /// assert!((black.distance(&white) - 3.0_f64.sqrt()).abs() < 1e-9);
+    /// ```
+    ///
+    /// [`distance_ciede2000`]: HexRGB::distance_ciede2000
+    pub fn distance(&self, other: &HexRGB<T>) -> f64
+    where
+        T: ToPrimitive,
+    {
+        let (first, second) = (
+            self.clone().to_f64_channels(),
+            other.clone().to_f64_channels(),
+        );
+        first
+            .iter()
+            .zip(second.iter())
+            .map(|(a, b)| (a - b).powi(2))
+            .sum::<f64>()
+            .sqrt()
+    }
+
+    /// Measures the perceptual difference between two [`HexRGB`] values with
+    /// the CIEDE2000 colour difference formula, `ΔE00`.
+    ///
+    /// Both colours are converted from sRGB to CIELAB, relative to a D65
+    /// white point, and compared with the formula from CIE publication
+    /// 142-2001, "Improvement to industrial colour-difference evaluation",
+    /// as presented by Sharma, Wu and Dalal in "The CIEDE2000
+    /// Color-Difference Formula: Implementation Notes, Supplementary Test
+    /// Data, and Mathematical Observations" (2005). The parametric weights
+    /// `kL`, `kC` and `kH` are all 1.
+    ///
+    /// A difference of about 1 is just noticeable side by side, and 0 means
+    /// the colours are identical. This corrects the CIE 1976 difference for
+    /// the eye's lower sensitivity to changes in chroma and in light or dark
+    /// colours, and for the way hue differences warp in the blue region.
+    ///
+    /// # Example
+    /// ```
+    /// use ::sht_colour::rgb::HexRGB;
+    ///
+    /// let red = "#F00".parse::<HexRGB<u16>>().unwrap();
+    /// let almost_red = "#FE0000".parse::<HexRGB<u16>>().unwrap();
+    /// let blue = "#00F".parse::<HexRGB<u16>>().unwrap();
+    ///
+    /// assert_eq!(red.distance_ciede2000(&red), 0.0);
+    /// assert!(red.distance_ciede2000(&almost_red) < 1.0);
+    /// assert!(red.distance_ciede2000(&blue) > 50.0);
+    /// ```
+    pub fn distance_ciede2000(&self, other: &HexRGB<T>) -> f64
+    where
+        T: ToPrimitive,
+    {
+        delta_e_2000(
+            srgb_to_lab(self.clone().to_f64_channels()),
+            srgb_to_lab(other.clone().to_f64_channels()),
+        )
+    }
+
+    /// Converts a [`HexRGB`] value into linear-light channels, in the order
+    /// red, green, blue, as expected by shaders and graphics APIs.
+    ///
+    /// The channels of a hex code are encoded with the sRGB transfer function,
+    /// which spends more of its range on dark colours. This decodes them so
+    /// that each channel is proportional to the intensity of the light, using
+    /// the exact sRGB curve (including its linear segment near black) rather
+    /// than a gamma of 2.2.
+    ///
+    /// # Example
+    /// ```
+    /// use ::sht_colour::rgb::HexRGB;
+    ///
+    /// let grey = "#808080".parse::<HexRGB<u16>>().unwrap();
+    /// let [red, green, blue] = grey.to_linear_f32();
+    ///
+    /// assert!((red - 0.2158).abs() < 1e-4);
+    /// assert_eq!(red, green);
+    /// assert_eq!(green, blue);
+    /// ```
+    pub fn to_linear_f32(self) -> [f32; 3]
+    where
+        T: ToPrimitive,
+    {
+        let [red, green, blue] = self.to_f64_channels();
+        [
+            srgb_to_linear(red) as f32,
+            srgb_to_linear(green) as f32,
+            srgb_to_linear(blue) as f32,
+        ]
+    }
+
+    /// Converts a [`HexRGB`] value into an [`RGB`] pixel of `f32` channels,
+    /// each from 0 to 1, for graphics pipelines that work with floats.
+    ///
+    /// The channels stay encoded with the sRGB transfer function, exactly as
+    /// in a hex code; use [`to_linear_f32`] for linear light. Each channel is
+    /// rounded to the nearest `f32`, so channels such as 1/3 that have no
+    /// exact binary fraction are approximated to within about `6e-8`.
+    ///
+    /// # Example
+    /// ```
+    /// use ::sht_colour::rgb::{HexRGB, RGB};
+    ///
+    /// let orange = "#FF8000".parse::<HexRGB<u32>>().unwrap();
+    /// let RGB { r, g, b } = orange.to_rgb_f32();
+    ///
+    /// assert_eq!((r, b), (1.0, 0.0));
+    /// assert!((g - 128.0 / 255.0).abs() < 1e-6);
+    /// ```
+    ///
+    /// [`to_linear_f32`]: HexRGB::to_linear_f32
+    pub fn to_rgb_f32(&self) -> RGB<f32>
+    where
+        T: ToPrimitive,
+    {
+        let [red, green, blue] = self.to_f64_channels();
+        RGB::new(red as f32, green as f32, blue as f32)
+    }
+
+    /// Constructs a [`HexRGB`] value from an [`RGB`] pixel of `f32`
+    /// channels, the inverse of [`to_rgb_f32`].
+    ///
+    /// Channels outside the range 0 to 1 are clamped, and NaN is treated as 0.
+    /// Each channel is then rounded to the nearest fraction over `0xF`,
+    /// `0xFF`, `0xFFFF` or `0xFFFF_FFFF`, whichever is the largest whose square
+    /// fits in `T`, leaving room for later arithmetic. So a round trip through
+    /// floats is only exact for channels with such a denominator, such as
+    /// those of a six-digit hex code.
+    ///
+    /// # Example
+    /// ```
+    /// use ::sht_colour::rgb::{HexRGB, RGB};
+    ///
+    /// let orange = "#FF8000".parse::<HexRGB<u64>>().unwrap();
+    ///
+    /// let round_trip = HexRGB::<u64>::from_rgb_f32(orange.to_rgb_f32());
+    /// assert_eq!(round_trip.to_bytes(), [0xFF, 0x80, 0]);
+    /// assert_eq!(
+    ///     HexRGB::<u64>::from_rgb_f32(RGB::new(2.0, -1.0, f32::NAN)),
+    ///     "#F00".parse().unwrap()
+    /// );
+    /// ```
+    ///
+    /// [`to_rgb_f32`]: HexRGB::to_rgb_f32
+    pub fn from_rgb_f32(pixel: RGB<f32>) -> HexRGB<T>
+    where
+        T: FromPrimitive,
+    {
+        HexRGB::from_f64_channels([pixel.r.into(), pixel.g.into(), pixel.b.into()])
+    }
+
+    /// Converts a [`HexRGB`] value into floating point channels, in the order
+    /// red, green, blue, each from 0 to 1, with an explicit [`Encoding`].
+    ///
+    /// # Arguments
+    /// * `encoding` - Whether to return the channels as they are encoded in a
+    ///   hex code, or decoded into linear light.
+    ///
+    /// # Example
+    /// ```
+    /// use ::sht_colour::rgb::{Encoding, HexRGB};
+    ///
+    /// let grey = "#808080".parse::<HexRGB<u16>>().unwrap();
+    ///
+    /// assert!((grey.to_array(Encoding::Srgb)[0] - 0.5020).abs() < 1e-4);
+    /// assert!((grey.to_array(Encoding::Linear)[0] - 0.2158).abs() < 1e-4);
+    /// ```
+    pub fn to_array(self, encoding: Encoding) -> [f64; 3]
+    where
+        T: ToPrimitive,
+    {
+        let [red, green, blue] = self.to_f64_channels();
+        match encoding {
+            Encoding::Srgb => [red, green, blue],
+            Encoding::Linear => [
+                srgb_to_linear(red),
+                srgb_to_linear(green),
+                srgb_to_linear(blue),
+            ],
+        }
+    }
+
+    /// Constructs a [`HexRGB`] value from floating point channels, in the
+    /// order red, green, blue, with an explicit [`Encoding`]. The inverse of
+    /// [`to_array`].
+    ///
+    /// Channels outside the range 0 to 1 are clamped, and each channel is
+    /// approximated by a fraction, so the result may differ very slightly
+    /// from the input.
+    ///
+    /// # Arguments
+    /// * `channels` - The red, green and blue channels.
+    /// * `encoding` - Whether the channels are encoded as in a hex code, or
+    ///   are in linear light.
+    ///
+    /// # Example
+    /// ```
+    /// use ::sht_colour::rgb::{Encoding, HexRGB};
+    ///
+    /// let grey = <HexRGB<u32>>::from_array([0.2158; 3], Encoding::Linear);
+    ///
+    /// assert_eq!(grey.to_bytes(), [0x80; 3]);
+    /// ```
+    ///
+    /// [`to_array`]: HexRGB::to_array
+    pub fn from_array([red, green, blue]: [f64; 3], encoding: Encoding) -> HexRGB<T>
+    where
+        T: FromPrimitive,
+    {
+        match encoding {
+            Encoding::Srgb => HexRGB::from_f64_channels([red, green, blue]),
+            Encoding::Linear => HexRGB::from_f64_channels([
+                linear_to_srgb(red),
+                linear_to_srgb(green),
+                linear_to_srgb(blue),
+            ]),
+        }
+    }
+
+    /// Converts a [`HexRGB`] value to CIE XYZ coordinates `(X, Y, Z)`.
+    ///
+    /// The channels are decoded into linear light with the sRGB transfer
+    /// function, as in [`to_array`] with [`Encoding::Linear`], then
+    /// transformed with the sRGB primaries matrix. The reference white is
+    /// that of the D65 standard illuminant, `(0.95047, 1, 1.08883)`, scaled so
+    /// that white has a luminance `Y` of 1. `Y` is the
+    /// [`relative_luminance`].
+    ///
+    /// # Example
+    /// ```
+    /// use ::sht_colour::rgb::HexRGB;
+    ///
+    /// let white = "#FFF".parse::<HexRGB<u32>>().unwrap();
+    /// let (x, y, z) = white.to_xyz();
+    ///
+    /// assert!((x - 0.95047).abs() < 1e-4);
+    /// assert!((y - 1.0).abs() < 1e-4);
+    /// assert!((z - 1.08883).abs() < 1e-4);
+    /// ```
+    ///
+    /// [`to_array`]: HexRGB::to_array
+    /// [`relative_luminance`]: HexRGB::relative_luminance
+    pub fn to_xyz(&self) -> (f64, f64, f64)
+    where
+        T: ToPrimitive,
+    {
+        let [x, y, z] = linear_to_xyz(self.clone().to_array(Encoding::Linear));
+        (x, y, z)
+    }
+
+    /// Converts CIE XYZ coordinates `(X, Y, Z)`, relative to a D65 white
+    /// point, to a [`HexRGB`] value. The inverse of [`to_xyz`].
+    ///
+    /// Colours outside the sRGB gamut have their channels clamped to the
+    /// range 0 to 1, and each channel is approximated by a fraction, so the
+    /// result may differ very slightly from the input.
+    ///
+    /// # Example
+    /// ```
+    /// use ::sht_colour::rgb::HexRGB;
+    ///
+    /// let white = HexRGB::<u32>::from_xyz((0.95047, 1.0, 1.08883));
+    ///
+    /// assert_eq!(white.to_bytes(), [0xFF; 3]);
+    /// ```
+    ///
+    /// [`to_xyz`]: HexRGB::to_xyz
+    pub fn from_xyz((x, y, z): (f64, f64, f64)) -> HexRGB<T>
+    where
+        T: FromPrimitive,
+    {
+        HexRGB::from_array(xyz_to_linear([x, y, z]), Encoding::Linear)
+    }
+
+    /// Converts a [`HexRGB`] value to CIELAB coordinates `(L*, a*, b*)`.
+    ///
+    /// The colour is first converted to CIE XYZ with [`to_xyz`], relative to
+    /// the D65 reference white. Each coordinate is divided by that of the
+    /// white point and companded with `f(t)`, which is the cube root of `t`
+    /// above `(6/29)³`, and the straight line `t / (3 (6/29)²) + 4/29` below
+    /// it, avoiding the infinite slope of the cube root at 0. Then
+    /// `L* = 116 f(Y) - 16`, `a* = 500 (f(X) - f(Y))` and
+    /// `b* = 200 (f(Y) - f(Z))`, so `L*` ranges from 0 for black to 100 for
+    /// white.
+    ///
+    /// # Example
+    /// ```
+    /// use ::sht_colour::rgb::HexRGB;
+    ///
+    /// let red = "#F00".parse::<HexRGB<u32>>().unwrap();
+    /// let (lightness, a, b) = red.to_lab();
+    ///
+    /// assert!((lightness - 53.24).abs() < 0.01);
+    /// assert!((a - 80.09).abs() < 0.01);
+    /// assert!((b - 67.20).abs() < 0.01);
+    /// ```
+    ///
+    /// [`to_xyz`]: HexRGB::to_xyz
+    pub fn to_lab(&self) -> (f64, f64, f64)
+    where
+        T: ToPrimitive,
+    {
+        let [lightness, a, b] = srgb_to_lab(self.clone().to_f64_channels());
+        (lightness, a, b)
+    }
+
+    /// Converts CIELAB coordinates `(L*, a*, b*)`, relative to a D65 white
+    /// point, to a [`HexRGB`] value. The inverse of [`to_lab`].
+    ///
+    /// Colours outside the sRGB gamut have their channels clamped to the
+    /// range 0 to 1, and each channel is approximated by a fraction, so the
+    /// result may differ very slightly from the input.
+    ///
+    /// # Example
+    /// ```
+    /// use ::sht_colour::rgb::HexRGB;
+    ///
+    /// let red = HexRGB::<u32>::from_lab((53.24, 80.09, 67.20));
+    ///
+    /// assert_eq!(red.to_bytes(), [0xFF, 0, 0]);
+    /// ```
+    ///
+    /// [`to_lab`]: HexRGB::to_lab
+    pub fn from_lab((lightness, a, b): (f64, f64, f64)) -> HexRGB<T>
+    where
+        T: FromPrimitive,
+    {
+        HexRGB::from_f64_channels(lab_to_srgb([lightness, a, b]))
+    }
+
+    /// Converts a [`HexRGB`] value into linear-light channels, as fractions,
+    /// for compositing and lighting.
+    ///
+    /// Each channel is decoded with the exact sRGB transfer function, which is
+    /// linear below 0.04045 and a 2.4 power curve above it. The curve is not
+    /// rational, so the conversion is done in `f64` and each channel is then
+    /// rounded to the nearest fraction over a denominator of `0xF`, `0xFF`,
+    /// `0xFFFF` or `0xFFFF_FFFF`, the largest whose square fits in `T`. So
+    /// unlike most conversions in this crate, **this is not exact**: with a
+    /// `u64`, each channel is within about 1.2e-10 of the true value, but
+    /// with a `u8` it is only within 1/30. Only black and white are always
+    /// exact.
+    ///
+    /// # Example
+    /// ```
+    /// use ::sht_colour::{rgb::HexRGB, Ratio};
+    ///
+    /// let colour = "#80FF00".parse::<HexRGB<u64>>().unwrap();
+    /// let linear = colour.to_linear();
+    ///
+    /// assert_eq!(linear.g, Ratio::from_integer(1));
+    /// assert_eq!(linear.b, Ratio::from_integer(0));
+    /// assert!((*linear.r.numer() as f64 / *linear.r.denom() as f64 - 0.2158).abs() < 1e-4);
+    /// ```
+    pub fn to_linear(self) -> RGB<Ratio<T>>
+    where
+        T: ToPrimitive + FromPrimitive,
+    {
+        let [red, green, blue] = self.to_array(Encoding::Linear);
+        RGB::new(f64_to_ratio(red), f64_to_ratio(green), f64_to_ratio(blue))
+    }
+
+    /// Constructs a [`HexRGB`] value from linear-light channels, encoding
+    /// them with the sRGB transfer function. The inverse of [`to_linear`].
+    ///
+    /// As with [`to_linear`], the conversion is done in `f64`, and each
+    /// channel of the result is rounded to a fraction, so **this is not
+    /// exact**. Channels greater than 1 are clamped.
+    ///
+    /// # Example
+    /// ```
+    /// use ::sht_colour::{
+    ///     rgb::{HexRGB, RGB},
+    ///     Ratio,
+    /// };
+    ///
+    /// let linear = RGB::new(Ratio::new(1, 2), Ratio::from_integer(0), Ratio::from_integer(1));
+    /// let colour = <HexRGB<u64>>::from_linear(linear);
+    ///
+    /// assert_eq!(colour.to_bytes(), [0xBC, 0x00, 0xFF]);
+    /// ```
+    ///
+    /// [`to_linear`]: HexRGB::to_linear
+    pub fn from_linear(linear: RGB<Ratio<T>>) -> HexRGB<T>
+    where
+        T: ToPrimitive + FromPrimitive,
+    {
+        let RGB { r, g, b } = linear;
+        HexRGB::from_array(
+            [ratio_to_f64(&r), ratio_to_f64(&g), ratio_to_f64(&b)],
+            Encoding::Linear,
+        )
+    }
+
+    /// Converts a [`HexRGB`] value to a grey with at least a given contrast
+    /// against a background, for instance to show a disabled control.
+    ///
+    /// Contrast is measured as the WCAG contrast ratio, from the
+    /// [`relative_luminance`] of each colour, which runs from 1 for identical
+    /// colours to 21 for black against white. The grey starts with the same
+    /// relative luminance as this colour, and is then made darker or lighter
+    /// as little as possible until it meets `min_ratio`. It moves away from
+    /// the background in whichever direction it already lies, unless only the
+    /// other direction can reach the ratio.
+    ///
+    /// If neither black nor white reaches `min_ratio` against `background`,
+    /// the target is unachievable, and whichever of black or white has the
+    /// higher contrast is returned instead.
+    ///
+    /// # Arguments
+    /// * `background` - The colour the grey will be shown against.
+    /// * `min_ratio` - The smallest acceptable contrast ratio, such as 4.5 for
+    ///   body text under WCAG level AA.
+    ///
+    /// # Example
+    /// ```
+    /// use ::sht_colour::rgb::HexRGB;
+    ///
+    /// let orange = "#FF8811".parse::<HexRGB<u32>>().unwrap();
+    /// let white = "#FFF".parse::<HexRGB<u32>>().unwrap();
+    ///
+    /// let grey = orange.to_accessible_gray(white, 4.5);
+    /// let (red, green, blue) = grey.components();
+    ///
+    /// assert!(red == green && green == blue);
+    /// assert_eq!(format!("{:2}", grey), "#777777");
+    /// ```
+    ///
+    /// [`relative_luminance`]: HexRGB::relative_luminance
+    pub fn to_accessible_gray(self, background: HexRGB<T>, min_ratio: f64) -> HexRGB<T>
+    where
+        T: ToPrimitive + FromPrimitive,
+    {
+        let background_luminance = background.relative_luminance();
+        let grey = |value: f64| HexRGB::<T>::from_f64_channels([value; 3]);
+        let contrast =
+            |value: f64| contrast_ratio(grey(value).relative_luminance(), background_luminance);
+
+        let start = linear_to_srgb(self.relative_luminance());
+        if contrast(start) >= min_ratio {
+            return grey(start);
+        }
+        let background_grey = linear_to_srgb(background_luminance);
+        // search from `near` to `extreme`, along which contrast only increases,
+        // for the value nearest `near` which meets the ratio
+        let towards = |near: f64, extreme: f64| {
+            if contrast(extreme) < min_ratio {
+                return None;
+            }
+            let (mut near, mut far) = (near, extreme);
+            for _ in 0..48 {
+                let middle = (near + far) / 2.0;
+                if contrast(middle) >= min_ratio {
+                    far = middle;
+                } else {
+                    near = middle;
+                }
+            }
+            Some(far)
+        };
+        let (same_side, other_side) = if start <= background_grey {
+            (0.0, 1.0)
+        } else {
+            (1.0, 0.0)
+        };
+        let value = towards(start, same_side)
+            .or_else(|| towards(background_grey, other_side))
+            .unwrap_or_else(|| {
+                if contrast(0.0) >= contrast(1.0) {
+                    0.0
+                } else {
+                    1.0
+                }
+            });
+        grey(value)
+    }
+
+    /// Derives the colours of a "chip" or badge from this colour, which is
+    /// used as the background.
+    ///
+    /// The text colour is black or white, whichever has the higher WCAG
+    /// contrast ratio against the background, preferring black if they tie.
+    /// The higher of the two ratios is always at least 4.58, so the text
+    /// always meets level AA for body text, which requires 4.5.
+    ///
+    /// The border has the same hue and chroma as the background in the
+    /// CIELAB colour space, but its lightness `L*` is 10 lower on backgrounds
+    /// with `L*` of 50 or more, and 10 higher on darker backgrounds. Channels
+    /// that fall outside the sRGB gamut are clamped.
+    ///
+    /// # Example
+    /// ```
+    /// use ::sht_colour::rgb::HexRGB;
+    ///
+    /// let navy = "#000080".parse::<HexRGB<u32>>().unwrap();
+    /// let theme = navy.chip_theme();
+    ///
+    /// assert_eq!(theme.background, navy);
+    /// assert_eq!(theme.text, "#FFF".parse().unwrap());
+    /// assert_eq!(theme.border.to_string(), "#331A9B");
+    /// ```
+    pub fn chip_theme(self) -> ChipTheme<T>
+    where
+        T: ToPrimitive + FromPrimitive,
+    {
+        let luminance = self.clone().relative_luminance();
+        let text = if contrast_ratio(luminance, 0.0) >= contrast_ratio(luminance, 1.0) {
+            0.0
+        } else {
+            1.0
+        };
+
+        let [lightness, a, b] = srgb_to_lab(self.to_f64_channels());
+        let border_lightness = if lightness >= 50.0 {
+            lightness - BORDER_LIGHTNESS_STEP
+        } else {
+            lightness + BORDER_LIGHTNESS_STEP
+        };
+        ChipTheme {
+            text: HexRGB::from_f64_channels([text; 3]),
+            border: HexRGB::from_f64_channels(lab_to_srgb([border_lightness, a, b])),
+            background: self,
+        }
+    }
+
+    /// Finds the [`SHT`] at a given precision that looks most like this
+    /// colour.
+    ///
+    /// Unlike [`to_sht`], which rounds each SHT component separately, this
+    /// compares the exact colour of every canonical code whose components
+    /// can be written with `precision` base-12 digits. The code with the
+    /// smallest CIE 1976 colour difference, `ΔE*ab`, is returned, taking the
+    /// first in the order greys then hues if several tie. The two methods
+    /// agree on colours that have an exact code, but can differ elsewhere,
+    /// especially for dark or pale colours where rounding the shade or tint
+    /// changes the colour more than rounding the hue.
+    ///
+    /// There are `6n³ + n + 1` codes to compare, where `n` is `12^precision`,
+    /// as counted by [`sht_code_count`]. That is 10,381 codes for a precision
+    /// of 1, but about 18 million for a precision of 2, so higher precisions
+    /// are impractically slow.
+    ///
+    /// # Example
+    /// ```
+    /// # #[cfg(feature = "parse")]
+    /// # {
+    /// use ::sht_colour::{rgb::HexRGB, SHT};
+    ///
+    /// let orange = "#FF8000".parse::<HexRGB<u32>>().unwrap();
+    ///
+    /// assert_eq!(orange.nearest_sht_perceptual(1), "r6g".parse::<SHT<u32>>().unwrap());
+    /// # }
+    /// ```
+    ///
+    /// # Panics
+    /// **Panics on overflow!**
+    ///
+    /// [`SHT`]: sht::SHT
+    /// [`to_sht`]: HexRGB::to_sht
+    /// [`sht_code_count`]: sht::sht_code_count
+    pub fn nearest_sht_perceptual(self, precision: usize) -> sht::SHT<T>
+    where
+        T: From<u8> + ToPrimitive,
+    {
+        let target = srgb_to_lab(self.to_f64_channels());
+        let difference = |code: &sht::SHT<T>| {
+            let (red, green, blue) = code.clone().exact_channels();
+            delta_e_1976(
+                target,
+                srgb_to_lab(HexRGB::new(red, green, blue).to_f64_channels()),
+            )
+        };
+        sht::SHT::all_codes(precision)
+            .map(|code| {
+                let difference = difference(&code);
+                (code, difference)
+            })
+            .fold(None, |best, (code, difference)| match best {
+                Some((_, best_difference)) if best_difference <= difference => best,
+                _ => Some((code, difference)),
+            })
+            .map(|(code, _)| code)
+            .expect("There is always at least one code")
+    }
+
+    /// Finds a short [`SHT`] code that looks like this colour, to within a
+    /// tolerance, trading precision for brevity.
+    ///
+    /// The colour is converted with [`to_sht`] at increasing precisions,
+    /// starting from 0 digits per component, and the first code whose colour
+    /// is close enough to this one is returned. Colour difference is measured
+    /// as the CIE 1976 colour difference, `ΔE*ab`, where a difference of about
+    /// 2.3 is just noticeable, and 0 means the colours are identical.
+    ///
+    /// # Arguments
+    /// * `tolerance` - The largest acceptable `ΔE*ab` between this colour and
+    ///   the colour of the code.
+    ///
+    /// # Example
+    /// ```
+    /// use ::sht_colour::rgb::HexRGB;
+    ///
+    /// let orange = "#FF8811".parse::<HexRGB<u64>>().unwrap();
+    ///
+    /// assert_eq!(orange.clone().shortest_sht_within(10.0).to_string(), "r6g1");
+    /// assert_eq!(orange.shortest_sht_within(1.0).to_string(), "r6g0X");
+    /// ```
+    ///
+    /// # Panics
+    /// **Panics on overflow!** This happens if the tolerance is so small that
+    /// the precision needed to meet it does not fit in `T`, such as a
+    /// tolerance of 0 for a colour with no exact SHT code.
+    ///
+    /// [`SHT`]: sht::SHT
+    /// [`to_sht`]: HexRGB::to_sht
+    pub fn shortest_sht_within(self, tolerance: f64) -> sht::SHT<T>
+    where
+        T: From<u8> + ToPrimitive,
+    {
+        let target = srgb_to_lab(self.to_f64_channels());
+        let difference = |code: &sht::SHT<T>| {
+            let (red, green, blue) = code.clone().exact_channels();
+            delta_e_1976(
+                target,
+                srgb_to_lab(HexRGB::new(red, green, blue).to_f64_channels()),
+            )
+        };
+        (0..)
+            .map(|precision| self.clone().to_sht(precision))
+            .find(|code| difference(code) <= tolerance)
+            .expect("Precision should be unbounded")
+    }
+}
+
+impl<T> HexRGBA<T>
+where
+    T: Unsigned + Integer + Clone + CheckedMul,
+{
+    /// Converts a [`HexRGBA`] value into linear-light channels, in the order
+    /// red, green, blue, alpha, as expected by shaders and graphics APIs.
+    ///
+    /// The colour channels are decoded as in [`HexRGB::to_linear_f32`]. The
+    /// alpha channel is already linear, so it is passed through unchanged.
+    ///
+    /// # Example
+    /// ```
+    /// use ::sht_colour::rgb::HexRGBA;
+    ///
+    /// let translucent_grey = "#80808080".parse::<HexRGBA<u16>>().unwrap();
+    /// let [red, green, blue, alpha] = translucent_grey.to_linear_f32();
+    ///
+    /// assert!((red - 0.2158).abs() < 1e-4);
+    /// assert_eq!((red, red), (green, blue));
+    /// assert!((alpha - 128.0 / 255.0).abs() < 1e-6);
+    /// ```
+    pub fn to_linear_f32(self) -> [f32; 4]
+    where
+        T: ToPrimitive,
+    {
+        let alpha = ratio_to_f64(&self.alpha()) as f32;
+        let [red, green, blue] = self.to_hex_rgb().to_linear_f32();
+        [red, green, blue, alpha]
+    }
+}
@@ -1,12 +1,28 @@
-use super::{round_denominator, sht};
-use ::num::{checked_pow, rational::Ratio, CheckedMul, Integer, One, Unsigned, Zero};
+use super::{
+    checked_ratio_sub, clamp_unit, f64_to_ratio, ratio_to_f64, round_denominator,
+    round_denominator_with, safe_denominator, sht, write_padded, OverflowError, RoundingMode,
+};
+use ::num::{
+    rational::Ratio, CheckedDiv, CheckedMul, FromPrimitive, Integer, One, ToPrimitive, Unsigned,
+    Zero,
+};
 use ::std::{
+    convert::TryFrom,
     fmt::{Display, Error, Formatter, Result as FMTResult, UpperHex},
+    num::TryFromIntError,
     str::FromStr,
 };
 
 /// Re-export from the `RGB` crate, representing the RGB pixel.
 pub use ::rgb::RGB;
+#[cfg(feature = "cssparser")]
+pub use css::parse_css_hex;
+pub use functional::ParseCssError;
+pub use hsl::mean_hue;
+pub use lab::{average_linear, ChipTheme, Encoding};
+pub use palette::{find_near_duplicates, palette_score, parse_gpl, write_gpl, PaletteError};
+pub use rgba::HexRGBA;
+pub use ycbcr::YCbCrMatrix;
 
 /// Represents possible errors parsing an [`HexRGB`] hex code from a string.
 #[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
@@ -17,7 +33,8 @@ pub enum ParseHexError {
     /// The code did not begin with a `'#'`
     MissingOctothorpe,
     /// The code contained a number of digits that was not a multiple of three.
-    /// (Transparency is not supported.)
+    /// (Transparency is only supported by [`HexRGBA`], which needs a multiple
+    /// of four.)
     InvalidDigitCount,
     /// Some failure parsing digits.
     DigitParseError,
@@ -25,6 +42,15 @@ pub enum ParseHexError {
     Overflow,
 }
 
+/// Represents a channel outside the range 0 to 1 when constructing a
+/// [`HexRGB`] with [`HexRGB::try_new`].
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub struct ChannelOutOfRange {
+    /// The first channel, in the order red, green, blue, that was greater
+    /// than 1.
+    pub channel: sht::ColourChannel,
+}
+
 /// Represents a standard RGB code in the hex format.
 ///
 /// RGB is a common colour format that is easily interoperable with most
@@ -35,7 +61,12 @@ pub enum ParseHexError {
 /// and blue colour channels, respectively.
 ///
 /// The codes can be abbreviated `#XYZ` if precision is not required.
-/// Conversely, colours can be made more precise by adding digits.
+/// Conversely, colours can be made more precise by adding digits. Codes of
+/// different lengths that describe the same colour, such as `#F00` and
+/// `#FF0000`, compare equal and have equal hashes, because each channel is
+/// stored as a [`Ratio`] and ratios are compared by value rather than by
+/// representation. This makes [`HexRGB`] suitable as a key in a `HashMap` or
+/// `HashSet` containing colours from different sources.
 ///
 /// # Example
 /// ```
@@ -70,124 +101,1131 @@ where
     inner: RGB<Ratio<T>>,
 }
 
-impl<T> HexRGB<T>
-where
-    T: Unsigned + Integer + Clone + CheckedMul,
-{
-    /// Constructs a [`HexRGB`] value.
+impl<T> HexRGB<T>
+where
+    T: Unsigned + Integer + Clone + CheckedMul,
+{
+    /// Constructs a [`HexRGB`] value.
+    ///
+    /// The channels are not checked. Each should lie between 0 and 1
+    /// inclusive, and a channel greater than 1 will misbehave later, such as
+    /// by writing a hex code with too many digits. Use [`HexRGB::try_new`] to
+    /// check the channels instead.
+    ///
+    /// # Arguments
+    ///
+    /// * `red` - The absolute brightness of the red channel.
+    /// * `green` - The absolute brightness of the green channel.
+    /// * `blue` - The absolute brightness of the blue channel.
+    ///
+    /// # Example
+    /// ```
+    /// use ::sht_colour::{rgb::HexRGB, Ratio};
+    ///
+    /// let dark_red = <HexRGB<u8>>::new(
+    ///     Ratio::new(0x5, 0xF),
+    ///     Ratio::new(0x0, 0xF),
+    ///     Ratio::new(0x0, 0xF),
+    /// );
+    ///
+    /// assert_eq!(dark_red, "#500".parse().unwrap());
+    /// ```
+    pub fn new(red: Ratio<T>, green: Ratio<T>, blue: Ratio<T>) -> HexRGB<T> {
+        HexRGB {
+            inner: RGB::new(red, green, blue),
+        }
+    }
+
+    /// Constructs a [`HexRGB`] value, checking that each channel lies between
+    /// 0 and 1 inclusive.
+    ///
+    /// [`HexRGB::new`] does not check its channels, so a channel greater than
+    /// 1 would only cause problems later, such as a nonsensical hex code from
+    /// the [`Display` implementation]. Since the channels are unsigned, they
+    /// can never be below 0.
+    ///
+    /// # Arguments
+    ///
+    /// * `red` - The absolute brightness of the red channel.
+    /// * `green` - The absolute brightness of the green channel.
+    /// * `blue` - The absolute brightness of the blue channel.
+    ///
+    /// # Example
+    /// ```
+    /// use ::sht_colour::{
+    ///     rgb::{ChannelOutOfRange, HexRGB},
+    ///     ColourChannel, Ratio,
+    /// };
+    ///
+    /// let dark_red = <HexRGB<u8>>::try_new(Ratio::new(1, 3), Ratio::new(0, 1), Ratio::new(0, 1));
+    /// assert_eq!(dark_red, Ok("#500".parse().unwrap()));
+    ///
+    /// let too_green = <HexRGB<u8>>::try_new(Ratio::new(0, 1), Ratio::new(2, 1), Ratio::new(0, 1));
+    /// assert_eq!(
+    ///     too_green,
+    ///     Err(ChannelOutOfRange {
+    ///         channel: ColourChannel::Green
+    ///     })
+    /// );
+    /// ```
+    ///
+    /// # Errors
+    /// Will return `Err` if any channel is greater than 1.
+    ///
+    /// [`Display` implementation]: HexRGB#impl-Display
+    pub fn try_new(
+        red: Ratio<T>,
+        green: Ratio<T>,
+        blue: Ratio<T>,
+    ) -> Result<HexRGB<T>, ChannelOutOfRange> {
+        let check = |ratio: &Ratio<T>, channel| {
+            if *ratio > Ratio::one() {
+                Err(ChannelOutOfRange { channel })
+            } else {
+                Ok(())
+            }
+        };
+        check(&red, sht::ColourChannel::Red)?;
+        check(&green, sht::ColourChannel::Green)?;
+        check(&blue, sht::ColourChannel::Blue)?;
+        Ok(HexRGB::new(red, green, blue))
+    }
+
+    /// Splits a [`HexRGB`] value into its individual components, the channels
+    /// red, green and blue.
+    ///
+    /// # Example
+    /// ```
+    /// use ::sht_colour::rgb;
+    ///
+    /// let colour = "#123456".parse::<rgb::HexRGB<u16>>().unwrap();
+    ///
+    /// let (red, green, blue) = colour.clone().components();
+    /// let new_colour = <rgb::HexRGB<_>>::new(red, green, blue);
+    ///
+    /// assert_eq!(colour, new_colour);
+    /// ```
+    pub fn components(self) -> (Ratio<T>, Ratio<T>, Ratio<T>) {
+        let Self {
+            inner: RGB { r, g, b },
+        } = self;
+        (r, g, b)
+    }
+
+    /// Returns the brightness of the red channel of a [`HexRGB`] value.
+    ///
+    /// # Example
+    /// ```
+    /// use ::sht_colour::{rgb::HexRGB, Ratio};
+    ///
+    /// let colour = "#FF8811".parse::<HexRGB<u16>>().unwrap();
+    ///
+    /// assert_eq!(colour.red(), Ratio::from_integer(1));
+    /// assert_eq!(colour.green(), Ratio::new(0x88, 0xFF));
+    /// assert_eq!(colour.blue(), Ratio::new(0x11, 0xFF));
+    /// ```
+    pub fn red(&self) -> Ratio<T> {
+        self.inner.r.clone()
+    }
+
+    /// Returns the brightness of the green channel of a [`HexRGB`] value.
+    ///
+    /// See [`red`](HexRGB::red) for an example.
+    pub fn green(&self) -> Ratio<T> {
+        self.inner.g.clone()
+    }
+
+    /// Returns the brightness of the blue channel of a [`HexRGB`] value.
+    ///
+    /// See [`red`](HexRGB::red) for an example.
+    pub fn blue(&self) -> Ratio<T> {
+        self.inner.b.clone()
+    }
+
+    /// Replaces the red channel of a [`HexRGB`] value, keeping the other
+    /// channels.
+    ///
+    /// As with [`new`], the channel is not checked, so it should lie between
+    /// 0 and 1 inclusive for the colour to have a hex code.
+    ///
+    /// # Example
+    /// ```
+    /// use ::sht_colour::{rgb::HexRGB, Ratio};
+    ///
+    /// let colour = "#008811".parse::<HexRGB<u16>>().unwrap();
+    ///
+    /// assert_eq!(
+    ///     colour.with_red(Ratio::from_integer(1)),
+    ///     "#FF8811".parse().unwrap()
+    /// );
+    /// ```
+    ///
+    /// [`new`]: HexRGB::new
+    pub fn with_red(self, red: Ratio<T>) -> HexRGB<T> {
+        let (_, green, blue) = self.components();
+        HexRGB::new(red, green, blue)
+    }
+
+    /// Replaces the green channel of a [`HexRGB`] value, keeping the other
+    /// channels.
+    ///
+    /// See [`with_red`](HexRGB::with_red) for details.
+    pub fn with_green(self, green: Ratio<T>) -> HexRGB<T> {
+        let (red, _, blue) = self.components();
+        HexRGB::new(red, green, blue)
+    }
+
+    /// Replaces the blue channel of a [`HexRGB`] value, keeping the other
+    /// channels.
+    ///
+    /// See [`with_red`](HexRGB::with_red) for details.
+    pub fn with_blue(self, blue: Ratio<T>) -> HexRGB<T> {
+        let (red, green, _) = self.components();
+        HexRGB::new(red, green, blue)
+    }
+
+    /// Constructs a [`HexRGB`] value from the cyan, magenta, yellow and black
+    /// components of a CMYK colour.
+    ///
+    /// This uses the simple device conversion, where each ink subtracts
+    /// directly from a channel of the screen's light. It is not colour-managed
+    /// with an ICC profile, so it will only approximate what a printer
+    /// produces.
+    ///
+    /// # Arguments
+    /// * `cyan` - The amount of cyan ink, which absorbs red light.
+    /// * `magenta` - The amount of magenta ink, which absorbs green light.
+    /// * `yellow` - The amount of yellow ink, which absorbs blue light.
+    /// * `black` - The amount of black ink (the key), which absorbs all light.
+    ///
+    /// # Example
+    /// ```
+    /// use ::sht_colour::{rgb::HexRGB, Ratio};
+    ///
+    /// let cyan = <HexRGB<u8>>::from_cmyk(
+    ///     Ratio::from_integer(1),
+    ///     Ratio::from_integer(0),
+    ///     Ratio::from_integer(0),
+    ///     Ratio::from_integer(0),
+    /// );
+    ///
+    /// assert_eq!(cyan, "#0FF".parse().unwrap());
+    /// ```
+    pub fn from_cmyk(
+        cyan: Ratio<T>,
+        magenta: Ratio<T>,
+        yellow: Ratio<T>,
+        black: Ratio<T>,
+    ) -> HexRGB<T> {
+        let brightest = <Ratio<T>>::one() - black;
+        let channel = |ink: Ratio<T>| (<Ratio<T>>::one() - ink) * brightest.clone();
+        HexRGB::new(channel(cyan), channel(magenta), channel(yellow))
+    }
+
+    /// Converts a [`HexRGB`] value to the cyan, magenta, yellow and black
+    /// components of a CMYK colour, in that order.
+    ///
+    /// As with [`from_cmyk`], this is the simple device conversion without
+    /// colour management. As much of the colour as possible is made from black
+    /// ink, so at least one of cyan, magenta and yellow will be zero. Pure
+    /// black is made entirely from black ink, with zero cyan, magenta and
+    /// yellow.
+    ///
+    /// # Example
+    /// ```
+    /// use ::sht_colour::{rgb::HexRGB, Ratio};
+    ///
+    /// let dark_orange = "#804000".parse::<HexRGB<u16>>().unwrap();
+    /// let (cyan, magenta, yellow, black) = dark_orange.to_cmyk();
+    ///
+    /// assert_eq!(cyan, Ratio::from_integer(0));
+    /// assert_eq!(magenta, Ratio::new(1, 2));
+    /// assert_eq!(yellow, Ratio::from_integer(1));
+    /// assert_eq!(black, Ratio::new(127, 255));
+    /// ```
+    ///
+    /// [`from_cmyk`]: HexRGB::from_cmyk
+    pub fn to_cmyk(self) -> (Ratio<T>, Ratio<T>, Ratio<T>, Ratio<T>) {
+        let (red, green, blue) = self.components();
+        let brightest = red.clone().max(green.clone()).max(blue.clone());
+        let black = <Ratio<T>>::one() - brightest.clone();
+        if brightest.is_zero() {
+            return (<_>::zero(), <_>::zero(), <_>::zero(), black);
+        }
+        let ink = |channel: Ratio<T>| (brightest.clone() - channel) / brightest.clone();
+        (ink(red), ink(green), ink(blue), black)
+    }
+
+    /// Finds the brightest channel of a [`HexRGB`] value, and its brightness.
+    ///
+    /// If several channels are equally bright, red is preferred over green,
+    /// and green over blue. In particular, greys report red.
+    ///
+    /// # Example
+    /// ```
+    /// use ::sht_colour::{rgb::HexRGB, ColourChannel, Ratio};
+    ///
+    /// let orange = "#FF8811".parse::<HexRGB<u16>>().unwrap();
+    /// let grey = "#808080".parse::<HexRGB<u16>>().unwrap();
+    ///
+    /// assert_eq!(
+    ///     orange.dominant_channel(),
+    ///     (ColourChannel::Red, Ratio::from_integer(1))
+    /// );
+    /// assert_eq!(
+    ///     grey.dominant_channel(),
+    ///     (ColourChannel::Red, Ratio::new(128, 255))
+    /// );
+    /// ```
+    pub fn dominant_channel(self) -> (sht::ColourChannel, Ratio<T>) {
+        let [_, _, (maximum, max_channel)] = self.sorted_channels();
+        (max_channel, maximum)
+    }
+
+    /// Rearranges the channels of a [`HexRGB`] value.
+    ///
+    /// # Arguments
+    /// * `order` - Which of the original channels to use for the red, green
+    ///   and blue channels of the result, in that order. Must contain each
+    ///   channel exactly once.
+    ///
+    /// # Example
+    /// ```
+    /// use ::sht_colour::{rgb::HexRGB, ColourChannel};
+    ///
+    /// let red = "#FF0000".parse::<HexRGB<u16>>().unwrap();
+    /// let swapped = red.swizzle([
+    ///     ColourChannel::Blue,
+    ///     ColourChannel::Green,
+    ///     ColourChannel::Red,
+    /// ]);
+    ///
+    /// assert_eq!(swapped, "#0000FF".parse().unwrap());
+    /// ```
+    ///
+    /// # Panics
+    /// Will panic if `order` is not a permutation of the three channels.
+    pub fn swizzle(self, order: [sht::ColourChannel; 3]) -> HexRGB<T> {
+        assert!(
+            sht::ColourChannel::is_permutation(order),
+            "Channel order must contain each channel once! {:?}",
+            order
+        );
+        let (red, green, blue) = self.components();
+        let pick = |channel| match channel {
+            sht::ColourChannel::Red => red.clone(),
+            sht::ColourChannel::Green => green.clone(),
+            sht::ColourChannel::Blue => blue.clone(),
+        };
+        let [new_red, new_green, new_blue] = order;
+        HexRGB::new(pick(new_red), pick(new_green), pick(new_blue))
+    }
+
+    /// Mixes two [`HexRGB`] values, taking a weighted average of each
+    /// channel.
+    ///
+    /// The channels are mixed exactly, as ratios, so nothing is rounded until
+    /// the result is formatted. The channels are mixed as they are, without
+    /// first undoing the sRGB transfer function, as in most image editors and
+    /// CSS gradients. See [`average_linear`] to mix linear light instead.
+    ///
+    /// # Arguments
+    /// * `other` - The colour to mix in.
+    /// * `weight` - How much of `other` to use, from 0 for none (returning
+    ///   `self`) to 1 for all of it (returning `other`). Weights greater than 1
+    ///   are treated as 1.
+    ///
+    /// # Example
+    /// ```
+    /// use ::sht_colour::{rgb::HexRGB, Ratio};
+    ///
+    /// let red = "#F00".parse::<HexRGB<u32>>().unwrap();
+    /// let blue = "#00F".parse::<HexRGB<u32>>().unwrap();
+    ///
+    /// let purple = red.mix(&blue, Ratio::new(1, 3));
+    /// assert_eq!(purple.red(), Ratio::new(2, 3));
+    /// assert_eq!(purple.blue(), Ratio::new(1, 3));
+    /// ```
+    ///
+    /// [`average_linear`]: average_linear
+    pub fn mix(&self, other: &HexRGB<T>, weight: Ratio<T>) -> HexRGB<T> {
+        let weight = clamp_unit(weight);
+        let remainder = <Ratio<T>>::one() - weight.clone();
+        let mix =
+            |first: Ratio<T>, second: Ratio<T>| first * remainder.clone() + second * weight.clone();
+        HexRGB::new(
+            mix(self.red(), other.red()),
+            mix(self.green(), other.green()),
+            mix(self.blue(), other.blue()),
+        )
+    }
+
+    /// Averages some [`HexRGB`] values, giving each the same weight, as in
+    /// [`mix`].
+    ///
+    /// Returns `None` if there are no colours.
+    ///
+    /// # Example
+    /// ```
+    /// use ::sht_colour::rgb::HexRGB;
+    ///
+    /// let colours = ["#F00", "#0F0", "#00F"]
+    ///     .iter()
+    ///     .map(|s| s.parse::<HexRGB<u32>>().unwrap())
+    ///     .collect::<Vec<_>>();
+    ///
+    /// assert_eq!(HexRGB::average(&colours), Some("#555".parse().unwrap()));
+    /// assert_eq!(HexRGB::<u32>::average(&[]), None);
+    /// ```
+    ///
+    /// [`mix`]: HexRGB::mix
+    pub fn average(colours: &[HexRGB<T>]) -> Option<HexRGB<T>> {
+        let (first, rest) = colours.split_first()?;
+        // a running mean, which never leaves the unit interval
+        let (mean, _) = rest
+            .iter()
+            .fold((first.clone(), T::one()), |(mean, count), colour| {
+                let count = count + T::one();
+                let weight = Ratio::new(T::one(), count.clone());
+                (mean.mix(colour, weight), count)
+            });
+        Some(mean)
+    }
+
+    /// Inverts a [`HexRGB`] value, replacing each channel with 1 minus that
+    /// channel, like a photographic negative.
+    ///
+    /// This is exact, so black and white invert to each other, and inverting
+    /// twice gives the original colour. Channels greater than 1 invert to 0.
+    ///
+    /// # Example
+    /// ```
+    /// use ::sht_colour::rgb::HexRGB;
+    ///
+    /// let orange = "#FF8811".parse::<HexRGB<u32>>().unwrap();
+    ///
+    /// assert_eq!(orange.invert(), "#0077EE".parse().unwrap());
+    /// assert_eq!(orange.invert().invert(), orange);
+    /// ```
+    pub fn invert(&self) -> HexRGB<T> {
+        let invert = |channel: Ratio<T>| {
+            if channel > Ratio::one() {
+                Ratio::zero()
+            } else {
+                Ratio::one() - channel
+            }
+        };
+        HexRGB::new(
+            invert(self.red()),
+            invert(self.green()),
+            invert(self.blue()),
+        )
+    }
+
+    /// Converts a [`HexRGB`] value to the grey with the same luma, with all
+    /// three channels equal.
+    ///
+    /// The luma is a weighted sum of the channels, using the weights of
+    /// Rec. 601: 0.299 for red, 0.587 for green and 0.114 for blue. These are
+    /// applied to the channels as they are, without undoing the sRGB transfer
+    /// function, so the result is exact, but only approximates the perceived
+    /// lightness. See [`relative_luminance`] for the Rec. 709 weights applied
+    /// to linear light.
+    ///
+    /// # Example
+    /// ```
+    /// use ::sht_colour::{rgb::HexRGB, Ratio};
+    ///
+    /// let red = "#F00".parse::<HexRGB<u32>>().unwrap();
+    /// let grey = red.grayscale();
+    ///
+    /// assert_eq!(grey.red(), Ratio::new(299, 1000));
+    /// assert_eq!(grey.green(), grey.red());
+    /// assert_eq!(grey.blue(), grey.red());
+    /// ```
+    ///
+    /// # Panics
+    /// **Panics on overflow!**
+    ///
+    /// [`relative_luminance`]: HexRGB::relative_luminance
+    pub fn grayscale(&self) -> HexRGB<T>
+    where
+        T: From<u16>,
+    {
+        let weight = |numerator: u16| Ratio::new(T::from(numerator), T::from(1000));
+        let luma =
+            self.red() * weight(299) + self.green() * weight(587) + self.blue() * weight(114);
+        HexRGB::new(luma.clone(), luma.clone(), luma)
+    }
+
+    /// Encodes a [`HexRGB`] value as three bytes, for compact storage.
+    ///
+    /// The bytes are the red, green and blue channels in that order, each
+    /// rounded to the nearest 8-bit value as in a six-digit hex code. This is
+    /// lossy for colours with more precise channels.
+    ///
+    /// # Example
+    /// ```
+    /// use ::sht_colour::rgb::HexRGB;
+    ///
+    /// let colour = "#FF8811".parse::<HexRGB<u16>>().unwrap();
+    ///
+    /// assert_eq!(colour.to_bytes(), [0xFF, 0x88, 0x11]);
+    /// assert_eq!(HexRGB::from_bytes(colour.to_bytes()), colour);
+    /// ```
+    pub fn to_bytes(self) -> [u8; 3]
+    where
+        T: From<u8> + ToPrimitive,
+    {
+        let (red, green, blue) = self.to_rgb8();
+        [red, green, blue]
+    }
+
+    /// Decodes a [`HexRGB`] value from the three bytes written by
+    /// [`to_bytes`], each a channel over 255.
+    ///
+    /// [`to_bytes`]: HexRGB::to_bytes
+    pub fn from_bytes([red, green, blue]: [u8; 3]) -> HexRGB<T>
+    where
+        T: From<u8>,
+    {
+        let channel = |byte: u8| Ratio::new(byte.into(), 255.into());
+        HexRGB::new(channel(red), channel(green), channel(blue))
+    }
+
+    /// Converts a [`HexRGB`] value to a tuple of 8-bit channels, in the order
+    /// red, green, blue, as expected by many graphics APIs.
+    ///
+    /// Each channel is multiplied by 255 and rounded to the nearest integer,
+    /// with halves rounded up, as in [`to_bytes`]. Channels greater than 1
+    /// become 255.
+    ///
+    /// # Example
+    /// ```
+    /// use ::sht_colour::{rgb::HexRGB, Ratio};
+    ///
+    /// let colour = "#FF8811".parse::<HexRGB<u32>>().unwrap();
+    /// let half = HexRGB::<u32>::new(Ratio::new(1, 2), Ratio::new(1, 2), Ratio::new(1, 2));
+    ///
+    /// assert_eq!(colour.to_rgb8(), (0xFF, 0x88, 0x11));
+    /// assert_eq!(half.to_rgb8(), (128, 128, 128));
+    /// ```
+    ///
+    /// [`to_bytes`]: HexRGB::to_bytes
+    pub fn to_rgb8(&self) -> (u8, u8, u8)
+    where
+        T: ToPrimitive,
+    {
+        let RGB { r, g, b } = &self.inner;
+        (
+            channel_to_u8(r.clone()),
+            channel_to_u8(g.clone()),
+            channel_to_u8(b.clone()),
+        )
+    }
+
+    /// Constructs a [`HexRGB`] value from 8-bit channels, each exactly that
+    /// many 255ths. The inverse of [`to_rgb8`] for colours from 8-bit data.
+    ///
+    /// # Example
+    /// ```
+    /// use ::sht_colour::rgb::HexRGB;
+    ///
+    /// let colour = HexRGB::<u32>::from_rgb8(0xFF, 0x88, 0x11);
+    ///
+    /// assert_eq!(colour, "#FF8811".parse().unwrap());
+    /// assert_eq!(colour.to_rgb8(), (0xFF, 0x88, 0x11));
+    /// ```
+    ///
+    /// [`to_rgb8`]: HexRGB::to_rgb8
+    pub fn from_rgb8(red: u8, green: u8, blue: u8) -> HexRGB<T>
+    where
+        T: From<u8>,
+    {
+        HexRGB::from_bytes([red, green, blue])
+    }
+
+    /// Packs a [`HexRGB`] value into an integer of the form `0x00RRGGBB`, as
+    /// taken by many graphics and windowing APIs.
+    ///
+    /// Each channel is rounded to 8 bits as in [`to_rgb8`]. The top byte,
+    /// which some APIs use for alpha, is always 0.
+    ///
+    /// # Example
+    /// ```
+    /// use ::sht_colour::rgb::HexRGB;
+    ///
+    /// let colour = "#FF8811".parse::<HexRGB<u32>>().unwrap();
+    ///
+    /// assert_eq!(colour.to_u32(), 0xFF8811);
+    /// ```
+    ///
+    /// [`to_rgb8`]: HexRGB::to_rgb8
+    pub fn to_u32(&self) -> u32
+    where
+        T: ToPrimitive,
+    {
+        let (red, green, blue) = self.to_rgb8();
+        u32::from_be_bytes([0, red, green, blue])
+    }
+
+    /// Unpacks a [`HexRGB`] value from an integer of the form `0x__RRGGBB`.
+    /// The inverse of [`to_u32`].
+    ///
+    /// The top byte is ignored, so any alpha stored there is discarded.
+    ///
+    /// # Example
+    /// ```
+    /// use ::sht_colour::rgb::HexRGB;
+    ///
+    /// let colour = "#FF8811".parse::<HexRGB<u32>>().unwrap();
+    ///
+    /// assert_eq!(HexRGB::from_u32(0xFF8811), colour);
+    /// assert_eq!(HexRGB::from_u32(0x80FF8811), colour);
+    /// ```
+    ///
+    /// [`to_u32`]: HexRGB::to_u32
+    pub fn from_u32(value: u32) -> HexRGB<T>
+    where
+        T: From<u8>,
+    {
+        let [_, red, green, blue] = value.to_be_bytes();
+        HexRGB::from_bytes([red, green, blue])
+    }
+
+    /// Checks whether a [`HexRGB`] value lies on the surface of the RGB cube,
+    /// with at least one channel exactly 0 or exactly 1.
+    ///
+    /// Every fully saturated colour is on the surface, as is any colour
+    /// whose channels have been clipped to the range 0 to 1.
+    ///
+    /// # Example
+    /// ```
+    /// use ::sht_colour::rgb::HexRGB;
+    ///
+    /// assert!("#FF8811".parse::<HexRGB<u32>>().unwrap().is_on_gamut_surface());
+    /// assert!(!"#7F8081".parse::<HexRGB<u32>>().unwrap().is_on_gamut_surface());
+    /// ```
+    pub fn is_on_gamut_surface(&self) -> bool {
+        [self.red(), self.green(), self.blue()]
+            .iter()
+            .any(|channel| channel.is_zero() || channel.is_one())
+    }
+
+    /// Checks whether a [`HexRGB`] value can be written as a three-digit hex
+    /// code without losing any precision.
+    ///
+    /// For a colour written as a six-digit code, this is whether each
+    /// channel's two hex digits are equal, so `#AABBCC` can be shortened to
+    /// `#ABC`, but `#ABCDEF` cannot. More generally, it is whether each channel
+    /// is exactly a multiple of 1/15, whatever number of digits it was
+    /// written with.
+    ///
+    /// # Example
+    /// ```
+    /// use ::sht_colour::rgb::HexRGB;
+    ///
+    /// assert!("#AABBCC".parse::<HexRGB<u32>>().unwrap().can_shorten());
+    /// assert!(!"#ABCDEF".parse::<HexRGB<u32>>().unwrap().can_shorten());
+    /// ```
+    pub fn can_shorten(&self) -> bool
+    where
+        T: From<u8>,
+    {
+        [self.red(), self.green(), self.blue()]
+            .iter()
+            .all(|channel| (T::from(15) % channel.denom().clone()).is_zero())
+    }
+
+    /// Returns the [`HexRGB`] value, if it can be written as a three-digit
+    /// hex code without losing precision, as checked by [`can_shorten`].
+    ///
+    /// A [`HexRGB`] does not remember how many digits it was written with, so
+    /// the result is the same colour, ready to be written with one digit per
+    /// channel by formatting it with a width of 1, as in `"{:1}"`.
+    ///
+    /// # Example
+    /// ```
+    /// use ::sht_colour::rgb::HexRGB;
+    ///
+    /// let colour = "#AABBCC".parse::<HexRGB<u32>>().unwrap();
+    /// let short = colour.shorten().unwrap();
+    ///
+    /// assert_eq!(format!("{:1}", short), "#ABC");
+    /// assert_eq!("#ABCDEF".parse::<HexRGB<u32>>().unwrap().shorten(), None);
+    /// ```
+    ///
+    /// [`can_shorten`]: HexRGB::can_shorten
+    pub fn shorten(self) -> Option<HexRGB<T>>
+    where
+        T: From<u8>,
+    {
+        if self.can_shorten() {
+            Some(self)
+        } else {
+            None
+        }
+    }
+
+    /// Parses a hex code written with any of the prefixes common in source
+    /// code and configuration files, leniently enough for data files and user
+    /// input.
+    ///
+    /// The accepted forms are a `'#'` prefix as in CSS (`"#FF8811"`), a `"0x"`
+    /// or `"0X"` prefix as in many programming languages (`"0xFF8811"`), or
+    /// no prefix at all (`"FF8811"`). Surrounding whitespace is ignored. After
+    /// the prefix, the digits are read exactly as by the
+    /// [`FromStr` implementation], which only accepts the `'#'` form.
+    ///
+    /// # Example
+    /// ```
+    /// use ::sht_colour::rgb::HexRGB;
+    ///
+    /// let colour = "#FF8811".parse::<HexRGB<u16>>().unwrap();
+    ///
+    /// assert_eq!(HexRGB::from_any_hex("0xFF8811"), Ok(colour));
+    /// assert_eq!(HexRGB::from_any_hex("ff8811"), Ok(colour));
+    /// assert_eq!(HexRGB::from_any_hex(" #f81\n"), Ok(colour));
+    /// ```
+    ///
+    /// # Errors
+    /// Will return `Err` if the digits after the prefix are not a valid hex
+    /// code.
+    ///
+    /// [`FromStr` implementation]: HexRGB#impl-FromStr
+    pub fn from_any_hex(s: &str) -> Result<HexRGB<T>, ParseHexError>
+    where
+        T: FromStr + From<u8>,
+    {
+        let s = s.trim();
+        if s.is_empty() {
+            return Err(ParseHexError::EmptyCode);
+        }
+        let digits = ["#", "0x", "0X"]
+            .iter()
+            .find_map(|prefix| s.strip_prefix(prefix))
+            .unwrap_or(s);
+        format!("#{}", digits).parse()
+    }
+
+    /// Sorts the channels of a [`HexRGB`] value from dimmest to brightest,
+    /// labelling each with its [`ColourChannel`].
+    ///
+    /// Channels with equal brightness are sorted in the reverse of the order
+    /// of [`ColourChannel`], so red sorts after green, which sorts after blue.
+    ///
+    /// [`ColourChannel`]: sht::ColourChannel
+    fn sorted_channels(self) -> [(Ratio<T>, sht::ColourChannel); 3] {
+        let (red_hex, green_hex, blue_hex) = self.components();
+        let mut channels = [
+            (red_hex, sht::ColourChannel::Red),
+            (green_hex, sht::ColourChannel::Green),
+            (blue_hex, sht::ColourChannel::Blue),
+        ];
+        channels.sort_by(|(first, first_channel), (second, second_channel)| {
+            first
+                .cmp(second)
+                .then_with(|| second_channel.cmp(first_channel))
+        });
+        channels
+    }
+
+    /// Converts each channel to an `f64` on the unit interval, in the order
+    /// red, green, blue.
+    fn to_f64_channels(&self) -> [f64; 3]
+    where
+        T: ToPrimitive,
+    {
+        let (red, green, blue) = self.clone().components();
+        [
+            ratio_to_f64(&red),
+            ratio_to_f64(&green),
+            ratio_to_f64(&blue),
+        ]
+    }
+
+    /// Constructs a [`HexRGB`] from `f64` channels in the order red, green,
+    /// blue, clamping each to the unit interval.
+    fn from_f64_channels([red, green, blue]: [f64; 3]) -> Self
+    where
+        T: FromPrimitive,
+    {
+        HexRGB::new(f64_to_ratio(red), f64_to_ratio(green), f64_to_ratio(blue))
+    }
+
+    /// Convert a colour from [`HexRGB`] format to [`SHT`].
+    ///
+    /// If a component rounds onto the edge of its range, the colour becomes
+    /// the equivalent simpler code. For instance, a blend that rounds to 0 is
+    /// dropped, and a blend that rounds to 1 makes a secondary colour.
+    ///
+    /// # Arguments
+    /// * `precision` - How many duodecimal digits to round the result of
+    ///   conversion to.
+    ///
+    /// # Example
+    /// ```
+    /// # #[cfg(feature = "parse")]
+    /// # {
+    /// use ::sht_colour::{rgb::HexRGB, sht::SHT};
+    ///
+    /// let red_sht = "r".parse::<SHT<u32>>().unwrap();
+    /// let red_rgb = "#F00".parse::<HexRGB<u32>>().unwrap();
+    ///
+    /// assert_eq!(red_rgb.to_sht(1), red_sht);
+    /// # }
+    /// ```
+    ///
+    /// # Panics
+    /// **Panics on overflow!** Use [`try_to_sht`] to handle it instead.
+    ///
+    /// [`SHT`]: sht::SHT
+    /// [`try_to_sht`]: HexRGB::try_to_sht
+    pub fn to_sht(self, precision: usize) -> sht::SHT<T>
+    where
+        T: Integer + Unsigned + Clone + From<u8> + CheckedMul,
+    {
+        let (channel_ratios, shade, tint) = self.to_sht_base(12.into(), precision);
+        sht::SHT::collapse(channel_ratios, shade, tint)
+            .expect("RGB to SHT should only create valid codes!")
+    }
+
+    /// Convert a colour from [`HexRGB`] format to [`SHT`], as in [`to_sht`],
+    /// but rounding each component with the chosen [`RoundingMode`] instead
+    /// of rounding halves up.
+    ///
+    /// # Arguments
+    /// * `precision` - How many duodecimal digits to round the result of
+    ///   conversion to.
+    /// * `mode` - How to round components that lie between two codes.
+    ///
+    /// # Example
+    /// ```
+    /// # #[cfg(feature = "parse")]
+    /// # {
+    /// use ::sht_colour::{rgb::HexRGB, sht::SHT, Ratio, RoundingMode};
+    ///
+    /// // the shade is 6.5 twelfths
+    /// let red = HexRGB::<u32>::new(Ratio::new(13, 24), Ratio::new(0, 1), Ratio::new(0, 1));
+    ///
+    /// assert_eq!(red.to_sht(1), "7r".parse::<SHT<u32>>().unwrap());
+    /// assert_eq!(
+    ///     red.to_sht_with_rounding(1, RoundingMode::HalfEven),
+    ///     "6r".parse::<SHT<u32>>().unwrap()
+    /// );
+    /// # }
+    /// ```
+    ///
+    /// # Panics
+    /// **Panics on overflow!**
+    ///
+    /// [`SHT`]: sht::SHT
+    /// [`to_sht`]: HexRGB::to_sht
+    pub fn to_sht_with_rounding(self, precision: usize, mode: RoundingMode) -> sht::SHT<T>
+    where
+        T: Integer + Unsigned + Clone + From<u8> + CheckedMul,
+    {
+        let (channel_ratios, shade, tint) = self
+            .checked_sht_components(|ratio| {
+                round_denominator_with::<T>(ratio, 12.into(), precision, <_>::zero(), mode)
+            })
+            .expect("Overflow converting RGB to SHT");
+        sht::SHT::collapse(channel_ratios, shade, tint)
+            .expect("RGB to SHT should only create valid codes!")
+    }
+
+    /// Convert a colour from [`HexRGB`] format to [`SHT`], as in [`to_sht`],
+    /// but returning an error instead of panicking.
     ///
     /// # Arguments
-    ///
-    /// * `red` - The absolute brightness of the red channel.
-    /// * `green` - The absolute brightness of the green channel.
-    /// * `blue` - The absolute brightness of the blue channel.
+    /// * `precision` - How many duodecimal digits to round the result of
+    ///   conversion to.
     ///
     /// # Example
     /// ```
-    /// use ::sht_colour::{rgb::HexRGB, Ratio};
+    /// # #[cfg(feature = "parse")]
+    /// # {
+    /// use ::sht_colour::{
+    ///     rgb::HexRGB,
+    ///     sht::{ConversionError, SHTValueError, SHT},
+    ///     Ratio,
+    /// };
     ///
-    /// let dark_red = <HexRGB<u8>>::new(
-    ///     Ratio::new(0x5, 0xF),
-    ///     Ratio::new(0x0, 0xF),
-    ///     Ratio::new(0x0, 0xF),
-    /// );
+    /// let red = "#F00".parse::<HexRGB<u8>>().unwrap();
+    /// assert_eq!(red.try_to_sht(1), Ok("r".parse::<SHT<u8>>().unwrap()));
+    /// assert_eq!(red.try_to_sht(3), Err(ConversionError::Overflow));
     ///
-    /// assert_eq!(dark_red, "#500".parse().unwrap());
+    /// let too_bright = HexRGB::<u8>::new(Ratio::new(2, 1), Ratio::new(0, 1), Ratio::new(0, 1));
+    /// assert_eq!(
+    ///     too_bright.try_to_sht(1),
+    ///     Err(ConversionError::InvalidComponents(vec![
+    ///         SHTValueError::ValueOutOfBounds
+    ///     ]))
+    /// );
+    /// # }
     /// ```
-    pub fn new(red: Ratio<T>, green: Ratio<T>, blue: Ratio<T>) -> HexRGB<T> {
-        HexRGB {
-            inner: RGB::new(red, green, blue),
+    ///
+    /// # Errors
+    /// Will return [`ConversionError::Overflow`] if computing the components,
+    /// or rounding them to `precision` duodecimal digits, overflows the
+    /// integer type. Will return [`ConversionError::InvalidComponents`] if a
+    /// channel is greater than 1.
+    ///
+    /// [`SHT`]: sht::SHT
+    /// [`to_sht`]: HexRGB::to_sht
+    /// [`ConversionError::Overflow`]: sht::ConversionError::Overflow
+    /// [`ConversionError::InvalidComponents`]: sht::ConversionError::InvalidComponents
+    pub fn try_to_sht(self, precision: usize) -> Result<sht::SHT<T>, sht::ConversionError>
+    where
+        T: Integer + Unsigned + Clone + From<u8> + CheckedMul,
+    {
+        if [self.red(), self.green(), self.blue()]
+            .iter()
+            .any(|channel| *channel > Ratio::one())
+        {
+            return Err(sht::ConversionError::InvalidComponents(vec![
+                sht::SHTValueError::ValueOutOfBounds,
+            ]));
         }
+        let (channel_ratios, shade, tint) = self
+            .checked_sht_components(|ratio| {
+                round_denominator::<T>(ratio, 12.into(), precision, <_>::zero())
+            })
+            .ok_or(sht::ConversionError::Overflow)?;
+        sht::SHT::collapse(channel_ratios, shade, tint)
+            .map_err(sht::ConversionError::InvalidComponents)
     }
 
-    /// Splits a [`HexRGB`] value into its individual components, the channels
-    /// red, green and blue.
+    /// Convert a colour from [`HexRGB`] format to [`SHT`], also reporting
+    /// whether rounding changed which channels are brightest.
+    ///
+    /// At low precision, rounding can move a colour between the kinds of
+    /// `SHT`: a small blend can round away to leave a lone primary, a large
+    /// blend can round up to a secondary colour, and a very dark or light
+    /// colour can round to a grey. The returned `bool` is `true` if the
+    /// rounded `SHT` has the same primary, direction or secondary as the
+    /// exact conversion, ignoring the values of the shade, tint and blend.
+    ///
+    /// # Arguments
+    /// * `precision` - How many duodecimal digits to round the result of
+    ///   conversion to.
     ///
     /// # Example
     /// ```
-    /// use ::sht_colour::rgb;
+    /// # #[cfg(feature = "parse")]
+    /// # {
+    /// use ::sht_colour::{rgb::HexRGB, sht::SHT};
     ///
-    /// let colour = "#123456".parse::<rgb::HexRGB<u16>>().unwrap();
+    /// let nearly_yellow = "#FFF800".parse::<HexRGB<u32>>().unwrap();
     ///
-    /// let (red, green, blue) = colour.clone().components();
-    /// let new_colour = <rgb::HexRGB<_>>::new(red, green, blue);
+    /// let (colour, unchanged) = nearly_yellow.clone().to_sht_checked_structure(2);
+    /// assert_eq!(colour, "rE8g".parse::<SHT<_>>().unwrap());
+    /// assert!(unchanged);
     ///
-    /// assert_eq!(colour, new_colour);
+    /// let (colour, unchanged) = nearly_yellow.to_sht_checked_structure(1);
+    /// assert_eq!(colour, "y".parse::<SHT<_>>().unwrap());
+    /// assert!(!unchanged);
+    /// # }
     /// ```
-    pub fn components(self) -> (Ratio<T>, Ratio<T>, Ratio<T>) {
-        let Self {
-            inner: RGB { r, g, b },
-        } = self;
-        (r, g, b)
+    ///
+    /// # Panics
+    /// **Panics on overflow!**
+    ///
+    /// [`SHT`]: sht::SHT
+    pub fn to_sht_checked_structure(self, precision: usize) -> (sht::SHT<T>, bool)
+    where
+        T: Integer + Unsigned + Clone + From<u8> + CheckedMul,
+    {
+        let (exact_channel_ratios, _, _) = self.clone().sht_components(|ratio| ratio);
+        let rounded = self.to_sht(precision);
+        let (channel_ratios, _, _) = rounded.clone().components();
+        let unchanged = channel_ratios.same_structure(&exact_channel_ratios);
+        (rounded, unchanged)
     }
 
-    /// Convert a colour from [`HexRGB`] format to [`SHT`].
+    /// **Experimental:** converts a colour from [`HexRGB`] format to the
+    /// components of an [`SHT`], rounding to digits in any base instead of
+    /// base 12.
+    ///
+    /// This is not part of the SHT spec, and is intended for exploring how
+    /// other quantisations of the same colour model behave. The components
+    /// are those that [`SHT::components`] would return, except that the
+    /// shade, tint and blend are rounded to multiples of `base^-precision`.
+    /// They are not checked, so after rounding they may not make a valid
+    /// `SHT`, for instance if a blend rounds to 0.
+    ///
+    /// [`to_sht`] and [`try_to_sht`] are the same as this method with a base
+    /// of 12, followed by collapsing a blend that rounded to 0 or 1, a shade
+    /// that rounded to 0 or a tint that rounded to 1 into a valid `SHT`.
     ///
     /// # Arguments
-    /// * `precision` - How many duodecimal digits to round the result of
+    /// * `base` - The base to round digits within.
+    /// * `precision` - How many digits in that base to round the result of
     ///   conversion to.
     ///
     /// # Example
     /// ```
-    /// use ::sht_colour::{rgb::HexRGB, sht::SHT};
+    /// use ::sht_colour::{rgb::HexRGB, ChannelRatios, ColourChannel, Ratio};
     ///
-    /// let red_sht = "r".parse::<SHT<u32>>().unwrap();
-    /// let red_rgb = "#F00".parse::<HexRGB<u32>>().unwrap();
+    /// let colour = "#FF6633".parse::<HexRGB<u32>>().unwrap();
+    /// let (channel_ratios, shade, tint) = colour.to_sht_base(10, 1);
     ///
-    /// assert_eq!(red_rgb.to_sht(1), red_sht);
+    /// assert_eq!(
+    ///     channel_ratios,
+    ///     ChannelRatios::OneBrightestChannel {
+    ///         primary: ColourChannel::Red,
+    ///         direction_blend: Some((ColourChannel::Green, Ratio::new(3, 10))),
+    ///     }
+    /// );
+    /// assert_eq!(shade, Ratio::from_integer(1));
+    /// assert_eq!(tint, Ratio::new(2, 10));
     /// ```
     ///
     /// # Panics
     /// **Panics on overflow!**
     ///
     /// [`SHT`]: sht::SHT
-    pub fn to_sht(self, precision: usize) -> sht::SHT<T>
+    /// [`SHT::components`]: sht::SHT::components
+    /// [`to_sht`]: HexRGB::to_sht
+    /// [`try_to_sht`]: HexRGB::try_to_sht
+    pub fn to_sht_base(
+        self,
+        base: T,
+        precision: usize,
+    ) -> (sht::ChannelRatios<T>, Ratio<T>, Ratio<T>)
     where
         T: Integer + Unsigned + Clone + From<u8> + CheckedMul,
     {
-        // Round duodecimal number to precision
-        let round =
-            |ratio: Ratio<T>| round_denominator::<T>(ratio, 12.into(), precision, <_>::zero());
+        // Round number to precision in the given base
+        self.checked_sht_components(|ratio| {
+            round_denominator::<T>(ratio, base.clone(), precision, <_>::zero())
+        })
+        .expect("Overflow converting RGB to SHT")
+    }
 
-        let (red_hex, green_hex, blue_hex) = self.components();
-        let mut channels = [(red_hex, 'r'), (green_hex, 'g'), (blue_hex, 'b')];
-        channels.sort();
-        let [(minimum, _), (middle, mid_channel), (maximum, max_channel)] = channels;
+    /// Finds the smallest precision at which a [`HexRGB`] value survives a
+    /// round trip through [`SHT`] exactly.
+    ///
+    /// This is the smallest `precision` such that converting the colour with
+    /// [`to_sht`] and back with [`SHT::to_rgb`], both at `precision`, loses
+    /// nothing. At that precision both conversions are exact: every SHT
+    /// component can be written with that many base-12 digits, and every
+    /// channel with that many hex digits.
+    ///
+    /// Returns `None` if no precision suffices, because the colour has no
+    /// exact SHT code, or because the hex digits needed would overflow `T`.
+    /// Most hex codes have no exact SHT code, since a channel over 255 only
+    /// has a finite base-12 expansion if it is a multiple of 1/3.
+    ///
+    /// # Example
+    /// ```
+    /// use ::sht_colour::rgb::HexRGB;
+    ///
+    /// let red = "#FF0000".parse::<HexRGB<u32>>().unwrap();
+    /// let pale_red = "#FFAAAA".parse::<HexRGB<u32>>().unwrap();
+    /// let orange = "#FF8811".parse::<HexRGB<u32>>().unwrap();
+    ///
+    /// assert_eq!(red.lossless_sht_precision(), Some(1));
+    /// assert_eq!(pale_red.lossless_sht_precision(), Some(1));
+    /// assert_eq!(orange.lossless_sht_precision(), None);
+    /// ```
+    ///
+    /// # Panics
+    /// **Panics on overflow!**
+    ///
+    /// [`SHT`]: sht::SHT
+    /// [`to_sht`]: HexRGB::to_sht
+    /// [`SHT::to_rgb`]: sht::SHT::to_rgb
+    pub fn lossless_sht_precision(self) -> Option<usize>
+    where
+        T: From<u8>,
+    {
+        let (channel_ratios, shade, tint) = self.clone().sht_components(|ratio| ratio);
+        let sht_precision = sht::SHT::collapse(channel_ratios, shade, tint)
+            .ok()?
+            .exact_precision()?;
+        let (red, green, blue) = self.components();
+        // a channel has an exact hex code with `precision` digits when its
+        // denominator divides `16^precision - 1`
+        (sht_precision.max(1)..)
+            .map_while(|precision| {
+                hex_denominator::<T>(precision)
+                    .ok()
+                    .map(|denominator| (precision, denominator))
+            })
+            .find(|(_, denominator)| {
+                [&red, &green, &blue]
+                    .iter()
+                    .all(|channel| (denominator.clone() % channel.denom().clone()).is_zero())
+            })
+            .map(|(precision, _)| precision)
+    }
+
+    /// Converts a colour from [`HexRGB`] format to the components of an
+    /// [`SHT`], passing the shade, tint and blend through `round`.
+    ///
+    /// # Panics
+    /// **Panics on overflow!**
+    ///
+    /// [`SHT`]: sht::SHT
+    fn sht_components(
+        self,
+        round: impl Fn(Ratio<T>) -> Ratio<T>,
+    ) -> (sht::ChannelRatios<T>, Ratio<T>, Ratio<T>) {
+        self.checked_sht_components(|ratio| Some(round(ratio)))
+            .expect("Overflow converting RGB to SHT")
+    }
+
+    /// Converts a colour from [`HexRGB`] format to the components of an
+    /// [`SHT`], as in [`sht_components`], returning `None` if the arithmetic
+    /// overflows or `round` returns `None`.
+    ///
+    /// Every channel must be no greater than 1.
+    ///
+    /// [`SHT`]: sht::SHT
+    /// [`sht_components`]: HexRGB::sht_components
+    fn checked_sht_components(
+        self,
+        round: impl Fn(Ratio<T>) -> Option<Ratio<T>>,
+    ) -> Option<(sht::ChannelRatios<T>, Ratio<T>, Ratio<T>)> {
+        let [(minimum, _), (middle, mid_channel), (maximum, max_channel)] = self.sorted_channels();
 
-        let tint = round(minimum.clone());
+        let tint = round(minimum.clone())?;
         let shade = if maximum.is_zero() {
             <num::rational::Ratio<_>>::zero()
         } else if minimum == maximum {
             <_>::one()
         } else {
             round(
-                (maximum.clone() - minimum.clone())
-                    / (<num::rational::Ratio<_>>::one() - minimum.clone()),
-            )
+                checked_ratio_sub(&maximum, &minimum)?
+                    .checked_div(&checked_ratio_sub(&<_>::one(), &minimum)?)?,
+            )?
         };
 
         let channel_ratios;
         if maximum > middle {
-            let primary = char_to_primary(max_channel);
+            let primary = max_channel;
 
             // if `middle == minimum`, `direction_blend` set to `None`
-            let direction_blend = (middle > minimum).then(|| {
-                let direction = char_to_primary(mid_channel);
-                let blend = (middle - minimum.clone()) / (maximum - minimum);
-                (direction, round(blend))
-            });
+            let direction_blend = if middle > minimum {
+                let direction = mid_channel;
+                let blend = checked_ratio_sub(&middle, &minimum)?
+                    .checked_div(&checked_ratio_sub(&maximum, &minimum)?)?;
+                Some((direction, round(blend)?))
+            } else {
+                None
+            };
             channel_ratios = sht::ChannelRatios::OneBrightestChannel {
                 primary,
                 direction_blend,
             };
         } else if middle > minimum {
-            let secondary = chars_to_secondary(max_channel, mid_channel);
+            let secondary = sht::SecondaryColour::from_primaries(max_channel, mid_channel);
             channel_ratios = sht::ChannelRatios::TwoBrightestChannels { secondary };
         } else {
             channel_ratios = sht::ChannelRatios::ThreeBrightestChannels;
         }
-        sht::SHT::new(channel_ratios, shade, tint)
-            .expect("RGB to SHT should only create valid codes!")
+        Some((channel_ratios, shade, tint))
     }
 }
 
@@ -209,29 +1247,212 @@ where
     }
 }
 
+/// Widens a [`HexRGB<u8>`] to a [`HexRGB<u16>`] without changing its colour.
+///
+/// A channel of `n / 255` is scaled to `(n × 257) / 65535`, which is the
+/// same ratio. Multiplying by 257 repeats the bits of the byte, as in
+/// `0xAB` → `0xABAB`, so `#AB` becomes `#ABAB`, and a full `0xFF` becomes
+/// `0xFFFF` rather than the `0xFF00` of multiplying by 256.
+///
+/// # Example
+/// ```
+/// use ::sht_colour::{rgb::HexRGB, Ratio};
+///
+/// let narrow = HexRGB::<u8>::from_bytes([0xFF, 0xAB, 0x00]);
+/// let wide = HexRGB::<u16>::from(narrow);
+///
+/// assert_eq!(wide.red(), Ratio::new(0xFFFF, 0xFFFF));
+/// assert_eq!(wide.green(), Ratio::new(0xABAB, 0xFFFF));
+/// ```
+impl From<HexRGB<u8>> for HexRGB<u16> {
+    fn from(hex: HexRGB<u8>) -> Self {
+        let widen = |channel: Ratio<u8>| {
+            Ratio::new(u16::from(*channel.numer()), u16::from(*channel.denom()))
+        };
+        HexRGB::new(widen(hex.red()), widen(hex.green()), widen(hex.blue()))
+    }
+}
+
+/// Narrows a [`HexRGB<u16>`] to a [`HexRGB<u8>`] without changing its
+/// colour, the inverse of widening a [`HexRGB<u8>`].
+///
+/// Each channel is stored in lowest terms, so a channel of `0xABAB / 0xFFFF`
+/// narrows to `0xAB / 0xFF`, and every widened colour narrows back to the
+/// original.
+///
+/// # Example
+/// ```
+/// use ::std::convert::TryFrom;
+/// use ::sht_colour::{rgb::HexRGB, Ratio};
+///
+/// let narrow = HexRGB::<u8>::from_bytes([0xFF, 0xAB, 0x00]);
+/// let fine = HexRGB::<u16>::new(Ratio::new(0xABCD, 0xFFFF), Ratio::new(0, 1), Ratio::new(0, 1));
+///
+/// assert_eq!(HexRGB::<u8>::try_from(HexRGB::<u16>::from(narrow)), Ok(narrow));
+/// assert!(HexRGB::<u8>::try_from(fine).is_err());
+/// ```
+///
+/// # Errors
+/// Will return `Err` if the numerator or denominator of any channel does not
+/// fit in a `u8`, so the colour cannot be represented exactly.
+impl TryFrom<HexRGB<u16>> for HexRGB<u8> {
+    type Error = TryFromIntError;
+
+    fn try_from(hex: HexRGB<u16>) -> Result<Self, Self::Error> {
+        let narrow = |channel: Ratio<u16>| -> Result<Ratio<u8>, TryFromIntError> {
+            Ok(Ratio::new(
+                u8::try_from(*channel.numer())?,
+                u8::try_from(*channel.denom())?,
+            ))
+        };
+        Ok(HexRGB::new(
+            narrow(hex.red())?,
+            narrow(hex.green())?,
+            narrow(hex.blue())?,
+        ))
+    }
+}
+
+/// Formats the colour as a hex code, such as `"#FF8811"`.
+///
+/// # Flags
+///
+/// * `precision` - The number of hex digits per channel, 2 by default. Each
+///   channel is rounded to the nearest value with that many digits, with
+///   halves rounded up, so `"{:.1}"` gives a three-digit code such as
+///   `"#F81"`. Numbers of digits whose denominator would not fit in `T` are
+///   capped at the largest that does, which is 8 for `u32`.
+/// * `#` - The alternate flag writes every letter in lowercase, as in
+///   `"#ff8811"`. This matches the alternate flag of [`SHT`].
+/// * `width`, fill and alignment - If an alignment is given, pad the code to
+///   at least `width` characters with the fill character, as for strings and
+///   as in the [`Display` implementation] of [`SHT`].
+///
+/// Without an alignment, `width` is instead the number of digits per channel,
+/// as if it were the precision, so `"{:1}"` also gives `"#F81"`. This is kept
+/// for backward compatibility. Other flags, such as `+` and `0`, have no
+/// effect.
+///
+/// # Example
+/// ```
+/// use ::sht_colour::rgb::HexRGB;
+///
+/// let colour = "#FF8811".parse::<HexRGB<u32>>().unwrap();
+/// let grey = "#808080".parse::<HexRGB<u32>>().unwrap();
+///
+/// assert_eq!(format!("{}", colour), "#FF8811");
+/// assert_eq!(format!("{:.1}", colour), "#F81");
+/// assert_eq!(format!("{:.1}", grey), "#888");
+/// assert_eq!(format!("{:1}", colour), "#F81");
+/// assert_eq!(format!("{:#}", colour), "#ff8811");
+/// assert_eq!(format!("{:*>6.1}", colour), "**#F81");
+/// ```
+///
+/// # Panics
+/// Formatting with [`format!`] panics if scaling a channel to the number of
+/// digits overflows `T`, which can only happen for channels whose
+/// denominators share few factors with `16^digits - 1`.
+///
+/// [`SHT`]: crate::SHT
+/// [`Display` implementation]: crate::SHT#impl-Display
 impl<T> Display for HexRGB<T>
 where
     T: Unsigned + Integer + Clone + CheckedMul + From<u8> + UpperHex,
 {
     fn fmt(&self, formatter: &mut Formatter) -> FMTResult {
-        let width = formatter.width().unwrap_or(2);
-        let denominator = checked_pow(<T>::from(16), width).ok_or(Error)? - <T>::one();
+        let (digits, denominator) = hex_digits::<T>(formatter);
+        let (red, green, blue) = self.clone().components();
+        let code = format!(
+            "#{:0digits$X}{:0digits$X}{:0digits$X}",
+            scale_channel(red, &denominator)?,
+            scale_channel(green, &denominator)?,
+            scale_channel(blue, &denominator)?,
+            digits = digits
+        );
+        write_hex_code(formatter, code)
+    }
+}
 
-        let from_ratio = |ratio: Ratio<T>| {
-            ratio
-                .checked_mul(&Ratio::from_integer(denominator.clone()))
-                .ok_or(Error)
-        };
+/// Scales a channel to a numerator over the denominator of a hex channel,
+/// rounding to the nearest integer with halves rounded up.
+///
+/// # Errors
+/// Will return `Err` if the scaled channel overflows the integer type.
+fn scale_channel<T>(channel: Ratio<T>, denominator: &T) -> Result<T, Error>
+where
+    T: Integer + CheckedMul + Clone,
+{
+    Ok(channel
+        .checked_mul(&Ratio::from_integer(denominator.clone()))
+        .ok_or(Error)?
+        .round()
+        .to_integer())
+}
 
-        let (red, green, blue) = self.clone().components();
-        write!(
-            formatter,
-            "#{:0width$X}{:0width$X}{:0width$X}",
-            from_ratio(red)?.to_integer(),
-            from_ratio(green)?.to_integer(),
-            from_ratio(blue)?.to_integer(),
-            width = width
-        )
+/// Computes `16^digits - 1`, the denominator of a hex channel with that many
+/// digits, without overflowing when only `16^digits` itself would not fit, as
+/// for two digits in a `u8`.
+///
+/// # Errors
+/// Will return `Err` if the denominator does not fit in the integer type.
+fn hex_denominator<T>(digits: usize) -> Result<T, OverflowError>
+where
+    T: Integer + CheckedMul + Clone,
+    u8: Into<T>,
+{
+    match digits.checked_sub(1) {
+        None => Ok(T::zero()),
+        Some(exponent) => {
+            // `16^digits - 1 = (16^exponent - 1) * 16 + 15`, and adding 15 to
+            // a multiple of 16 cannot overflow
+            let power = safe_denominator(16.into(), exponent)?;
+            let shifted = (power - T::one())
+                .checked_mul(&16.into())
+                .ok_or(OverflowError)?;
+            Ok(shifted + 15.into())
+        }
+    }
+}
+
+/// Finds the number of hex digits per channel requested by a formatter, from
+/// its precision, or from its width if it has no alignment to pad to that
+/// width, along with the matching denominator from [`hex_denominator`].
+///
+/// The number of digits is capped at the largest whose denominator fits in
+/// the integer type.
+fn hex_digits<T>(formatter: &Formatter) -> (usize, T)
+where
+    T: Integer + CheckedMul + Clone,
+    u8: Into<T>,
+{
+    let requested = match (formatter.precision(), formatter.align()) {
+        (Some(precision), _) => precision,
+        (None, None) => formatter.width().unwrap_or(2),
+        (None, Some(_)) => 2,
+    };
+    let mut capped = (0, T::zero());
+    for digits in 1..=requested {
+        match hex_denominator(digits) {
+            Ok(denominator) => capped = (digits, denominator),
+            Err(_) => break,
+        }
+    }
+    capped
+}
+
+/// Writes a hex code in uppercase, or lowercase with the alternate flag,
+/// padding it only if the formatter has an alignment, since otherwise the
+/// width is the number of digits per channel.
+fn write_hex_code(formatter: &mut Formatter, code: String) -> FMTResult {
+    let code = if formatter.alternate() {
+        code.to_lowercase()
+    } else {
+        code
+    };
+    if formatter.align().is_some() {
+        write_padded(formatter, &code)
+    } else {
+        write!(formatter, "{}", code)
     }
 }
 
@@ -251,7 +1472,7 @@ where
         }
 
         let digits = &s[1..];
-        if digits.len() % 3 != 0 {
+        if !digits.len().is_multiple_of(3) {
             return Err(ParseHexError::InvalidDigitCount);
         }
 
@@ -280,6 +1501,274 @@ where
     }
 }
 
+/// Normalises a hex code string, re-emitting it with a fixed number of hex
+/// digits per channel.
+///
+/// The code is parsed as usual, so it must begin with `'#'`, and then each
+/// channel is rounded to the nearest value expressible in `digits` digits. The
+/// output uses uppercase digits, like the [`Display` implementation] of
+/// [`HexRGB`].
+///
+/// # Example
+/// ```
+/// use ::sht_colour::rgb::normalise_hex;
+///
+/// assert_eq!(normalise_hex("#abc", 2).unwrap(), "#AABBCC");
+/// assert_eq!(normalise_hex("#AABBCC", 1).unwrap(), "#ABC");
+/// assert_eq!(normalise_hex("#1F1F1F", 1).unwrap(), "#222");
+/// ```
+///
+/// # Errors
+/// Will return `Err` if the input is not a valid hex code, if `digits` is zero,
+/// or if the input or output needs more digits than can be computed
+/// precisely (15 digits per channel).
+///
+/// [`Display` implementation]: HexRGB#impl-Display
+pub fn normalise_hex(s: &str, digits: usize) -> Result<String, ParseHexError> {
+    if digits == 0 {
+        return Err(ParseHexError::InvalidDigitCount);
+    }
+    let (red, green, blue) = s.parse::<HexRGB<u64>>()?.components();
+    let rounded = HexRGB::new(
+        round_hex_channel(red, digits)?,
+        round_hex_channel(green, digits)?,
+        round_hex_channel(blue, digits)?,
+    );
+    Ok(format!("{:1$}", rounded, digits))
+}
+
+/// Checks whether a hex code is written in shorthand, with one hex digit per
+/// channel, like `"#ABC"`, rather than an expanded form with more digits, like
+/// `"#AABBCC"`.
+///
+/// This only looks at how the code is written, not at which colour it
+/// describes, so `"#AABBCC"` is not shorthand even though it could be
+/// shortened without changing its colour.
+///
+/// # Example
+/// ```
+/// use ::sht_colour::rgb::hex_is_shorthand;
+///
+/// assert_eq!(hex_is_shorthand("#ABC"), Some(true));
+/// assert_eq!(hex_is_shorthand("#AABBCC"), Some(false));
+/// assert_eq!(hex_is_shorthand("ABC"), None);
+/// ```
+///
+/// Returns `None` if the code could not be parsed, in the same way as
+/// [`normalise_hex`], which accepts up to 15 digits per channel.
+pub fn hex_is_shorthand(s: &str) -> Option<bool> {
+    s.parse::<HexRGB<u64>>().ok()?;
+    Some(s.len() == 4)
+}
+
+/// Converts many [`HexRGB`] values to 8-bit channels at once, giving the same
+/// result as calling [`to_bytes`] on each.
+///
+/// Colours parsed from hex codes with one or two digits per channel have
+/// denominators that divide 255, so their channels can be scaled to bytes
+/// with a single integer multiplication, without the [`Ratio`] arithmetic
+/// needed to round them. The scale factor is computed once for each run of
+/// channels sharing a denominator, which is usually the whole slice, and the
+/// output is allocated once up front. The remaining channels fall back to
+/// the rounding in [`to_bytes`].
+///
+/// The inner loop has no allocations and few branches, so the compiler may
+/// be able to vectorise it, but this is not guaranteed.
+///
+/// # Example
+/// ```
+/// use ::sht_colour::rgb::{to_rgb8_batch, HexRGB};
+///
+/// let colours = ["#FF8811", "#ABC", "#123456789"]
+///     .iter()
+///     .map(|s| s.parse::<HexRGB<u64>>().unwrap())
+///     .collect::<Vec<_>>();
+///
+/// assert_eq!(
+///     to_rgb8_batch(&colours),
+///     [[0xFF, 0x88, 0x11], [0xAA, 0xBB, 0xCC], [0x12, 0x45, 0x78]]
+/// );
+/// ```
+///
+/// [`to_bytes`]: HexRGB::to_bytes
+pub fn to_rgb8_batch<T>(colours: &[HexRGB<T>]) -> Vec<[u8; 3]>
+where
+    T: Unsigned + Integer + Clone + CheckedMul + From<u8> + ToPrimitive,
+{
+    let full: T = 255.into();
+    // the last denominator seen, and the factor scaling it to 255, if any
+    let mut cached: Option<(T, Option<u64>)> = None;
+    let mut to_byte = |channel: &Ratio<T>| {
+        let denominator = channel.denom();
+        let factor = match &cached {
+            Some((cached_denominator, factor)) if cached_denominator == denominator => *factor,
+            _ => {
+                let factor = if full.is_multiple_of(denominator) {
+                    (full.clone() / denominator.clone()).to_u64()
+                } else {
+                    None
+                };
+                cached = Some((denominator.clone(), factor));
+                factor
+            }
+        };
+        match (factor, channel.numer().to_u64()) {
+            (Some(factor), Some(numerator)) => {
+                u8::try_from(numerator.saturating_mul(factor)).unwrap_or(u8::MAX)
+            }
+            _ => channel_to_u8(channel.clone()),
+        }
+    };
+    let mut bytes = Vec::with_capacity(colours.len());
+    for colour in colours {
+        let RGB { r, g, b } = &colour.inner;
+        bytes.push([to_byte(r), to_byte(g), to_byte(b)]);
+    }
+    bytes
+}
+
+/// Rewrites every hex colour in a CSS stylesheet, leaving the rest of the text
+/// untouched.
+///
+/// Hex colours are only recognised in the values of declarations, that is
+/// between a `':'` inside a `{...}` block and the following `';'` or `'}'`.
+/// A `':'` outside a block, or one followed by a `'{'` before any `';'` or
+/// `'}'`, belongs to a selector such as `a:hover #add`, so ID selectors are
+/// not mistaken for colours, even after a pseudo-class or inside an at-rule
+/// such as `@media`. Comments and quoted strings are copied
+/// unchanged. Only the three-digit `#RGB` and six-digit `#RRGGBB` forms are
+/// rewritten; codes with an alpha channel are left as they are.
+///
+/// Each rewritten code keeps its original number of digits, so each channel
+/// of a three-digit code is rounded to a single digit. Codes written entirely
+/// in lowercase stay lowercase.
+///
+/// # Arguments
+/// * `css` - The stylesheet to rewrite.
+/// * `f` - The transformation to apply to each colour.
+///
+/// # Example
+/// ```
+/// use ::sht_colour::rgb::{rewrite_css_colours, HexRGB};
+///
+/// let css = "#nav { color: #fff; border: 1px solid #FF8811 }";
+/// let inverted = rewrite_css_colours(css, |colour: HexRGB<u32>| {
+///     let (red, green, blue) = colour.components();
+///     let one = || sht_colour::Ratio::from_integer(1);
+///     HexRGB::new(one() - red, one() - green, one() - blue)
+/// });
+///
+/// assert_eq!(inverted, "#nav { color: #000; border: 1px solid #0077EE }");
+/// ```
+pub fn rewrite_css_colours<T>(css: &str, f: impl Fn(HexRGB<T>) -> HexRGB<T>) -> String
+where
+    T: Unsigned + Integer + Clone + CheckedMul + FromStr + From<u8> + UpperHex,
+{
+    let mut output = String::with_capacity(css.len());
+    // how many `{...}` blocks enclose the current position
+    let mut depth = 0_usize;
+    let mut in_value = false;
+    let mut rest = css;
+    while let Some(character) = rest.chars().next() {
+        // length of text to copy unchanged
+        let skip = match character {
+            '/' if rest.starts_with("/*") => rest[2..].find("*/").map_or(rest.len(), |end| end + 4),
+            '"' | '\'' => rest[1..].find(character).map_or(rest.len(), |end| end + 2),
+            '#' if in_value => {
+                let digits = rest[1..]
+                    .find(|c: char| !c.is_ascii_alphanumeric() && c != '-' && c != '_')
+                    .map_or(&rest[1..], |end| &rest[1..=end]);
+                let is_colour = (digits.len() == 3 || digits.len() == 6)
+                    && digits.chars().all(|c| c.is_ascii_hexdigit());
+                match format!("#{}", digits).parse::<HexRGB<T>>() {
+                    Ok(colour) if is_colour => {
+                        let lowercase = !digits.chars().any(|c| c.is_ascii_uppercase());
+                        let width = digits.len() / 3;
+                        if lowercase {
+                            output.push_str(&format!("{:#1$}", f(colour), width));
+                        } else {
+                            output.push_str(&format!("{:1$}", f(colour), width));
+                        }
+                        rest = &rest[1 + digits.len()..];
+                        continue;
+                    }
+                    _ => 1 + digits.len(),
+                }
+            }
+            ':' => {
+                // a selector's pseudo-class is followed by the block it opens
+                let next_delimiter = rest[1..].chars().find(|c| matches!(c, ';' | '{' | '}'));
+                in_value = depth > 0 && next_delimiter != Some('{');
+                1
+            }
+            '{' => {
+                depth += 1;
+                in_value = false;
+                1
+            }
+            '}' => {
+                depth = depth.saturating_sub(1);
+                in_value = false;
+                1
+            }
+            ';' => {
+                in_value = false;
+                1
+            }
+            other => other.len_utf8(),
+        };
+        output.push_str(&rest[..skip]);
+        rest = &rest[skip..];
+    }
+    output
+}
+
+/// Rounds a channel to the nearest value expressible with a certain number of
+/// hex digits, rounding halves up.
+///
+/// # Errors
+/// Will return `Err` if there are too many digits to compute the result
+/// without overflowing.
+fn round_hex_channel(channel: Ratio<u64>, digits: usize) -> Result<Ratio<u64>, ParseHexError> {
+    // at most 15 digits, so the new denominator fits in 60 bits and the
+    // intermediate products below fit in a `u128`
+    if digits > 15 {
+        return Err(ParseHexError::Overflow);
+    }
+    let new_denominator =
+        safe_denominator(16_u64, digits).map_err(|_| ParseHexError::Overflow)? - 1;
+    let (numerator, denominator) = (u128::from(*channel.numer()), u128::from(*channel.denom()));
+    let scaled = (2 * numerator * u128::from(new_denominator) + denominator) / (2 * denominator);
+    let scaled = u64::try_from(scaled).map_err(|_| ParseHexError::Overflow)?;
+    Ok(Ratio::new(scaled, new_denominator))
+}
+
+/// Rounds a channel to the nearest 8-bit value, rounding halves up.
+///
+/// Channels greater than 1 saturate at 255. The arithmetic is done in `u128`
+/// rather than `T`, so it cannot overflow even when `T` is too small to hold
+/// 255 times the numerator, as for `u8`. Integer types too large for `u128`
+/// fall back to rounding the nearest `f64`.
+fn channel_to_u8<T>(channel: Ratio<T>) -> u8
+where
+    T: Integer + Clone + ToPrimitive,
+{
+    if channel > Ratio::one() {
+        return u8::MAX;
+    }
+    let scaled = || {
+        let (numerator, denominator) = (channel.numer().to_u128()?, channel.denom().to_u128()?);
+        numerator
+            .checked_mul(2 * 255)?
+            .checked_add(denominator)?
+            .checked_div(denominator.checked_mul(2)?)
+    };
+    scaled().map_or_else(
+        || (ratio_to_f64(&channel) * 255.0).round() as u8,
+        |scaled| u8::try_from(scaled).unwrap_or(u8::MAX),
+    )
+}
+
 /// Splits a string into exact thirds.
 ///
 /// May give incorrect results if the string length is not a multiple of three.
@@ -304,50 +1793,46 @@ where
     T: Unsigned + Integer + FromStr + Clone + CheckedMul,
     u8: Into<T>,
 {
+    let denominator = hex_denominator(digits.len()).map_err(|_| ParseHexError::Overflow)?;
     Ok(<Ratio<T>>::new(
         T::from_str_radix(digits, 16).map_err(|_| ParseHexError::DigitParseError)?,
-        // Equivalent to (2 ** (len(digits) * 4)) - 1
-        checked_pow(
-            2.into(),
-            digits.len().checked_mul(4).ok_or(ParseHexError::Overflow)?,
-        )
-        .ok_or(ParseHexError::Overflow)?
-            - T::one(),
+        denominator,
     ))
 }
 
-/// Return the [`ColourChannel`] corresponding to a lowercase character.
-///
-/// # Errors
-/// Will panic if given a character other than `'r'`, `'g'` or `'b'`.
-///
-/// [`ColourChannel`]: sht::ColourChannel
-fn char_to_primary(c: char) -> sht::ColourChannel {
-    match c {
-        'r' => sht::ColourChannel::Red,
-        'g' => sht::ColourChannel::Green,
-        'b' => sht::ColourChannel::Blue,
-        _ => panic!("Invalid primary colour! {}", c),
-    }
-}
-
-/// Return the [`SecondaryColour`] corresponding to a pair of lowercase
-/// characters (which represent the primary colours that would add to the
-/// secondary colour).
-///
-/// # Panics
-/// Will panic if the pair does not contain exactly two distinct characters from
-/// `'r'`, `'g'` and `'b'`.
-///
-/// [`SecondaryColour`]: sht::SecondaryColour
-fn chars_to_secondary(a: char, b: char) -> sht::SecondaryColour {
-    match (a, b) {
-        ('g', 'b') | ('b', 'g') => sht::SecondaryColour::Cyan,
-        ('r', 'g') | ('g', 'r') => sht::SecondaryColour::Yellow,
-        ('r', 'b') | ('b', 'r') => sht::SecondaryColour::Magenta,
-        _ => panic!("Unexpected colour channel combination! {} {}", a, b),
-    }
-}
-
 #[cfg(test)]
 mod tests;
+
+/// Contains conversions between [`HexRGB`] values and the CIE XYZ and CIELAB
+/// colour spaces.
+mod lab;
+
+/// Contains functions for reading and writing palettes of [`HexRGB`] values.
+mod palette;
+
+/// Contains conversions between [`HexRGB`] values and the HSL colour space.
+mod hsl;
+
+/// Contains [`HexRGBA`], a hex code with an alpha channel.
+mod rgba;
+
+/// Contains conversions between [`HexRGB`] values and the HWB colour model.
+mod hwb;
+
+/// Contains conversions from [`HexRGB`] values to terminal escape codes.
+mod ansi;
+
+/// Contains conversions between [`HexRGB`] values and YCbCr.
+mod ycbcr;
+
+/// Contains functions for parsing [`HexRGB`] values from CSS with the
+/// `cssparser` crate.
+#[cfg(feature = "cssparser")]
+mod css;
+
+/// Contains conversions between [`HexRGB`] values and CSS functional
+/// notation.
+mod functional;
+
+/// Contains lookups between [`HexRGB`] values and the named colours of CSS.
+pub mod named;
@@ -0,0 +1,251 @@
+use super::{
+    channel_to_u8,
+    lab::{delta_e_1976, srgb_to_lab},
+    HexRGB,
+};
+use ::num::{rational::Ratio, CheckedMul, Integer, ToPrimitive, Unsigned};
+
+/// Represents possible errors reading a palette file.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+#[non_exhaustive]
+pub enum PaletteError {
+    /// The file did not begin with the `"GIMP Palette"` header line.
+    MissingHeader,
+    /// A line could not be read as a colour. Contains the line number,
+    /// counting from 1.
+    InvalidLine(usize),
+}
+
+/// Reads the colours from a GIMP palette (`.gpl`) file.
+///
+/// The file must begin with the line `GIMP Palette`. After that, blank lines,
+/// comments beginning with `'#'`, and the optional `Name:` and `Columns:`
+/// header fields are skipped. Every other line must contain the red, green and
+/// blue channels as integers from 0 to 255, separated by whitespace and
+/// optionally followed by a name for the colour, which is ignored.
+///
+/// # Example
+/// ```
+/// use ::sht_colour::rgb::{parse_gpl, HexRGB};
+///
+/// let contents = "GIMP Palette
+/// Name: Brand
+/// #
+/// 255 136  17 Orange
+///   0   0   0 Black
+/// ";
+///
+/// let colours = parse_gpl::<u16>(contents).unwrap();
+///
+/// assert_eq!(colours, ["#FF8811".parse().unwrap(), "#000".parse().unwrap()]);
+/// ```
+///
+/// # Errors
+/// Will return `Err` if the header is missing, or if a line could not be read
+/// as a colour.
+pub fn parse_gpl<T>(contents: &str) -> Result<Vec<HexRGB<T>>, PaletteError>
+where
+    T: Unsigned + Integer + Clone + CheckedMul + From<u8>,
+{
+    let mut lines = contents.lines().map(str::trim).enumerate();
+    match lines.next() {
+        Some((_, "GIMP Palette")) => {}
+        _ => return Err(PaletteError::MissingHeader),
+    }
+    lines
+        .filter(|(_, line)| {
+            !(line.is_empty()
+                || line.starts_with('#')
+                || line.starts_with("Name:")
+                || line.starts_with("Columns:"))
+        })
+        .map(|(index, line)| {
+            let error = PaletteError::InvalidLine(index + 1);
+            let mut channels = line
+                .split_whitespace()
+                .map(|channel| channel.parse::<u8>().map_err(|_| error));
+            let mut next_channel = || {
+                channels
+                    .next()
+                    .unwrap_or(Err(error))
+                    .map(|channel| Ratio::new(channel.into(), 255.into()))
+            };
+            Ok(HexRGB::new(
+                next_channel()?,
+                next_channel()?,
+                next_channel()?,
+            ))
+        })
+        .collect()
+}
+
+/// Writes colours to a GIMP palette (`.gpl`) file.
+///
+/// Each channel is rounded to the nearest 8-bit value, since that is the only
+/// precision the format supports. Each colour is named after its 8-bit hex
+/// code.
+///
+/// # Arguments
+/// * `name` - The name of the palette, stored in the file's header.
+/// * `colours` - The colours to write, in order.
+///
+/// # Example
+/// ```
+/// use ::sht_colour::rgb::{write_gpl, HexRGB};
+///
+/// let colours = ["#FF8811".parse::<HexRGB<u16>>().unwrap()];
+///
+/// assert_eq!(
+///     write_gpl("Brand", &colours),
+///     "GIMP Palette\nName: Brand\n#\n255 136  17\t#FF8811\n"
+/// );
+/// ```
+pub fn write_gpl<T>(name: &str, colours: &[HexRGB<T>]) -> String
+where
+    T: Unsigned + Integer + Clone + CheckedMul + From<u8> + ToPrimitive,
+{
+    let mut contents = format!("GIMP Palette\nName: {}\n#\n", name);
+    for colour in colours {
+        let (red, green, blue) = colour.clone().components();
+        let (red, green, blue) = (
+            channel_to_u8(red),
+            channel_to_u8(green),
+            channel_to_u8(blue),
+        );
+        contents.push_str(&format!(
+            "{:3} {:3} {:3}\t#{:02X}{:02X}{:02X}\n",
+            red, green, blue, red, green, blue
+        ));
+    }
+    contents
+}
+
+/// Finds pairs of colours in a palette that look almost identical.
+///
+/// Colours are compared by their CIE 1976 colour difference, `ΔE*ab`, which is
+/// their straight-line distance in the CIELAB colour space. A difference of
+/// about 2.3 is often quoted as the smallest that is just noticeable.
+///
+/// Every pair of colours is compared, so this takes `O(n²)` time for a palette
+/// of `n` colours.
+///
+/// # Arguments
+/// * `colours` - The palette to search.
+/// * `threshold` - Pairs with a colour difference strictly less than this are
+///   reported.
+///
+/// Returns the indices of each such pair, with the smaller index first, sorted
+/// in ascending order.
+///
+/// # Example
+/// ```
+/// use ::sht_colour::rgb::{find_near_duplicates, HexRGB};
+///
+/// let colours = ["#FF0000", "#00F", "#FE0101"]
+///     .iter()
+///     .map(|s| s.parse::<HexRGB<u32>>().unwrap())
+///     .collect::<Vec<_>>();
+///
+/// assert_eq!(find_near_duplicates(&colours, 2.3), [(0, 2)]);
+/// ```
+pub fn find_near_duplicates<T>(colours: &[HexRGB<T>], threshold: f64) -> Vec<(usize, usize)>
+where
+    T: Unsigned + Integer + Clone + CheckedMul + ToPrimitive,
+{
+    let labs = colours
+        .iter()
+        .map(|colour| srgb_to_lab(colour.to_f64_channels()))
+        .collect::<Vec<_>>();
+    let mut pairs = Vec::new();
+    for (first_index, &first) in labs.iter().enumerate() {
+        for (second_index, &second) in labs.iter().enumerate().skip(first_index + 1) {
+            if delta_e_1976(first, second) < threshold {
+                pairs.push((first_index, second_index));
+            }
+        }
+    }
+    pairs
+}
+
+/// Scores how well the colours of a palette can be told apart, from 0 for a
+/// useless palette to 1 for an ideal one.
+///
+/// The score is a heuristic for comparing candidate palettes, such as ones
+/// generated automatically, and combines three components, each measured in
+/// the CIELAB colour space and scaled to lie between 0 and 1:
+/// * Separation, with a weight of 1/2: the smallest CIE 1976 colour
+///   difference, `ΔE*ab`, between any two colours, divided by 50 and capped
+///   at 1. A palette with two nearly identical colours scores badly however
+///   well spread the rest are.
+/// * Lightness spread, with a weight of 1/4: the difference between the
+///   largest and smallest `L*`, divided by 100.
+/// * Hue distribution, with a weight of 1/4: how evenly the hues of the
+///   colourful colours are spread around the colour wheel, measured as 1
+///   minus the length of the mean of their hue directions. Colours with a
+///   chroma below 10 are treated as greys and ignored, and a palette with
+///   fewer than two colourful colours scores 0 here.
+///
+/// Every pair of colours is compared, so this takes `O(n²)` time for a palette
+/// of `n` colours. A palette of fewer than two colours scores 0.
+///
+/// # Example
+/// ```
+/// use ::sht_colour::rgb::{palette_score, HexRGB};
+///
+/// let parse = |codes: &[&str]| {
+///     codes
+///         .iter()
+///         .map(|s| s.parse::<HexRGB<u32>>().unwrap())
+///         .collect::<Vec<_>>()
+/// };
+/// let spread = parse(&["#F00", "#0C0", "#00F", "#FF0", "#000", "#FFF"]);
+/// let clustered = parse(&["#F00", "#E00", "#F10", "#D11"]);
+///
+/// assert!(palette_score(&spread) > palette_score(&clustered));
+/// ```
+pub fn palette_score<T>(colours: &[HexRGB<T>]) -> f64
+where
+    T: Unsigned + Integer + Clone + CheckedMul + ToPrimitive,
+{
+    if colours.len() < 2 {
+        return 0.0;
+    }
+    let labs = colours
+        .iter()
+        .map(|colour| srgb_to_lab(colour.to_f64_channels()))
+        .collect::<Vec<_>>();
+
+    let minimum_difference = labs
+        .iter()
+        .enumerate()
+        .flat_map(|(index, &first)| {
+            labs[index + 1..]
+                .iter()
+                .map(move |&second| delta_e_1976(first, second))
+        })
+        .fold(f64::INFINITY, f64::min);
+    let separation = (minimum_difference / 50.0).min(1.0);
+
+    let (darkest, lightest) = labs
+        .iter()
+        .fold((f64::INFINITY, f64::NEG_INFINITY), |(low, high), lab| {
+            (low.min(lab[0]), high.max(lab[0]))
+        });
+    let lightness_spread = ((lightest - darkest) / 100.0).clamp(0.0, 1.0);
+
+    let hues = labs
+        .iter()
+        .filter(|[_, a, b]| a.hypot(*b) >= 10.0)
+        .map(|[_, a, b]| b.atan2(*a))
+        .collect::<Vec<_>>();
+    let hue_distribution = if hues.len() < 2 {
+        0.0
+    } else {
+        let (cos_sum, sin_sum) = hues
+            .iter()
+            .fold((0.0, 0.0), |(x, y), hue| (x + hue.cos(), y + hue.sin()));
+        1.0 - cos_sum.hypot(sin_sum) / hues.len() as f64
+    };
+
+    separation / 2.0 + lightness_spread / 4.0 + hue_distribution / 4.0
+}
@@ -0,0 +1,170 @@
+use super::{hex_digits, parse_channel, scale_channel, write_hex_code, HexRGB, ParseHexError};
+use ::num::{rational::Ratio, CheckedMul, Integer, Unsigned};
+use ::rgb::RGBA;
+use ::std::{
+    fmt::{Display, Formatter, Result as FMTResult, UpperHex},
+    str::FromStr,
+};
+
+/// Represents an RGB code with an alpha channel, in the hex format.
+///
+/// These are written as strings of the form `"#XXYYZZAA"` or `"#XYZA"`, like
+/// the codes of a [`HexRGB`] followed by the opacity, where 0 is fully
+/// transparent and 1 is fully opaque. The alpha channel is stored as a
+/// [`Ratio`] with the same denominator as the colour channels.
+///
+/// # Example
+/// ```
+/// use ::sht_colour::{
+///     rgb::{HexRGB, HexRGBA},
+///     Ratio,
+/// };
+///
+/// let translucent_red = "#FF000080".parse::<HexRGBA<u16>>().unwrap();
+///
+/// assert_eq!(translucent_red.alpha(), Ratio::new(0x80, 0xFF));
+/// assert_eq!(translucent_red.to_hex_rgb(), "#F00".parse().unwrap());
+/// assert_eq!(translucent_red.to_string(), "#FF000080");
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub struct HexRGBA<T>
+where
+    T: Unsigned + Integer + Clone + CheckedMul,
+{
+    /// Inner RGBA struct, which stores three colour channels, red, blue and
+    /// green, and an alpha channel.
+    #[doc(hidden)]
+    inner: RGBA<Ratio<T>>,
+}
+
+impl<T> HexRGBA<T>
+where
+    T: Unsigned + Integer + Clone + CheckedMul,
+{
+    /// Constructs a [`HexRGBA`] value from its colour channels and its alpha
+    /// channel.
+    ///
+    /// # Arguments
+    /// * `red` - Red colour channel.
+    /// * `green` - Green colour channel.
+    /// * `blue` - Blue colour channel.
+    /// * `alpha` - Opacity, from 0 for transparent to 1 for opaque.
+    pub fn new(red: Ratio<T>, green: Ratio<T>, blue: Ratio<T>, alpha: Ratio<T>) -> HexRGBA<T> {
+        HexRGBA {
+            inner: RGBA {
+                r: red,
+                g: green,
+                b: blue,
+                a: alpha,
+            },
+        }
+    }
+
+    /// Returns the alpha channel of a [`HexRGBA`] value.
+    pub fn alpha(&self) -> Ratio<T> {
+        self.inner.a.clone()
+    }
+
+    /// Drops the alpha channel of a [`HexRGBA`] value, leaving the colour as
+    /// a [`HexRGB`].
+    pub fn to_hex_rgb(self) -> HexRGB<T> {
+        let RGBA { r, g, b, .. } = self.inner;
+        HexRGB::new(r, g, b)
+    }
+}
+
+impl<T> HexRGB<T>
+where
+    T: Unsigned + Integer + Clone + CheckedMul,
+{
+    /// Adds an alpha channel to a [`HexRGB`] value, making it a
+    /// [`HexRGBA`].
+    ///
+    /// # Example
+    /// ```
+    /// use ::sht_colour::{rgb::HexRGB, Ratio};
+    ///
+    /// let red = "#F00".parse::<HexRGB<u16>>().unwrap();
+    ///
+    /// assert_eq!(
+    ///     red.with_alpha(Ratio::new(1, 3)),
+    ///     "#F005".parse().unwrap()
+    /// );
+    /// ```
+    pub fn with_alpha(self, alpha: Ratio<T>) -> HexRGBA<T> {
+        let (red, green, blue) = self.components();
+        HexRGBA::new(red, green, blue, alpha)
+    }
+}
+
+/// Formats a [`HexRGBA`] value like a [`HexRGB`], followed by the alpha
+/// channel.
+///
+/// The precision, width, fill, alignment and alternate flag behave as in the
+/// [`Display` implementation] of [`HexRGB`].
+///
+/// # Example
+/// ```
+/// use ::sht_colour::rgb::HexRGBA;
+///
+/// let colour = "#FF881180".parse::<HexRGBA<u32>>().unwrap();
+///
+/// assert_eq!(format!("{}", colour), "#FF881180");
+/// assert_eq!(format!("{:#}", colour), "#ff881180");
+/// assert_eq!(format!("{:^7.1}", colour), " #F818 ");
+/// ```
+///
+/// [`Display` implementation]: HexRGB#impl-Display
+impl<T> Display for HexRGBA<T>
+where
+    T: Unsigned + Integer + Clone + CheckedMul + From<u8> + UpperHex,
+{
+    fn fmt(&self, formatter: &mut Formatter) -> FMTResult {
+        let (digits, denominator) = hex_digits::<T>(formatter);
+        let alpha = scale_channel(self.alpha(), &denominator)?;
+        let code = format!(
+            "{:.digits$}{:0digits$X}",
+            self.clone().to_hex_rgb(),
+            alpha,
+            digits = digits
+        );
+        write_hex_code(formatter, code)
+    }
+}
+
+/// Parses a [`HexRGBA`] value from a hex code whose number of digits is a
+/// multiple of four.
+///
+/// Codes whose number of digits is only a multiple of three, which have no
+/// alpha channel, are rejected with [`ParseHexError::InvalidDigitCount`],
+/// just as [`HexRGB`] rejects codes with an alpha channel.
+impl<T> FromStr for HexRGBA<T>
+where
+    T: Unsigned + Integer + FromStr + From<u8> + Clone + CheckedMul,
+{
+    type Err = ParseHexError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.is_empty() {
+            return Err(ParseHexError::EmptyCode);
+        }
+
+        if &s[..1] != "#" {
+            return Err(ParseHexError::MissingOctothorpe);
+        }
+
+        let digits = &s[1..];
+        if digits.is_empty() || !digits.len().is_multiple_of(4) {
+            return Err(ParseHexError::InvalidDigitCount);
+        }
+
+        let width = digits.len() / 4;
+        let channel = |index: usize| parse_channel(&digits[index * width..(index + 1) * width]);
+        Ok(HexRGBA::new(
+            channel(0)?,
+            channel(1)?,
+            channel(2)?,
+            channel(3)?,
+        ))
+    }
+}
@@ -90,6 +90,69 @@ fn display_precision_1() {
     );
 }
 
+#[test]
+fn display_flags() {
+    use super::HexRGB;
+    let colour = "#FF8811".parse::<HexRGB<u32>>().unwrap();
+    assert_eq!(format!("{:#}", colour), "#ff8811");
+    assert_eq!(format!("{:#1}", colour), "#f81");
+    assert_eq!(format!("{:.1}", colour), "#F81");
+    assert_eq!(format!("{:#.3}", colour), "#fff888111");
+    // without an alignment, the width is the digit count
+    assert_eq!(format!("{:3}", colour), "#FFF888111");
+    // with one, the code is padded as for SHT codes
+    assert_eq!(format!("{:*>9}", colour), "**#FF8811");
+    assert_eq!(format!("{:<8.1}", colour), "#F81    ");
+    assert_eq!(format!("{:-^8.1}", colour), "--#F81--");
+    assert_eq!(format!("{:*>#9}", colour), "**#ff8811");
+    // other flags do nothing
+    assert_eq!(format!("{:+}", colour), "#FF8811");
+    assert_eq!(format!("{:02}", colour), "#FF8811");
+}
+
+#[test]
+fn display_rounds_to_nearest() {
+    use super::{HexRGB, HexRGBA};
+    let hex = |code: &str| code.parse::<HexRGB<u32>>().unwrap();
+    assert_eq!(format!("{:.1}", hex("#FEFEFE")), "#FFF");
+    assert_eq!(format!("{:.1}", hex("#808080")), "#888");
+    assert_eq!(format!("{:.1}", hex("#777777")), "#777");
+    // 0x7F is 7.47 over 0xF, and 0x80 is 7.53
+    assert_eq!(format!("{:.1}", hex("#807F80")), "#878");
+    assert_eq!(format!("{:3}", hex("#FEFEFE")), "#FEFFEFFEF");
+    let translucent = "#FFFFFF08".parse::<HexRGBA<u32>>().unwrap();
+    assert_eq!(format!("{:.1}", translucent), "#FFF0");
+}
+
+#[cfg(feature = "parse")]
+#[test]
+fn display_width_on_both_types() {
+    use super::HexRGB;
+    use crate::SHT;
+    let hex = "#FF8811".parse::<HexRGB<u32>>().unwrap();
+    let sht = "8r6g3".parse::<SHT<u32>>().unwrap();
+    // SHT pads to the width, and HexRGB writes that many digits
+    assert_eq!(format!("{:10}", sht), "8r6g3     ");
+    assert_eq!(format!("{:3}", hex), "#FFF888111");
+    // with an alignment, both pad
+    assert_eq!(format!("{:>10}", sht), "     8r6g3");
+    assert_eq!(format!("{:>10}", hex), "   #FF8811");
+    // widths too large for the integer type are capped rather than failing
+    assert_eq!(format!("{:10}", hex), format!("{:8}", hex));
+    assert_eq!(format!("{:.100}", hex).len(), 1 + 3 * 8);
+    let narrow = HexRGB::<u8>::from_bytes([0xFF, 0x88, 0x11]);
+    assert_eq!(format!("{:10}", narrow), "#FF8811");
+}
+
+#[test]
+fn display_flags_rgba() {
+    use super::HexRGBA;
+    let colour = "#FF881180".parse::<HexRGBA<u32>>().unwrap();
+    assert_eq!(format!("{:1}", colour), "#F818");
+    assert_eq!(format!("{:#.1}", colour), "#f818");
+    assert_eq!(format!("{:_<7.1}", colour), "#F818__");
+}
+
 #[test]
 fn diplay_no_precision() {
     use super::HexRGB;
@@ -102,3 +165,1768 @@ fn diplay_no_precision() {
         "#000000"
     );
 }
+
+#[test]
+fn equal_across_digit_counts() {
+    use super::HexRGB;
+    let parse = |s: &str| s.parse::<HexRGB<u32>>().unwrap();
+    assert_eq!(parse("#F00"), parse("#FF0000"));
+    assert_eq!(parse("#F00"), parse("#FFF000000"));
+    assert_eq!(parse("#ABC"), parse("#AABBCC"));
+    assert_eq!(parse("#ABC"), parse("#AAABBBCCC"));
+    assert_ne!(parse("#ABC"), parse("#ABBBCC"));
+}
+
+#[test]
+fn hash_across_digit_counts() {
+    use super::HexRGB;
+    use ::num::rational::Ratio;
+    use ::std::collections::HashSet;
+    assert_eq!(Ratio::new(1_u32, 15), Ratio::new(17, 255));
+    let colours = ["#F00", "#FF0000", "#FFF000000", "#ABC", "#AABBCC", "#123"]
+        .iter()
+        .map(|s| s.parse::<HexRGB<u32>>().unwrap())
+        .collect::<HashSet<_>>();
+    assert_eq!(colours.len(), 3);
+}
+
+#[test]
+fn hash_unreduced_ratios() {
+    use super::HexRGB;
+    use ::num::rational::Ratio;
+    use ::std::collections::HashSet;
+    let reduced = HexRGB::new(Ratio::new(1_u32, 15), Ratio::new(0, 1), Ratio::new(1, 1));
+    let unreduced = HexRGB::new(
+        Ratio::new_raw(17_u32, 255),
+        Ratio::new_raw(0, 255),
+        Ratio::new_raw(255, 255),
+    );
+    assert_eq!(reduced, unreduced);
+    let colours = [reduced, unreduced].iter().copied().collect::<HashSet<_>>();
+    assert_eq!(colours.len(), 1);
+}
+
+#[test]
+fn normalise_hex_expand() {
+    use super::normalise_hex;
+    assert_eq!(normalise_hex("#abc", 2), Ok("#AABBCC".to_owned()));
+    assert_eq!(normalise_hex("#abc", 3), Ok("#AAABBBCCC".to_owned()));
+    assert_eq!(normalise_hex("#000", 2), Ok("#000000".to_owned()));
+}
+
+#[test]
+fn normalise_hex_shorten() {
+    use super::normalise_hex;
+    assert_eq!(normalise_hex("#aabbcc", 1), Ok("#ABC".to_owned()));
+    assert_eq!(normalise_hex("#123456", 1), Ok("#135".to_owned()));
+    assert_eq!(normalise_hex("#FFFFFFFFF", 1), Ok("#FFF".to_owned()));
+}
+
+#[test]
+fn normalise_hex_failure() {
+    use super::{normalise_hex, ParseHexError};
+    assert_eq!(
+        normalise_hex("abc", 2),
+        Err(ParseHexError::MissingOctothorpe)
+    );
+    assert_eq!(
+        normalise_hex("#abc", 0),
+        Err(ParseHexError::InvalidDigitCount)
+    );
+    assert_eq!(normalise_hex("#abc", 16), Err(ParseHexError::Overflow));
+}
+
+#[test]
+fn lab_white_and_black() {
+    use super::lab::srgb_to_lab;
+    let [lightness, a, b] = srgb_to_lab([1.0, 1.0, 1.0]);
+    assert!((lightness - 100.0).abs() < 1e-3);
+    assert!(a.abs() < 1e-3 && b.abs() < 1e-3);
+    assert_eq!(srgb_to_lab([0.0, 0.0, 0.0]), [0.0, 0.0, 0.0]);
+}
+
+#[test]
+fn lab_round_trip() {
+    use super::lab::{lab_to_srgb, srgb_to_lab};
+    for channels in &[
+        [1.0, 0.0, 0.0],
+        [0.0, 1.0, 0.0],
+        [0.0, 0.0, 1.0],
+        [0.2, 0.4, 0.6],
+        [0.01, 0.02, 0.03],
+    ] {
+        let round_trip = lab_to_srgb(srgb_to_lab(*channels));
+        for (original, result) in channels.iter().zip(round_trip.iter()) {
+            assert!((original - result).abs() < 1e-6);
+        }
+    }
+}
+
+#[test]
+fn lightness_ramp_even_spacing() {
+    use super::{lab::srgb_to_lab, HexRGB};
+    for code in &["#F00", "#FF8811", "#123456", "#808080"] {
+        let ramp = code.parse::<HexRGB<u32>>().unwrap().lightness_ramp(6);
+        assert_eq!(ramp.len(), 6);
+        let lightnesses = ramp
+            .into_iter()
+            .map(|colour| srgb_to_lab(colour.to_f64_channels())[0])
+            .collect::<Vec<_>>();
+        for (index, lightness) in lightnesses.iter().enumerate() {
+            let expected = 100.0 * (6 - index) as f64 / 7.0;
+            assert!((lightness - expected).abs() < 0.1);
+        }
+    }
+}
+
+#[test]
+fn lightness_ramp_empty() {
+    use super::HexRGB;
+    assert_eq!("#F00".parse::<HexRGB<u32>>().unwrap().lightness_ramp(0), []);
+}
+
+#[test]
+fn parse_gpl_success() {
+    use super::{parse_gpl, HexRGB};
+    let contents = "GIMP Palette
+Name: Sample
+Columns: 2
+# a comment
+
+255   0   0\tRed
+ 17  34 255\tUntitled
+# another comment
+0 0 0
+";
+    assert_eq!(
+        parse_gpl::<u32>(contents),
+        Ok(vec![
+            "#F00".parse::<HexRGB<u32>>().unwrap(),
+            "#1122FF".parse().unwrap(),
+            "#000".parse().unwrap(),
+        ])
+    );
+}
+
+#[test]
+fn parse_gpl_empty_palette() {
+    use super::parse_gpl;
+    assert_eq!(parse_gpl::<u32>("GIMP Palette\nName: Empty\n"), Ok(vec![]));
+}
+
+#[test]
+fn parse_gpl_missing_header() {
+    use super::{parse_gpl, PaletteError};
+    assert_eq!(parse_gpl::<u32>(""), Err(PaletteError::MissingHeader));
+    assert_eq!(
+        parse_gpl::<u32>("255 0 0 Red\n"),
+        Err(PaletteError::MissingHeader)
+    );
+}
+
+#[test]
+fn parse_gpl_invalid_line() {
+    use super::{parse_gpl, PaletteError};
+    let error = |line| Err(PaletteError::InvalidLine(line));
+    assert_eq!(parse_gpl::<u32>("GIMP Palette\n0 0\n"), error(2));
+    assert_eq!(parse_gpl::<u32>("GIMP Palette\n\n0 0 256\n"), error(3));
+    assert_eq!(parse_gpl::<u32>("GIMP Palette\n0 0 0\nred\n"), error(3));
+}
+
+#[test]
+fn write_gpl_format() {
+    use super::{write_gpl, HexRGB};
+    let colours = ["#F00", "#1122FF", "#000"]
+        .iter()
+        .map(|s| s.parse::<HexRGB<u32>>().unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        write_gpl("Sample", &colours),
+        "GIMP Palette\nName: Sample\n#\n255   0   0\t#FF0000\n 17  34 255\t#1122FF\n  0   0   0\t#000000\n"
+    );
+}
+
+#[test]
+fn write_gpl_round_trip() {
+    use super::{parse_gpl, write_gpl, HexRGB};
+    let colours = ["#F00", "#FF8811", "#123456", "#000", "#FFF"]
+        .iter()
+        .map(|s| s.parse::<HexRGB<u32>>().unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(parse_gpl(&write_gpl("Sample", &colours)), Ok(colours));
+}
+
+#[test]
+fn write_gpl_rounds_to_8_bit() {
+    use super::{write_gpl, HexRGB};
+    let colours = ["#FFFF80000000".parse::<HexRGB<u64>>().unwrap()];
+    assert_eq!(
+        write_gpl("Sample", &colours),
+        "GIMP Palette\nName: Sample\n#\n255 128   0\t#FF8000\n"
+    );
+}
+
+#[test]
+fn near_duplicates_reported() {
+    use super::{find_near_duplicates, HexRGB};
+    let colours = ["#808080", "#818181", "#123456", "#7F8080", "#F00"]
+        .iter()
+        .map(|s| s.parse::<HexRGB<u32>>().unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        find_near_duplicates(&colours, 1.0),
+        [(0, 1), (0, 3), (1, 3)]
+    );
+}
+
+#[test]
+fn near_duplicates_distinct() {
+    use super::{find_near_duplicates, HexRGB};
+    let colours = ["#F00", "#0F0", "#00F", "#FFF", "#000"]
+        .iter()
+        .map(|s| s.parse::<HexRGB<u32>>().unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(find_near_duplicates(&colours, 10.0), []);
+    assert_eq!(find_near_duplicates::<u32>(&[], 10.0), []);
+}
+
+#[test]
+fn palette_score_prefers_spread() {
+    use super::{palette_score, HexRGB};
+    let parse = |codes: &[&str]| {
+        codes
+            .iter()
+            .map(|s| s.parse::<HexRGB<u32>>().unwrap())
+            .collect::<Vec<_>>()
+    };
+    let spread = palette_score(&parse(&["#F00", "#0C0", "#00F", "#FF0", "#000", "#FFF"]));
+    let clustered = palette_score(&parse(&["#F00", "#E00", "#F10", "#D11"]));
+    let duplicated = palette_score(&parse(&["#F00", "#0C0", "#00F", "#FF0", "#000", "#F00"]));
+    assert!(spread > clustered);
+    assert!(spread > duplicated);
+    assert!((0.0..=1.0).contains(&spread));
+    assert!((0.0..=1.0).contains(&clustered));
+}
+
+#[test]
+fn palette_score_too_small() {
+    use super::{palette_score, HexRGB};
+    assert_eq!(palette_score::<u32>(&[]), 0.0);
+    assert_eq!(
+        palette_score(&["#F00".parse::<HexRGB<u32>>().unwrap()]),
+        0.0
+    );
+}
+
+#[test]
+fn from_cmyk_inks() {
+    use super::HexRGB;
+    use ::num::rational::Ratio;
+    let (zero, one) = (Ratio::from_integer(0), Ratio::from_integer(1));
+    let parse = |s: &str| s.parse::<HexRGB<u32>>().unwrap();
+    assert_eq!(HexRGB::from_cmyk(one, zero, zero, zero), parse("#0FF"));
+    assert_eq!(HexRGB::from_cmyk(zero, one, zero, zero), parse("#F0F"));
+    assert_eq!(HexRGB::from_cmyk(zero, zero, one, zero), parse("#FF0"));
+    assert_eq!(HexRGB::from_cmyk(zero, zero, zero, one), parse("#000"));
+    assert_eq!(HexRGB::from_cmyk(one, one, one, one), parse("#000"));
+    assert_eq!(HexRGB::from_cmyk(zero, zero, zero, zero), parse("#FFF"));
+    assert_eq!(
+        HexRGB::from_cmyk(zero, Ratio::new(1, 2), one, Ratio::new(1, 2)),
+        HexRGB::new(Ratio::new(1, 2), Ratio::new(1, 4), zero)
+    );
+}
+
+#[test]
+fn to_cmyk_black() {
+    use super::HexRGB;
+    use ::num::rational::Ratio;
+    let (zero, one) = (Ratio::from_integer(0), Ratio::from_integer(1));
+    assert_eq!(
+        "#000".parse::<HexRGB<u32>>().unwrap().to_cmyk(),
+        (zero, zero, zero, one)
+    );
+}
+
+#[test]
+fn to_cmyk_primaries_and_secondaries() {
+    use super::HexRGB;
+    use ::num::rational::Ratio;
+    let (zero, one) = (Ratio::from_integer(0), Ratio::from_integer(1));
+    for (code, cmyk) in &[
+        ("#FFF", (zero, zero, zero, zero)),
+        ("#000", (zero, zero, zero, one)),
+        ("#F00", (zero, one, one, zero)),
+        ("#0F0", (one, zero, one, zero)),
+        ("#00F", (one, one, zero, zero)),
+        ("#0FF", (one, zero, zero, zero)),
+        ("#F0F", (zero, one, zero, zero)),
+        ("#FF0", (zero, zero, one, zero)),
+    ] {
+        let colour = code.parse::<HexRGB<u32>>().unwrap();
+        assert_eq!(colour.to_cmyk(), *cmyk, "{}", code);
+        let (cyan, magenta, yellow, black) = *cmyk;
+        assert_eq!(HexRGB::from_cmyk(cyan, magenta, yellow, black), colour);
+    }
+}
+
+#[test]
+fn cmyk_round_trip() {
+    use super::HexRGB;
+    for code in &["#FF8811", "#123456", "#000", "#FFF", "#0FF", "#808080"] {
+        let colour = code.parse::<HexRGB<u32>>().unwrap();
+        let (cyan, magenta, yellow, black) = colour.to_cmyk();
+        assert_eq!(HexRGB::from_cmyk(cyan, magenta, yellow, black), colour);
+    }
+}
+
+#[test]
+fn dominant_channel_distinct() {
+    use super::HexRGB;
+    use crate::ColourChannel;
+    use ::num::rational::Ratio;
+    let dominant = |s: &str| s.parse::<HexRGB<u32>>().unwrap().dominant_channel();
+    assert_eq!(dominant("#FF8811"), (ColourChannel::Red, Ratio::new(1, 1)));
+    assert_eq!(
+        dominant("#123456"),
+        (ColourChannel::Blue, Ratio::new(0x56, 0xFF))
+    );
+    assert_eq!(dominant("#0A0"), (ColourChannel::Green, Ratio::new(2, 3)));
+}
+
+#[test]
+fn dominant_channel_ties() {
+    use super::HexRGB;
+    use crate::ColourChannel;
+    use ::num::rational::Ratio;
+    let dominant = |s: &str| s.parse::<HexRGB<u32>>().unwrap().dominant_channel();
+    assert_eq!(
+        dominant("#808080"),
+        (ColourChannel::Red, Ratio::new(0x80, 0xFF))
+    );
+    assert_eq!(dominant("#000"), (ColourChannel::Red, Ratio::new(0, 1)));
+    assert_eq!(dominant("#FF0"), (ColourChannel::Red, Ratio::new(1, 1)));
+    assert_eq!(dominant("#0FF"), (ColourChannel::Green, Ratio::new(1, 1)));
+    assert_eq!(dominant("#F0F"), (ColourChannel::Red, Ratio::new(1, 1)));
+}
+
+#[test]
+fn swizzle_swaps_red_and_blue() {
+    use super::HexRGB;
+    use crate::ColourChannel::{Blue, Green, Red};
+    let red = "#FF0000".parse::<HexRGB<u32>>().unwrap();
+    assert_eq!(
+        red.swizzle([Blue, Green, Red]),
+        "#0000FF".parse::<HexRGB<u32>>().unwrap()
+    );
+    let colour = "#123456".parse::<HexRGB<u32>>().unwrap();
+    assert_eq!(colour.swizzle([Red, Green, Blue]), colour);
+    assert_eq!(
+        colour.swizzle([Green, Blue, Red]),
+        "#345612".parse::<HexRGB<u32>>().unwrap()
+    );
+}
+
+#[test]
+#[should_panic]
+fn swizzle_rejects_repeated_channel() {
+    use super::HexRGB;
+    use crate::ColourChannel::{Green, Red};
+    let colour = "#123456".parse::<HexRGB<u32>>().unwrap();
+    let _ = colour.swizzle([Red, Green, Red]);
+}
+
+#[test]
+fn hsl_round_trip() {
+    use super::hsl::{hsl_to_rgb, rgb_to_hsl};
+    for channels in &[
+        [1.0, 0.0, 0.0],
+        [0.2, 0.4, 0.6],
+        [0.9, 0.1, 0.5],
+        [0.5, 0.5, 0.5],
+        [0.0, 0.7, 0.3],
+    ] {
+        let result = hsl_to_rgb(rgb_to_hsl(*channels));
+        for (original, converted) in channels.iter().zip(result.iter()) {
+            assert!((original - converted).abs() < 1e-9, "{:?}", channels);
+        }
+    }
+    assert_eq!(rgb_to_hsl([0.0, 0.0, 1.0]), [240.0, 1.0, 0.5]);
+}
+
+#[test]
+fn hue_midpoint_shorter_arc() {
+    use super::HexRGB;
+    let check = |a: &str, b: &str, expected: [f64; 3]| {
+        let a = a.parse::<HexRGB<u32>>().unwrap();
+        let b = b.parse::<HexRGB<u32>>().unwrap();
+        let result = a.hue_midpoint(b).to_f64_channels();
+        for (channel, expected) in result.iter().zip(expected.iter()) {
+            assert!((channel - expected).abs() < 1e-4, "{:?}", result);
+        }
+    };
+    // red and blue meet at magenta, not grey or green
+    check("#F00", "#00F", [1.0, 0.0, 1.0]);
+    check("#00F", "#F00", [1.0, 0.0, 1.0]);
+    // the arc through 0 degrees is used when it is shorter
+    check("#F0F", "#FF0", [1.0, 0.0, 0.0]);
+    // opposite hues travel forwards from the first hue
+    check("#F00", "#0FF", [0.5, 1.0, 0.0]);
+    check("#0FF", "#F00", [0.5, 0.0, 1.0]);
+    // greys take the hue of the other colour
+    check("#F00", "#FFF", [0.875, 0.625, 0.625]);
+    check("#FFF", "#F00", [0.875, 0.625, 0.625]);
+    check("#FFF", "#000", [0.5, 0.5, 0.5]);
+}
+
+#[test]
+fn relative_luminance_srgb() {
+    use super::HexRGB;
+    let luminance = |s: &str| s.parse::<HexRGB<u32>>().unwrap().relative_luminance();
+    assert!(luminance("#000").abs() < 1e-9);
+    assert!((luminance("#FFF") - 1.0).abs() < 1e-6);
+    assert!((luminance("#F00") - 0.2126).abs() < 1e-4);
+    assert!((luminance("#00F") - 0.0722).abs() < 1e-4);
+    // mid grey is much darker than half in linear light
+    assert!((luminance("#808080") - 0.2158).abs() < 1e-4);
+}
+
+#[test]
+fn relative_luminance_gamma() {
+    use super::HexRGB;
+    let colour = "#336699".parse::<HexRGB<u32>>().unwrap();
+    let [red, green, blue] = [
+        0x33 as f64 / 255.0,
+        0x66 as f64 / 255.0,
+        0x99 as f64 / 255.0,
+    ];
+    let weighted = 0.212_672_9 * red + 0.715_152_2 * green + 0.072_175_0 * blue;
+    assert!((colour.relative_luminance_with_gamma(1.0) - weighted).abs() < 1e-9);
+    let squared = 0.212_672_9 * red * red + 0.715_152_2 * green * green + 0.072_175_0 * blue * blue;
+    assert!((colour.relative_luminance_with_gamma(2.0) - squared).abs() < 1e-9);
+    // sRGB sits close to a gamma of 2.2
+    assert!((colour.relative_luminance_with_gamma(2.2) - colour.relative_luminance()).abs() < 0.01);
+}
+
+#[test]
+fn linear_f32_channels() {
+    use super::HexRGB;
+    let linear = |s: &str| s.parse::<HexRGB<u32>>().unwrap().to_linear_f32();
+    assert_eq!(linear("#000000"), [0.0; 3]);
+    assert_eq!(linear("#FFFFFF"), [1.0; 3]);
+    let [red, green, blue] = linear("#FF8000");
+    assert_eq!(red, 1.0);
+    assert!(green < 128.0 / 255.0);
+    assert!((green - 0.2158).abs() < 1e-4);
+    assert_eq!(blue, 0.0);
+}
+
+#[test]
+fn linear_f32_channels_with_alpha() {
+    use super::HexRGBA;
+    let linear = |s: &str| s.parse::<HexRGBA<u32>>().unwrap().to_linear_f32();
+    assert_eq!(linear("#00000000"), [0.0; 4]);
+    assert_eq!(linear("#FFFFFFFF"), [1.0; 4]);
+    let [red, green, blue, alpha] = linear("#FF800080");
+    assert_eq!((red, blue), (1.0, 0.0));
+    assert!((green - 0.2158).abs() < 1e-4);
+    // alpha is not decoded with the sRGB curve
+    assert!((alpha - 128.0 / 255.0).abs() < 1e-6);
+}
+
+#[test]
+fn sht_base_quantisation() {
+    use super::HexRGB;
+    use crate::{ChannelRatios, ColourChannel};
+    use ::num::rational::Ratio;
+    let colour = "#FF6633".parse::<HexRGB<u32>>().unwrap();
+    // tint is 1/5 and blend is 1/4
+    let blended = |blend| ChannelRatios::OneBrightestChannel {
+        primary: ColourChannel::Red,
+        direction_blend: Some((ColourChannel::Green, blend)),
+    };
+    assert_eq!(
+        colour.to_sht_base(10, 1),
+        (
+            blended(Ratio::new(3, 10)),
+            Ratio::from_integer(1),
+            Ratio::new(2, 10)
+        )
+    );
+    assert_eq!(
+        colour.to_sht_base(10, 2),
+        (
+            blended(Ratio::new(25, 100)),
+            Ratio::from_integer(1),
+            Ratio::new(20, 100)
+        )
+    );
+    assert_eq!(
+        colour.to_sht_base(16, 1),
+        (
+            blended(Ratio::new(4, 16)),
+            Ratio::from_integer(1),
+            Ratio::new(3, 16)
+        )
+    );
+    assert_eq!(
+        colour.to_sht_base(16, 2),
+        (
+            blended(Ratio::new(64, 256)),
+            Ratio::from_integer(1),
+            Ratio::new(51, 256)
+        )
+    );
+    assert_eq!(colour.to_sht_base(12, 2), colour.to_sht(2).components());
+}
+
+#[test]
+fn sht_rounding_collapses_edges() {
+    use super::HexRGB;
+    let sht = |s: &str, precision| {
+        s.parse::<HexRGB<u32>>()
+            .unwrap()
+            .to_sht(precision)
+            .to_string()
+    };
+    assert_eq!(sht("#FF0100", 1), "r");
+    assert_eq!(sht("#FFFE00", 1), "y");
+    assert_eq!(sht("#FEFFFF", 1), "W");
+    assert_eq!(sht("#010000", 1), "0");
+    assert_eq!(sht("#FF0100", 3), "r01g");
+}
+
+#[test]
+fn sht_checked_structure() {
+    use super::HexRGB;
+    let check = |s: &str, precision| {
+        let (colour, unchanged) = s
+            .parse::<HexRGB<u32>>()
+            .unwrap()
+            .to_sht_checked_structure(precision);
+        (colour.to_string(), unchanged)
+    };
+    // near the boundary between red-with-green and yellow
+    assert_eq!(check("#FFF800", 2), ("rE8g".to_owned(), true));
+    assert_eq!(check("#FFF800", 1), ("y".to_owned(), false));
+    // near the boundary between red and red-with-green
+    assert_eq!(check("#FF0800", 2), ("r05g".to_owned(), true));
+    assert_eq!(check("#FF0800", 1), ("r".to_owned(), false));
+    // near grey
+    assert_eq!(check("#807F7F", 1), ("6".to_owned(), false));
+    assert_eq!(check("#F00", 1), ("r".to_owned(), true));
+    assert_eq!(check("#888", 1), ("6".to_owned(), true));
+}
+
+#[test]
+fn hwb_pure_hues() {
+    use super::HexRGB;
+    use ::num::rational::Ratio;
+    let hwb = |s: &str| s.parse::<HexRGB<u32>>().unwrap().to_hwb();
+    let zero = Ratio::from_integer(0);
+    assert_eq!(hwb("#F00"), (zero, zero, zero));
+    assert_eq!(hwb("#FF0"), (Ratio::new(1, 6), zero, zero));
+    assert_eq!(hwb("#0F0"), (Ratio::new(1, 3), zero, zero));
+    assert_eq!(hwb("#0FF"), (Ratio::new(1, 2), zero, zero));
+    assert_eq!(hwb("#00F"), (Ratio::new(2, 3), zero, zero));
+    assert_eq!(hwb("#F0F"), (Ratio::new(5, 6), zero, zero));
+    assert_eq!(
+        hwb("#F08"),
+        (Ratio::new(1, 1) - Ratio::new(136, 255 * 6), zero, zero)
+    );
+    assert_eq!(
+        hwb("#963"),
+        (Ratio::new(1, 12), Ratio::new(3, 15), Ratio::new(6, 15))
+    );
+    assert_eq!(hwb("#888"), (zero, Ratio::new(8, 15), Ratio::new(7, 15)));
+}
+
+#[test]
+fn hwb_round_trip() {
+    use super::HexRGB;
+    for code in &[
+        "#F00", "#FF8811", "#123456", "#963", "#F08", "#808080", "#000", "#FFF", "#0AF",
+    ] {
+        let colour = code.parse::<HexRGB<u64>>().unwrap();
+        let (hue, whiteness, blackness) = colour.to_hwb();
+        assert_eq!(
+            HexRGB::from_hwb(hue, whiteness, blackness),
+            colour,
+            "{}",
+            code
+        );
+    }
+}
+
+#[test]
+fn hwb_normalises_greys() {
+    use super::HexRGB;
+    use ::num::rational::Ratio;
+    let grey = |value| HexRGB::new(value, value, value);
+    let colour = <HexRGB<u32>>::from_hwb(Ratio::new(1, 3), Ratio::new(3, 5), Ratio::new(3, 5));
+    assert_eq!(colour, grey(Ratio::new(1, 2)));
+    let colour = <HexRGB<u32>>::from_hwb(Ratio::new(1, 3), Ratio::new(1, 4), Ratio::new(3, 4));
+    assert_eq!(colour, grey(Ratio::new(1, 4)));
+    // hue wraps around
+    let red = <HexRGB<u32>>::from_hwb(Ratio::from_integer(2), Ratio::new(0, 1), Ratio::new(0, 1));
+    assert_eq!(red, "#F00".parse().unwrap());
+}
+
+#[cfg(feature = "cssparser")]
+#[test]
+fn css_hex_in_value() {
+    use super::{parse_css_hex, HexRGB, ParseHexError};
+    use ::cssparser::{ParseErrorKind, Parser, ParserInput};
+    let mut input = ParserInput::new("linear-gradient(#F00, #00ff00 50%)");
+    let mut parser = Parser::new(&mut input);
+    parser.expect_function_matching("linear-gradient").unwrap();
+    let colours = parser
+        .parse_nested_block(|parser| {
+            let first = parse_css_hex::<u32>(parser)?;
+            parser.expect_comma()?;
+            let second = parse_css_hex::<u32>(parser)?;
+            parser.expect_percentage()?;
+            Ok((first, second))
+        })
+        .unwrap();
+    assert_eq!(
+        colours,
+        (
+            "#F00".parse::<HexRGB<u32>>().unwrap(),
+            "#00FF00".parse::<HexRGB<u32>>().unwrap()
+        )
+    );
+
+    let mut input = ParserInput::new("#F000 red");
+    let mut parser = Parser::new(&mut input);
+    let error = parse_css_hex::<u32>(&mut parser).unwrap_err();
+    assert_eq!(
+        error.kind,
+        ParseErrorKind::Custom(ParseHexError::InvalidDigitCount)
+    );
+    assert!(parse_css_hex::<u32>(&mut parser).is_err());
+}
+
+#[test]
+fn wheel_positions() {
+    use super::HexRGB;
+    use ::num::rational::Ratio;
+    let check = |s: &str, (x, y): (f64, f64)| {
+        let (actual_x, actual_y) = s.parse::<HexRGB<u32>>().unwrap().wheel_position();
+        assert!((actual_x - x).abs() < 1e-9, "{} {}", s, actual_x);
+        assert!((actual_y - y).abs() < 1e-9, "{} {}", s, actual_y);
+    };
+    let root_three = 3.0_f64.sqrt();
+    check("#F00", (1.0, 0.0));
+    check("#FF0", (0.5, root_three / 2.0));
+    check("#0F0", (-0.5, root_three / 2.0));
+    check("#0FF", (-1.0, 0.0));
+    check("#00F", (-0.5, -root_three / 2.0));
+    check("#000", (0.0, 0.0));
+    check("#808080", (0.0, 0.0));
+    check("#FFF", (0.0, 0.0));
+    // half saturation lies halfway to the edge
+    let half_saturated = HexRGB::<u32>::new(Ratio::new(3, 4), Ratio::new(1, 4), Ratio::new(1, 4));
+    assert_eq!(half_saturated.wheel_position(), (0.5, 0.0));
+}
+
+#[test]
+fn bytes_round_trip() {
+    use super::HexRGB;
+    for code in &["#000", "#FFF", "#FF8811", "#123456", "#ABCDEF", "#7F7F80"] {
+        let colour = code.parse::<HexRGB<u32>>().unwrap();
+        assert_eq!(HexRGB::from_bytes(colour.to_bytes()), colour, "{}", code);
+    }
+    let precise = "#123456789".parse::<HexRGB<u32>>().unwrap();
+    assert_eq!(precise.to_bytes(), [0x12, 0x45, 0x78]);
+}
+
+#[test]
+fn accessible_gray_meets_ratio() {
+    use super::HexRGB;
+    let parse = |s: &str| s.parse::<HexRGB<u32>>().unwrap();
+    let contrast = |first: HexRGB<u32>, second: HexRGB<u32>| {
+        let (first, second) = (first.relative_luminance(), second.relative_luminance());
+        (first.max(second) + 0.05) / (first.min(second) + 0.05)
+    };
+    for background in &["#FFF", "#000", "#808080", "#123456", "#FF8811"] {
+        for colour in &["#F00", "#0F0", "#777", "#FEDCBA"] {
+            for &ratio in &[1.0, 3.0, 4.5] {
+                let background = parse(background);
+                let grey = parse(colour).to_accessible_gray(background, ratio);
+                let (red, green, blue) = grey.components();
+                assert!(red == green && green == blue);
+                assert!(contrast(grey, background) >= ratio);
+            }
+        }
+    }
+}
+
+#[test]
+fn accessible_gray_unachievable() {
+    use super::HexRGB;
+    let parse = |s: &str| s.parse::<HexRGB<u32>>().unwrap();
+    let grey = parse("#808080");
+    assert_eq!(grey.to_accessible_gray(parse("#FFF"), 25.0), parse("#000"));
+    assert_eq!(grey.to_accessible_gray(parse("#000"), 25.0), parse("#FFF"));
+    // mid grey cannot reach 7:1 either way, so the better extreme is used
+    assert_eq!(grey.to_accessible_gray(parse("#777"), 7.0), parse("#000"));
+    // a light grey on a dark background stays light
+    let light = parse("#EEE").to_accessible_gray(parse("#222"), 4.5);
+    assert!(light.relative_luminance() > 0.5);
+}
+
+#[test]
+fn ansi_256_nearest() {
+    use super::HexRGB;
+    let index = |s: &str| s.parse::<HexRGB<u32>>().unwrap().to_ansi_256();
+    assert_eq!(index("#F00"), 196);
+    assert_eq!(index("#000"), 16);
+    assert_eq!(index("#FFF"), 231);
+    assert_eq!(index("#5F87AF"), 67);
+    assert_eq!(index("#080808"), 232);
+    assert_eq!(index("#EEEEEE"), 255);
+    assert_eq!(index("#777"), 243);
+}
+
+#[test]
+fn ansi_truecolor_escape() {
+    use super::HexRGB;
+    let colour = "#123456".parse::<HexRGB<u32>>().unwrap();
+    assert_eq!(colour.to_ansi_truecolor(true), "\u{1b}[38;2;18;52;86m");
+    assert_eq!(colour.to_ansi_truecolor(false), "\u{1b}[48;2;18;52;86m");
+}
+
+#[test]
+fn paint_text() {
+    use super::HexRGB;
+    let colour = "#123456".parse::<HexRGB<u32>>().unwrap();
+    let painted = colour.paint("swatch");
+    assert!(painted.starts_with("\u{1b}[38;2;18;52;86m"));
+    assert!(painted.contains("swatch"));
+    assert!(painted.ends_with("\u{1b}[0m"));
+    let black = "#000".parse::<HexRGB<u32>>().unwrap();
+    let painted = colour.paint_on("swatch", black);
+    assert_eq!(
+        painted,
+        "\u{1b}[38;2;18;52;86m\u{1b}[48;2;0;0;0mswatch\u{1b}[0m"
+    );
+}
+
+#[cfg(feature = "parse")]
+#[test]
+fn nearest_sht_perceptual_exact() {
+    use super::{sht::SHT, HexRGB};
+    for code in &["0", "W", "6", "r", "8r6g3", "4c", "y3", "EmX", "b4r"] {
+        let colour = code.parse::<SHT<u32>>().unwrap();
+        let (red, green, blue) = colour.exact_channels();
+        let rgb = HexRGB::new(red, green, blue);
+        assert_eq!(rgb.nearest_sht_perceptual(1), colour, "{}", code);
+        assert_eq!(rgb.to_sht(1), colour, "{}", code);
+    }
+}
+
+#[cfg(feature = "parse")]
+#[test]
+fn nearest_sht_perceptual_differs() {
+    use super::{sht::SHT, HexRGB};
+    let colour = "#123456".parse::<HexRGB<u32>>().unwrap();
+    assert_eq!(colour.to_sht(1), "3b6g1".parse::<SHT<u32>>().unwrap());
+    assert_eq!(
+        colour.nearest_sht_perceptual(1),
+        "4b7g".parse::<SHT<u32>>().unwrap()
+    );
+}
+
+#[test]
+fn channel_getters_and_setters() {
+    use super::HexRGB;
+    use ::num::rational::Ratio;
+    let parse = |s: &str| s.parse::<HexRGB<u32>>().unwrap();
+    let colour = parse("#008811");
+    assert_eq!(colour.red(), Ratio::from_integer(0));
+    assert_eq!(colour.green(), Ratio::new(0x88, 0xFF));
+    assert_eq!(colour.blue(), Ratio::new(0x11, 0xFF));
+    assert_eq!(colour.with_red(Ratio::new(0x12, 0xFF)), parse("#128811"));
+    assert_eq!(colour.with_green(Ratio::from_integer(0)), parse("#000011"));
+    assert_eq!(colour.with_blue(Ratio::from_integer(1)), parse("#0088FF"));
+}
+
+#[test]
+fn from_any_hex_prefixes() {
+    use super::{HexRGB, ParseHexError};
+    let colour = "#FF8811".parse::<HexRGB<u32>>().unwrap();
+    for code in &["#FF8811", "0xFF8811", "0XFF8811", "FF8811", "ff8811"] {
+        assert_eq!(HexRGB::from_any_hex(code), Ok(colour), "{}", code);
+    }
+    assert_eq!(
+        HexRGB::from_any_hex("0xF81"),
+        Ok("#F81".parse::<HexRGB<u32>>().unwrap())
+    );
+    assert_eq!(
+        HexRGB::<u32>::from_any_hex(""),
+        Err(ParseHexError::EmptyCode)
+    );
+    assert!(HexRGB::<u32>::from_any_hex("0x").is_err());
+    assert!(HexRGB::<u32>::from_any_hex("##FF8811").is_err());
+    assert!(HexRGB::<u32>::from_any_hex("0xFF881").is_err());
+}
+
+#[test]
+fn mean_hue_wraps_around() {
+    use super::{mean_hue, HexRGB};
+    // hues of about 350 and 10 degrees
+    let colours = ["#FF002B", "#FF2B00"]
+        .iter()
+        .map(|s| s.parse::<HexRGB<u32>>().unwrap())
+        .collect::<Vec<_>>();
+    let hue = mean_hue(&colours).unwrap();
+    let distance_from_red = ((hue + 180.0).rem_euclid(360.0) - 180.0).abs();
+    assert!(distance_from_red < 1e-9, "{}", hue);
+}
+
+#[test]
+fn mean_hue_no_average() {
+    use super::{mean_hue, HexRGB};
+    let parse = |codes: &[&str]| {
+        codes
+            .iter()
+            .map(|s| s.parse::<HexRGB<u32>>().unwrap())
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(mean_hue::<u32>(&[]), None);
+    assert_eq!(mean_hue(&parse(&["#000", "#888", "#FFF"])), None);
+    assert_eq!(mean_hue(&parse(&["#F00", "#0FF"])), None);
+    let blue = mean_hue(&parse(&["#00F", "#888"])).unwrap();
+    assert!((blue - 240.0).abs() < 1e-9);
+}
+
+#[test]
+fn ycbcr_grey_is_neutral() {
+    use super::{HexRGB, YCbCrMatrix};
+    for matrix in &[YCbCrMatrix::Bt601, YCbCrMatrix::Bt709] {
+        for code in &["#000", "#808080", "#FFF"] {
+            let (_, blue, red) = code.parse::<HexRGB<u32>>().unwrap().to_ycbcr(*matrix);
+            assert!((blue - 0.5).abs() < 1e-9);
+            assert!((red - 0.5).abs() < 1e-9);
+        }
+    }
+}
+
+#[test]
+fn ycbcr_round_trip() {
+    use super::{HexRGB, YCbCrMatrix};
+    for matrix in &[YCbCrMatrix::Bt601, YCbCrMatrix::Bt709] {
+        for code in &["#F00", "#0F0", "#00F", "#FF8811", "#123456"] {
+            let colour = code.parse::<HexRGB<u32>>().unwrap();
+            let (luma, blue, red) = colour.to_ycbcr(*matrix);
+            assert_eq!(
+                format!("{}", HexRGB::<u32>::from_ycbcr(luma, blue, red, *matrix)),
+                format!("{}", colour)
+            );
+        }
+    }
+}
+
+#[test]
+fn ycbcr_clamps_out_of_gamut() {
+    use super::{HexRGB, YCbCrMatrix};
+    use ::num::rational::Ratio;
+    let colour = HexRGB::<u32>::from_ycbcr(1.0, 1.0, 1.0, YCbCrMatrix::Bt709);
+    assert_eq!(colour.red(), Ratio::from_integer(1));
+    assert_eq!(colour.blue(), Ratio::from_integer(1));
+    assert!(colour.green() < Ratio::from_integer(1));
+    let colour = HexRGB::<u32>::from_ycbcr(0.0, 0.0, 0.0, YCbCrMatrix::Bt601);
+    assert_eq!(colour.red(), Ratio::from_integer(0));
+    assert_eq!(colour.blue(), Ratio::from_integer(0));
+}
+
+#[test]
+fn lossless_sht_precision_round_trips() {
+    use super::HexRGB;
+    for code in &[
+        "#F00",
+        "#FFF",
+        "#000",
+        "#555",
+        "#FFAAAA",
+        "#AA5500",
+        "#1C71C71C7",
+    ] {
+        let colour = code.parse::<HexRGB<u64>>().unwrap();
+        let precision = colour.lossless_sht_precision().unwrap();
+        assert_eq!(
+            colour.to_sht(precision).to_rgb(precision),
+            colour,
+            "{}",
+            code
+        );
+    }
+    let parse = |s: &str| s.parse::<HexRGB<u64>>().unwrap();
+    assert_eq!(parse("#F00").lossless_sht_precision(), Some(1));
+    assert_eq!(parse("#1C71C71C7").lossless_sht_precision(), Some(3));
+    assert_eq!(parse("#FF8811").lossless_sht_precision(), None);
+    assert_eq!(parse("#808080").lossless_sht_precision(), None);
+}
+
+#[test]
+fn rewrite_css_colours_in_values() {
+    use super::{rewrite_css_colours, HexRGB};
+    let css = "body {\n  color: #123456;\n  background: url(\"#abc\") #ABC;\n}\n";
+    let swapped = rewrite_css_colours(css, |colour: HexRGB<u32>| {
+        let (red, green, blue) = colour.components();
+        HexRGB::new(blue, green, red)
+    });
+    assert_eq!(
+        swapped,
+        "body {\n  color: #563412;\n  background: url(\"#abc\") #CBA;\n}\n"
+    );
+}
+
+#[test]
+fn rewrite_css_colours_leaves_other_text() {
+    use super::{rewrite_css_colours, HexRGB};
+    let css = "#add, a:hover { /* #fff */ color: #fed; border-color: #abcd; --x: #12345 }";
+    let black = "#000".parse::<HexRGB<u32>>().unwrap();
+    assert_eq!(
+        rewrite_css_colours(css, |_| black),
+        "#add, a:hover { /* #fff */ color: #000; border-color: #abcd; --x: #12345 }"
+    );
+    assert_eq!(rewrite_css_colours("", |_| black), "");
+    assert_eq!(
+        rewrite_css_colours("a { b: c; } /* #fff", |_| black),
+        "a { b: c; } /* #fff"
+    );
+}
+
+#[test]
+fn hex_shorthand_detection() {
+    use super::{hex_is_shorthand, normalise_hex};
+    assert_eq!(hex_is_shorthand("#ABC"), Some(true));
+    assert_eq!(hex_is_shorthand("#abc"), Some(true));
+    assert_eq!(hex_is_shorthand("#AABBCC"), Some(false));
+    assert_eq!(hex_is_shorthand("#AAABBBCCC"), Some(false));
+    for invalid in &[
+        "",
+        "ABC",
+        "#AB",
+        "#ABCD",
+        "#GGG",
+        "#00000000000000000FFFFFFFFFFFFFFFFF00000000000000000",
+    ] {
+        assert_eq!(hex_is_shorthand(invalid), None);
+    }
+    // sixteen digits per channel fit in a `u64` exactly
+    assert_eq!(
+        hex_is_shorthand("#0000000000000000FFFFFFFFFFFFFFFF0000000000000000"),
+        Some(false)
+    );
+    // an expanded code can be safely shortened when shortening it and
+    // expanding it again gives back the same code
+    let shortened = normalise_hex("#AABBCC", 1).unwrap();
+    assert_eq!(hex_is_shorthand(&shortened), Some(true));
+    assert_eq!(normalise_hex(&shortened, 2), Ok("#AABBCC".to_owned()));
+}
+
+#[test]
+fn shorten_when_lossless() {
+    use super::HexRGB;
+    let hex = |code: &str| code.parse::<HexRGB<u32>>().unwrap();
+    for code in &[
+        "#AABBCC",
+        "#000000",
+        "#FFFFFF",
+        "#ABC",
+        "#AAABBBCCC",
+        "#11FF00",
+    ] {
+        assert!(hex(code).can_shorten(), "{}", code);
+        assert_eq!(hex(code).shorten(), Some(hex(code)));
+    }
+    for code in &["#ABCDEF", "#AABBCD", "#808080", "#AAABBBCCD"] {
+        assert!(!hex(code).can_shorten(), "{}", code);
+        assert_eq!(hex(code).shorten(), None);
+    }
+    assert_eq!(format!("{:1}", hex("#11FF00").shorten().unwrap()), "#1F0");
+}
+
+#[test]
+fn chip_theme_text_meets_aa() {
+    use super::HexRGB;
+    let parse = |code: &str| code.parse::<HexRGB<u32>>().unwrap();
+    let contrast = |first: HexRGB<u32>, second: HexRGB<u32>| {
+        let (first, second) = (first.relative_luminance(), second.relative_luminance());
+        (first.max(second) + 0.05) / (first.min(second) + 0.05)
+    };
+    for code in &[
+        "#FF8811", "#000080", "#777777", "#767676", "#FFFF00", "#000", "#FFF", "#1E90FF",
+    ] {
+        let theme = parse(code).chip_theme();
+        assert_eq!(theme.background, parse(code));
+        assert!(theme.text == parse("#000") || theme.text == parse("#FFF"));
+        assert!(contrast(theme.text, theme.background) >= 4.5, "{}", code);
+        assert_ne!(theme.border, theme.background, "{}", code);
+    }
+    assert_eq!(parse("#FFFF00").chip_theme().text, parse("#000"));
+    assert_eq!(parse("#000080").chip_theme().text, parse("#FFF"));
+    // light backgrounds get darker borders, and dark backgrounds lighter ones
+    let yellow = parse("#FFFF00");
+    assert!(yellow.chip_theme().border.relative_luminance() < yellow.relative_luminance());
+    let navy = parse("#000080");
+    assert!(navy.chip_theme().border.relative_luminance() > navy.relative_luminance());
+}
+
+#[test]
+fn average_linear_light() {
+    use super::{average_linear, HexRGB};
+    let parse = |code: &str| code.parse::<HexRGB<u32>>().unwrap();
+    let mean = average_linear(&[parse("#000"), parse("#FFF")]).unwrap();
+    let [red, green, blue] = mean.to_bytes();
+    assert!(red == green && green == blue);
+    assert!((0xBB..=0xBC).contains(&red));
+    assert_ne!(mean, parse("#808080"));
+
+    assert_eq!(average_linear::<u32>(&[]), None);
+    assert_eq!(
+        average_linear(&[parse("#FF8811")]).unwrap().to_string(),
+        "#FF8811"
+    );
+    assert_eq!(
+        average_linear(&[parse("#F00"), parse("#00F")])
+            .unwrap()
+            .to_bytes(),
+        [0xBC, 0x00, 0xBC]
+    );
+}
+
+#[test]
+fn tonal_scale_monotonic() {
+    use super::HexRGB;
+    let stops = [50, 100, 200, 300, 400, 500, 600, 700, 800, 900];
+    for code in &["#F00", "#FF8811", "#1E90FF", "#888"] {
+        let scale = code.parse::<HexRGB<u32>>().unwrap().tonal_scale(&stops);
+        assert_eq!(
+            scale.iter().map(|(stop, _)| *stop).collect::<Vec<_>>(),
+            stops
+        );
+        let luminances = scale
+            .iter()
+            .map(|(_, colour)| colour.relative_luminance())
+            .collect::<Vec<_>>();
+        assert!(
+            luminances.windows(2).all(|pair| pair[0] > pair[1]),
+            "{}",
+            code
+        );
+    }
+    let red = "#F00".parse::<HexRGB<u32>>().unwrap();
+    assert_eq!(red.tonal_scale(&[]), []);
+    assert_eq!(red.tonal_scale(&[0])[0].1.to_bytes(), [0xFF; 3]);
+    assert_eq!(red.tonal_scale(&[1000, 2000])[1].1.to_bytes(), [0; 3]);
+    let [red_channel, green, blue] = red.tonal_scale(&[600])[0].1.to_bytes();
+    assert!(red_channel > 0x80 && green == 0 && blue == 0);
+}
+
+#[test]
+fn shortest_sht_within_tolerance() {
+    use super::HexRGB;
+    let parse = |code: &str| code.parse::<HexRGB<u64>>().unwrap();
+    for code in &["#FF8811", "#123456", "#3A7BD5", "#6A5ACD"] {
+        let colour = parse(code);
+        let loose = colour.shortest_sht_within(10.0).to_string();
+        let tight = colour.shortest_sht_within(0.5).to_string();
+        assert!(loose.len() < tight.len(), "{} {} {}", code, loose, tight);
+    }
+    // colours with exact short codes need no extra digits
+    assert_eq!(parse("#F00").shortest_sht_within(0.0).to_string(), "r");
+    assert_eq!(parse("#C04040").shortest_sht_within(0.5).to_string(), "8r3");
+    assert_eq!(parse("#000").shortest_sht_within(100.0).to_string(), "0");
+}
+
+#[test]
+fn try_new_checks_range() {
+    use super::{ChannelOutOfRange, HexRGB};
+    use crate::ColourChannel;
+    use num::rational::Ratio;
+    let (zero, one) = (Ratio::from_integer(0), Ratio::from_integer(1));
+    assert_eq!(
+        HexRGB::<u32>::try_new(one, Ratio::new(1, 2), zero),
+        Ok(HexRGB::new(one, Ratio::new(1, 2), zero))
+    );
+    assert_eq!(
+        HexRGB::<u32>::try_new(Ratio::new(2, 1), zero, zero),
+        Err(ChannelOutOfRange {
+            channel: ColourChannel::Red
+        })
+    );
+    assert_eq!(
+        HexRGB::<u32>::try_new(zero, Ratio::new(16, 15), Ratio::new(3, 2)),
+        Err(ChannelOutOfRange {
+            channel: ColourChannel::Green
+        })
+    );
+    assert_eq!(
+        HexRGB::<u32>::try_new(one, one, Ratio::new(256, 255)),
+        Err(ChannelOutOfRange {
+            channel: ColourChannel::Blue
+        })
+    );
+}
+
+#[test]
+fn array_encodings() {
+    use super::{Encoding, HexRGB};
+    let parse = |code: &str| code.parse::<HexRGB<u32>>().unwrap();
+    let grey = parse("#808080");
+    let (srgb, linear) = (
+        grey.to_array(Encoding::Srgb),
+        grey.to_array(Encoding::Linear),
+    );
+    assert!(srgb[0] - linear[0] > 0.25);
+    for code in &["#000", "#FFF"] {
+        assert_eq!(
+            parse(code).to_array(Encoding::Srgb),
+            parse(code).to_array(Encoding::Linear)
+        );
+    }
+    assert_eq!(parse("#F00").to_array(Encoding::Linear), [1.0, 0.0, 0.0]);
+    for code in &["#FF8811", "#123456", "#808080", "#000", "#FFF"] {
+        for &encoding in &[Encoding::Srgb, Encoding::Linear] {
+            let array = parse(code).to_array(encoding);
+            assert_eq!(
+                HexRGB::<u32>::from_array(array, encoding).to_bytes(),
+                parse(code).to_bytes()
+            );
+        }
+    }
+    assert_eq!(
+        HexRGB::<u32>::from_array([-1.0, 0.5, 2.0], Encoding::Srgb).to_bytes(),
+        [0x00, 0x80, 0xFF]
+    );
+}
+
+#[test]
+fn linear_transfer_function() {
+    use super::{HexRGB, RGB};
+    use num::rational::Ratio;
+    let to_f64 = |ratio: Ratio<u64>| *ratio.numer() as f64 / *ratio.denom() as f64;
+    let channel = |value: Ratio<u64>| HexRGB::new(value, value, value).to_linear().r;
+    // below the threshold, the curve is linear
+    let dark = Ratio::new(1, 30);
+    assert!((to_f64(channel(dark)) - 1.0 / 30.0 / 12.92).abs() < 1e-9);
+    let threshold = Ratio::new(4045, 100_000);
+    assert!((to_f64(channel(threshold)) - 0.04045 / 12.92).abs() < 1e-9);
+    // above it, the curve is a power
+    let mid = Ratio::new(1, 2);
+    assert!((to_f64(channel(mid)) - (0.555_f64 / 1.055).powf(2.4)).abs() < 1e-9);
+    // the extremes are exact
+    let (zero, one) = (Ratio::from_integer(0), Ratio::from_integer(1));
+    assert_eq!(
+        HexRGB::<u8>::new(zero, one, zero).to_linear(),
+        RGB::new(zero, one, zero)
+    );
+
+    for code in &["#FF8811", "#123456", "#0A0A0A", "#808080"] {
+        let colour = code.parse::<HexRGB<u64>>().unwrap();
+        assert_eq!(
+            HexRGB::from_linear(colour.to_linear()).to_bytes(),
+            colour.to_bytes()
+        );
+    }
+}
+
+#[test]
+fn approximate_names() {
+    use super::HexRGB;
+    let name = |code: &str| code.parse::<HexRGB<u32>>().unwrap().approximate_name();
+    assert!(name("#FF8811").ends_with("orange"));
+    assert_eq!(name("#808080"), "gray");
+    assert_eq!(name("#000"), "black");
+    assert_eq!(name("#FFF"), "white");
+    assert_eq!(name("#333"), "dark gray");
+    assert_eq!(name("#800"), "dark red");
+    assert_eq!(name("#F9F"), "light magenta");
+    assert_eq!(name("#797"), "muted green");
+}
+
+#[test]
+fn rgb8_batch_matches_scalar() {
+    use super::{to_rgb8_batch, HexRGB};
+    use ::num::rational::Ratio;
+    let mut colours = [
+        "#FF8811",
+        "#ABC",
+        "#000",
+        "#123456789",
+        "#FFFFFF",
+        "#80808F",
+    ]
+    .iter()
+    .map(|s| s.parse::<HexRGB<u64>>().unwrap())
+    .collect::<Vec<_>>();
+    colours.push(HexRGB::new(
+        Ratio::new(1, 3),
+        Ratio::new(2, 7),
+        Ratio::new(5, 4),
+    ));
+    let scalar = colours
+        .iter()
+        .map(|colour| colour.to_bytes())
+        .collect::<Vec<_>>();
+    assert_eq!(to_rgb8_batch(&colours), scalar);
+    assert_eq!(to_rgb8_batch::<u64>(&[]), Vec::<[u8; 3]>::new());
+}
+
+#[test]
+fn rgb8_batch_narrow() {
+    use super::{to_rgb8_batch, HexRGB};
+    use ::num::rational::Ratio;
+    let mut colours = (0..=u8::MAX)
+        .map(|byte| {
+            HexRGB::<u8>::new(
+                Ratio::new(byte, 255),
+                Ratio::new(byte, 255),
+                Ratio::new(0, 1),
+            )
+        })
+        .collect::<Vec<_>>();
+    colours.extend(
+        ["#F80", "#ABC"]
+            .iter()
+            .map(|s| s.parse::<HexRGB<u8>>().unwrap()),
+    );
+    colours.push(HexRGB::new(
+        Ratio::new(1, 3),
+        Ratio::new(2, 7),
+        Ratio::new(200, 201),
+    ));
+    let scalar = colours
+        .iter()
+        .map(|colour| {
+            let (red, green, blue) = colour.to_rgb8();
+            [red, green, blue]
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(to_rgb8_batch(&colours), scalar);
+}
+
+#[test]
+fn parse_rgba() {
+    use super::{HexRGB, HexRGBA, ParseHexError};
+    use ::num::rational::Ratio;
+    let opaque_red = "#F00F".parse::<HexRGBA<u8>>().unwrap();
+    assert_eq!(opaque_red.alpha(), Ratio::from_integer(1));
+    assert_eq!(opaque_red.to_hex_rgb(), "#F00".parse().unwrap());
+    let translucent_red = "#FF000080".parse::<HexRGBA<u16>>().unwrap();
+    assert_eq!(translucent_red.alpha(), Ratio::new(0x80, 0xFF));
+    assert_eq!(
+        translucent_red,
+        "#F00"
+            .parse::<HexRGB<u16>>()
+            .unwrap()
+            .with_alpha(Ratio::new(0x80, 0xFF))
+    );
+    assert_eq!(translucent_red.to_string(), "#FF000080");
+    assert_eq!(
+        "#F00".parse::<HexRGBA<u8>>(),
+        Err(ParseHexError::InvalidDigitCount)
+    );
+    assert_eq!(
+        "#".parse::<HexRGBA<u8>>(),
+        Err(ParseHexError::InvalidDigitCount)
+    );
+    assert_eq!(
+        "#F00F".parse::<HexRGB<u8>>(),
+        Err(ParseHexError::InvalidDigitCount)
+    );
+}
+
+#[test]
+fn gamut_surface() {
+    use super::HexRGB;
+    let on_surface = |code: &str| code.parse::<HexRGB<u32>>().unwrap().is_on_gamut_surface();
+    assert!(on_surface("#FF8811"));
+    assert!(on_surface("#008811"));
+    assert!(on_surface("#000"));
+    assert!(on_surface("#FFF"));
+    assert!(!on_surface("#7F8081"));
+    assert!(!on_surface("#888"));
+}
+
+#[test]
+fn widen_u8_to_u16() {
+    use super::HexRGB;
+    use ::num::rational::Ratio;
+    use ::std::convert::TryFrom;
+    let narrow = HexRGB::<u8>::from_bytes([0xFF, 0x80, 0x01]);
+    let wide = HexRGB::<u16>::from(narrow);
+    assert_eq!(
+        wide.red() * Ratio::from_integer(0xFFFF),
+        Ratio::from_integer(0xFFFF)
+    );
+    assert_eq!(wide.green(), Ratio::new(0x8080, 0xFFFF));
+    assert_eq!(wide.blue(), Ratio::new(0x0101, 0xFFFF));
+    assert_eq!(HexRGB::<u8>::try_from(wide), Ok(narrow));
+    let fine = HexRGB::<u16>::new(
+        Ratio::from_integer(1),
+        Ratio::from_integer(0),
+        Ratio::new(1, 0xFFFF),
+    );
+    assert!(HexRGB::<u8>::try_from(fine).is_err());
+}
+
+#[test]
+fn try_to_sht_errors() {
+    use super::HexRGB;
+    use crate::sht::{ConversionError, SHTValueError};
+    use ::num::rational::Ratio;
+    let orange = "#FF8811".parse::<HexRGB<u32>>().unwrap();
+    for precision in 0..=3 {
+        assert_eq!(orange.try_to_sht(precision), Ok(orange.to_sht(precision)));
+    }
+    assert_eq!(orange.try_to_sht(10), Err(ConversionError::Overflow));
+    let too_bright = HexRGB::<u32>::new(Ratio::new(1, 2), Ratio::new(3, 2), Ratio::new(0, 1));
+    assert_eq!(
+        too_bright.try_to_sht(1),
+        Err(ConversionError::InvalidComponents(vec![
+            SHTValueError::ValueOutOfBounds
+        ]))
+    );
+}
+
+#[test]
+fn mix_and_average() {
+    use super::HexRGB;
+    use ::num::rational::Ratio;
+    let orange = "#FF8811".parse::<HexRGB<u32>>().unwrap();
+    let teal = "#118888".parse::<HexRGB<u32>>().unwrap();
+    assert_eq!(orange.mix(&teal, Ratio::from_integer(0)), orange);
+    assert_eq!(orange.mix(&teal, Ratio::from_integer(1)), teal);
+    assert_eq!(orange.mix(&teal, Ratio::from_integer(2)), teal);
+    assert_eq!(
+        orange.mix(&teal, Ratio::new(1, 2)).red(),
+        Ratio::new(0xFF + 0x11, 2 * 0xFF)
+    );
+    assert_eq!(HexRGB::average(&[orange]), Some(orange));
+    assert_eq!(
+        HexRGB::average(&[orange, teal]),
+        Some(orange.mix(&teal, Ratio::new(1, 2)))
+    );
+    let black = "#000".parse::<HexRGB<u32>>().unwrap();
+    let white = "#FFF".parse::<HexRGB<u32>>().unwrap();
+    assert_eq!(
+        HexRGB::average(&[black, black, black, white]).map(|colour| colour.green()),
+        Some(Ratio::new(1, 4))
+    );
+    assert_eq!(HexRGB::<u32>::average(&[]), None);
+}
+
+#[test]
+fn grayscale_weights() {
+    use super::HexRGB;
+    use ::num::rational::Ratio;
+    let luma = |code: &str| code.parse::<HexRGB<u32>>().unwrap().grayscale().red();
+    assert_eq!(luma("#F00"), Ratio::new(299, 1000));
+    assert_eq!(luma("#0F0"), Ratio::new(587, 1000));
+    assert_eq!(luma("#00F"), Ratio::new(114, 1000));
+    assert_eq!(luma("#FFF"), Ratio::from_integer(1));
+    assert_eq!(luma("#000"), Ratio::from_integer(0));
+    let grey = "#808080".parse::<HexRGB<u32>>().unwrap();
+    assert_eq!(grey.grayscale(), grey);
+}
+
+#[test]
+fn invert_channels() {
+    use super::HexRGB;
+    use ::num::rational::Ratio;
+    let black = "#000".parse::<HexRGB<u32>>().unwrap();
+    let white = "#FFF".parse::<HexRGB<u32>>().unwrap();
+    assert_eq!(black.invert(), white);
+    assert_eq!(white.invert(), black);
+    assert_eq!(
+        "#123456".parse::<HexRGB<u32>>().unwrap().invert(),
+        "#EDCBA9".parse().unwrap()
+    );
+    let too_bright = HexRGB::<u32>::new(Ratio::new(3, 2), Ratio::new(1, 3), Ratio::from_integer(0));
+    assert_eq!(
+        too_bright.invert(),
+        HexRGB::new(
+            Ratio::from_integer(0),
+            Ratio::new(2, 3),
+            Ratio::from_integer(1)
+        )
+    );
+}
+
+#[test]
+fn from_css_functional_notation() {
+    use super::{HexRGB, HexRGBA, ParseCssError};
+    use ::num::rational::Ratio;
+    let orange = "#FF8811".parse::<HexRGB<u32>>().unwrap();
+    for code in &[
+        "rgb(255, 136, 17)",
+        "RGB(255,136,17)",
+        "rgba(255, 136, 17, 0.5)",
+        "rgb(100% 136 17)",
+        "rgb(255 136 17 / 50%)",
+        "  rgb( 255.0  136  17 )  ",
+        "rgb(300, 136, 17)",
+    ] {
+        assert_eq!(HexRGB::from_css(code), Ok(orange), "{}", code);
+    }
+    assert_eq!(
+        HexRGB::<u32>::from_css("rgb(-10, 50%, 12.5%)"),
+        Ok(HexRGB::new(
+            Ratio::from_integer(0),
+            Ratio::new(1, 2),
+            Ratio::new(1, 8)
+        ))
+    );
+    assert_eq!(
+        HexRGBA::<u32>::from_css("rgba(0, 0, 0, 0.25)").map(|colour| colour.alpha()),
+        Ok(Ratio::new(1, 4))
+    );
+    assert_eq!(
+        HexRGB::<u32>::from_css("#FF8811"),
+        Err(ParseCssError::NotRgbFunction)
+    );
+    assert_eq!(
+        HexRGB::<u32>::from_css("hsl(0, 0%, 0%)"),
+        Err(ParseCssError::NotRgbFunction)
+    );
+    assert_eq!(
+        HexRGB::<u32>::from_css("rgb(1, 2)"),
+        Err(ParseCssError::InvalidArguments)
+    );
+    assert_eq!(
+        HexRGB::<u32>::from_css("rgb(1, 2, x)"),
+        Err(ParseCssError::InvalidNumber)
+    );
+    assert_eq!(
+        HexRGB::<u8>::from_css("rgb(1, 2, 3000)"),
+        Err(ParseCssError::Overflow)
+    );
+}
+
+#[test]
+fn to_css_notation() {
+    use super::HexRGB;
+    let css_hex = |code: &str| code.parse::<HexRGB<u32>>().unwrap().to_css_hex();
+    assert_eq!(css_hex("#FFAA00"), "#fa0");
+    assert_eq!(css_hex("#ABC"), "#abc");
+    assert_eq!(css_hex("#000"), "#000");
+    assert_eq!(css_hex("#FF8811"), "#f81");
+    assert_eq!(css_hex("#123456"), "#123456");
+    assert_eq!(css_hex("#FFAA01"), "#ffaa01");
+    assert_eq!(css_hex("#123456789"), "#124578");
+    let css_rgb = |code: &str| code.parse::<HexRGB<u32>>().unwrap().to_css_rgb();
+    assert_eq!(css_rgb("#FF8811"), "rgb(255, 136, 17)");
+    assert_eq!(css_rgb("#FFF"), "rgb(255, 255, 255)");
+    assert_eq!(css_rgb("#000"), "rgb(0, 0, 0)");
+}
+
+#[test]
+fn to_css_notation_narrow() {
+    use super::HexRGB;
+    let amber = HexRGB::<u8>::from_bytes([0xFF, 0xAA, 0x00]);
+    let orange = HexRGB::<u8>::from_bytes([0xFF, 0x88, 0x12]);
+    assert_eq!(amber.to_css_hex(), "#fa0");
+    assert_eq!(orange.to_css_hex(), "#ff8812");
+    assert_eq!(orange.to_css_rgb(), "rgb(255, 136, 18)");
+    for byte in 0..=u8::MAX {
+        let grey = HexRGB::<u8>::from_bytes([byte; 3]);
+        let rgb = format!("rgb({0}, {0}, {0})", byte);
+        assert_eq!(grey.to_css_rgb(), rgb);
+        assert_eq!(HexRGB::from_css(&grey.to_css_rgb()), Ok(grey));
+    }
+}
+
+#[test]
+fn named_colours() {
+    use super::{
+        named::{from_name, nearest_name, NAMED_COLOURS},
+        HexRGB,
+    };
+    let red = "#FF0000".parse::<HexRGB<u32>>().unwrap();
+    assert_eq!(from_name("red"), Some(red));
+    assert_eq!(from_name("RED"), Some(red));
+    assert_eq!(nearest_name(&red), ("red", 0));
+    assert_eq!(
+        from_name("RebeccaPurple"),
+        Some("#663399".parse::<HexRGB<u32>>().unwrap())
+    );
+    assert_eq!(from_name::<u32>(""), None);
+    assert_eq!(from_name::<u32>("reddish"), None);
+    assert_eq!(NAMED_COLOURS.len(), 148);
+    for &(name, bytes) in NAMED_COLOURS {
+        let colour = from_name::<u32>(name).unwrap();
+        assert_eq!(colour.to_bytes(), bytes);
+        assert_eq!(nearest_name(&colour).1, 0, "{}", name);
+        let narrow = from_name::<u8>(name).unwrap();
+        assert_eq!(narrow.to_bytes(), bytes);
+        assert_eq!(nearest_name(&narrow).1, 0, "{}", name);
+    }
+    assert_eq!(
+        nearest_name(&"#00FFFF".parse::<HexRGB<u32>>().unwrap()),
+        ("aqua", 0)
+    );
+    assert_eq!(
+        nearest_name(&"#010203".parse::<HexRGB<u32>>().unwrap()),
+        ("black", 14)
+    );
+}
+
+#[test]
+fn ciede2000_reference_pairs() {
+    use super::lab::delta_e_2000;
+    // pairs from the supplementary test data of Sharma, Wu and Dalal (2005)
+    let pairs = [
+        ([50.0, 2.6772, -79.7751], [50.0, 0.0, -82.7485], 2.0425),
+        ([50.0, 2.8361, -74.0200], [50.0, 0.0, -82.7485], 3.4412),
+        ([50.0, 0.0, 0.0], [50.0, -1.0, 2.0], 2.3669),
+        ([50.0, 2.49, -0.001], [50.0, -2.49, 0.0009], 7.1792),
+        ([50.0, 2.49, -0.001], [50.0, -2.49, 0.0011], 7.2195),
+        ([50.0, 2.5, 0.0], [73.0, 25.0, -18.0], 27.1492),
+        (
+            [60.2574, -34.0099, 36.2677],
+            [60.4626, -34.1751, 39.4387],
+            1.2644,
+        ),
+        (
+            [22.7233, 20.0904, -46.6940],
+            [23.0331, 14.9730, -42.5619],
+            2.0373,
+        ),
+        (
+            [2.0776, 0.0795, -1.1350],
+            [0.9033, -0.0636, -0.5514],
+            0.9082,
+        ),
+    ];
+    for &(first, second, expected) in &pairs {
+        let difference = delta_e_2000(first, second);
+        assert!((difference - expected).abs() < 1e-4, "{}", difference);
+        assert!((delta_e_2000(second, first) - expected).abs() < 1e-4);
+    }
+}
+
+#[test]
+fn rgb_distance() {
+    use super::HexRGB;
+    let colour = |code: &str| code.parse::<HexRGB<u32>>().unwrap();
+    assert_eq!(colour("#000").distance(&colour("#000")), 0.0);
+    assert_eq!(colour("#0F0").distance(&colour("#000")), 1.0);
+    assert!((colour("#F00").distance(&colour("#0F0")) - 2.0_f64.sqrt()).abs() < 1e-9);
+    assert_eq!(colour("#ABC").distance_ciede2000(&colour("#ABC")), 0.0);
+    assert!(
+        colour("#808080").distance_ciede2000(&colour("#818181"))
+            < colour("#808080").distance_ciede2000(&colour("#808000"))
+    );
+}
+
+#[test]
+fn xyz_and_lab_round_trip() {
+    use super::HexRGB;
+    let close = |(a, b, c): (f64, f64, f64), (x, y, z): (f64, f64, f64)| {
+        (a - x).abs() < 1e-6 && (b - y).abs() < 1e-6 && (c - z).abs() < 1e-6
+    };
+    for code in &["#000", "#FFF", "#F00", "#0F0", "#00F", "#FF8811"] {
+        let colour = code.parse::<HexRGB<u64>>().unwrap();
+        let from_xyz = HexRGB::<u64>::from_xyz(colour.to_xyz());
+        assert!(close(from_xyz.to_xyz(), colour.to_xyz()), "{}", code);
+        assert_eq!(from_xyz.to_bytes(), colour.to_bytes());
+        let from_lab = HexRGB::<u64>::from_lab(colour.to_lab());
+        assert!(close(from_lab.to_lab(), colour.to_lab()), "{}", code);
+        assert_eq!(from_lab.to_bytes(), colour.to_bytes());
+    }
+    let (lightness, a, b) = "#FFF".parse::<HexRGB<u64>>().unwrap().to_lab();
+    assert!((lightness - 100.0).abs() < 1e-3 && a.abs() < 1e-3 && b.abs() < 1e-3);
+    // dark enough that every coordinate is on the linear segment of f(t)
+    let (lightness, _, _) = "#010101".parse::<HexRGB<u64>>().unwrap().to_lab();
+    assert!((lightness - 0.2742).abs() < 1e-4, "{}", lightness);
+}
+
+#[test]
+fn any_hex_whitespace() {
+    use super::{HexRGB, ParseHexError};
+    let orange = "#FF8811".parse::<HexRGB<u32>>().unwrap();
+    for code in &[
+        "FF8811", "0xff8811", "0XFF8811", "#ff8811", "f81", "\t#F81 ",
+    ] {
+        assert_eq!(HexRGB::from_any_hex(code), Ok(orange), "{}", code);
+    }
+    assert_eq!(
+        HexRGB::<u32>::from_any_hex("#fa0"),
+        "#FFAA00".parse::<HexRGB<u32>>()
+    );
+    assert_eq!(
+        HexRGB::<u32>::from_any_hex("  "),
+        Err(ParseHexError::EmptyCode)
+    );
+    assert_eq!(
+        HexRGB::<u32>::from_any_hex("0xFF88"),
+        Err(ParseHexError::InvalidDigitCount)
+    );
+    assert_eq!(
+        "FF8811".parse::<HexRGB<u32>>(),
+        Err(ParseHexError::MissingOctothorpe)
+    );
+}
+
+#[test]
+fn rgb8_tuples() {
+    use super::HexRGB;
+    use ::num::rational::Ratio;
+    for &byte in &[0, 1, 127, 128, 254, 255] {
+        let colour = HexRGB::<u32>::from_rgb8(byte, 255 - byte, byte);
+        assert_eq!(colour.to_rgb8(), (byte, 255 - byte, byte));
+        assert_eq!(
+            HexRGB::<u16>::from_rgb8(255, byte, 0).to_rgb8(),
+            (255, byte, 0)
+        );
+    }
+    // 127.5 out of 255 rounds half up
+    let half = Ratio::new(1, 2);
+    assert_eq!(
+        HexRGB::<u32>::new(half, half, half).to_rgb8(),
+        (128, 128, 128)
+    );
+    let just_below_half = Ratio::new(1274, 2550 + 1);
+    assert_eq!(
+        HexRGB::<u32>::new(just_below_half, half, Ratio::from_integer(2)).to_rgb8(),
+        (127, 128, 255)
+    );
+    let sixteen_bit = "#123456789".parse::<HexRGB<u64>>().unwrap();
+    let bytes = sixteen_bit.to_rgb8();
+    assert_eq!(bytes, (0x12, 0x45, 0x78));
+    let (red, green, blue) = bytes;
+    assert_eq!(HexRGB::<u64>::from_rgb8(red, green, blue).to_rgb8(), bytes);
+}
+
+#[test]
+fn packed_u32() {
+    use super::HexRGB;
+    let orange = "#FF8811".parse::<HexRGB<u32>>().unwrap();
+    assert_eq!(orange.to_u32(), 0x00FF_8811);
+    assert_eq!(HexRGB::<u32>::from_u32(0x00FF_8811), orange);
+    assert_eq!(HexRGB::<u32>::from_u32(0xFFFF_8811), orange);
+    assert_eq!(HexRGB::<u32>::from_u32(0xAB00_0000).to_u32(), 0);
+    assert_eq!(HexRGB::<u32>::from_u32(u32::MAX).to_u32(), 0x00FF_FFFF);
+    for &value in &[0, 0x12_3456, 0xAB_CDEF, 0xFF_FFFF] {
+        assert_eq!(HexRGB::<u16>::from_u32(value).to_u32(), value);
+        assert_eq!(HexRGB::<u8>::from_u32(value).to_u32(), value);
+    }
+    for byte in 0..=u8::MAX {
+        let value = u32::from_be_bytes([0, byte, 0, u8::MAX - byte]);
+        assert_eq!(HexRGB::<u8>::from_u32(value).to_u32(), value);
+    }
+}
+
+#[test]
+fn rgb_f32_primaries() {
+    use super::{HexRGB, RGB};
+    let primaries = [
+        ("#000", [0.0, 0.0, 0.0]),
+        ("#FFF", [1.0, 1.0, 1.0]),
+        ("#F00", [1.0, 0.0, 0.0]),
+        ("#0F0", [0.0, 1.0, 0.0]),
+        ("#00F", [0.0, 0.0, 1.0]),
+        ("#0FF", [0.0, 1.0, 1.0]),
+        ("#F0F", [1.0, 0.0, 1.0]),
+        ("#FF0", [1.0, 1.0, 0.0]),
+    ];
+    for &(code, [red, green, blue]) in &primaries {
+        let colour = code.parse::<HexRGB<u32>>().unwrap();
+        let RGB { r, g, b } = colour.to_rgb_f32();
+        assert!((r - red).abs() < 1e-6, "{}", code);
+        assert!((g - green).abs() < 1e-6, "{}", code);
+        assert!((b - blue).abs() < 1e-6, "{}", code);
+        assert_eq!(HexRGB::from_rgb_f32(colour.to_rgb_f32()), colour);
+    }
+    let third = "#555".parse::<HexRGB<u64>>().unwrap().to_rgb_f32();
+    assert!((third.r - 1.0 / 3.0).abs() < 1e-6);
+    let sixteen_bit = "#123456789".parse::<HexRGB<u64>>().unwrap();
+    let round_trip = HexRGB::<u64>::from_rgb_f32(sixteen_bit.to_rgb_f32());
+    assert!(sixteen_bit.distance(&round_trip) < 1e-6);
+}
+
+#[test]
+fn display_alternate_lowercase() {
+    use super::HexRGB;
+    let colour = "#AABBCC".parse::<HexRGB<u32>>().unwrap();
+    assert_eq!(format!("{}", colour), "#AABBCC");
+    assert_eq!(format!("{:#}", colour), "#aabbcc");
+    // the alternate flag only changes the case of the digits
+    for width in 1..=4 {
+        let upper = format!("{:width$}", colour, width = width);
+        let lower = format!("{:#width$}", colour, width = width);
+        assert_eq!(lower, upper.to_lowercase());
+        assert_eq!(lower.len(), 1 + 3 * width);
+    }
+}
+
+#[test]
+fn write_gpl_narrow_channels() {
+    use super::{write_gpl, HexRGB};
+    let colours = ["#F81", "#000", "#FFF"]
+        .iter()
+        .map(|code| code.parse::<HexRGB<u8>>().unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        write_gpl("Narrow", &colours),
+        "GIMP Palette\nName: Narrow\n#\n255 136  17\t#FF8811\n  0   0   0\t#000000\n255 255 255\t#FFFFFF\n"
+    );
+    let byte_colours = [HexRGB::<u8>::from_bytes([0xFF, 0x88, 0x11])];
+    assert_eq!(
+        write_gpl("Bytes", &byte_colours),
+        "GIMP Palette\nName: Bytes\n#\n255 136  17\t#FF8811\n"
+    );
+}
+
+#[test]
+fn bytes_round_trip_narrow() {
+    use super::HexRGB;
+    for byte in 0..=u8::MAX {
+        let bytes = [byte, u8::MAX - byte, byte / 2];
+        let colour = HexRGB::<u8>::from_bytes(bytes);
+        assert_eq!(colour.to_bytes(), bytes);
+        assert_eq!(HexRGB::<u8>::from_bytes(colour.to_bytes()), colour);
+    }
+}
+
+#[test]
+fn rgb8_tuples_narrow() {
+    use super::HexRGB;
+    for &byte in &[0, 127, 128, 255] {
+        let colour = HexRGB::<u8>::from_rgb8(byte, 255 - byte, byte);
+        assert_eq!(colour.to_rgb8(), (byte, 255 - byte, byte));
+        let (red, green, blue) = colour.to_rgb8();
+        assert_eq!(HexRGB::<u8>::from_rgb8(red, green, blue), colour);
+    }
+    assert_eq!(
+        HexRGB::<u8>::from_rgb8(0xFF, 0x88, 0x11).to_rgb8(),
+        (0xFF, 0x88, 0x11)
+    );
+}
+
+#[test]
+fn rewrite_css_colours_skips_pseudo_class_selectors() {
+    use super::{rewrite_css_colours, HexRGB};
+    let black = "#000".parse::<HexRGB<u32>>().unwrap();
+    let rewrite = |css| rewrite_css_colours(css, |_| black);
+    assert_eq!(
+        rewrite("a:hover #add { color: #fff }"),
+        "a:hover #add { color: #000 }"
+    );
+    assert_eq!(
+        rewrite("li:first-child > #bad, p { color: #fff; }"),
+        "li:first-child > #bad, p { color: #000; }"
+    );
+    assert_eq!(
+        rewrite("@media (min-width: 1px) { a:focus #bad { color: #fed } }"),
+        "@media (min-width: 1px) { a:focus #bad { color: #000 } }"
+    );
+    assert_eq!(
+        rewrite("a:hover{color:#abc}#bed:x{}"),
+        "a:hover{color:#000}#bed:x{}"
+    );
+}
@@ -0,0 +1,110 @@
+use super::HexRGB;
+use ::num::{CheckedMul, FromPrimitive, Integer, ToPrimitive, Unsigned};
+
+/// The standard defining how luma is weighted between the channels when
+/// converting between [`HexRGB`] and YCbCr.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+#[non_exhaustive]
+pub enum YCbCrMatrix {
+    /// The ITU-R BT.601 matrix, used for standard-definition video and JPEG
+    /// images, with red, green and blue weighted 0.299, 0.587 and 0.114.
+    Bt601,
+    /// The ITU-R BT.709 matrix, used for high-definition video, with red,
+    /// green and blue weighted 0.2126, 0.7152 and 0.0722.
+    Bt709,
+}
+
+impl YCbCrMatrix {
+    /// Returns the weights of the red and blue channels in luma. The weight
+    /// of green is whatever remains.
+    fn weights(self) -> (f64, f64) {
+        match self {
+            YCbCrMatrix::Bt601 => (0.299, 0.114),
+            YCbCrMatrix::Bt709 => (0.2126, 0.0722),
+        }
+    }
+}
+
+impl<T> HexRGB<T>
+where
+    T: Unsigned + Integer + Clone + CheckedMul,
+{
+    /// Converts a [`HexRGB`] value to luma and blue-difference and
+    /// red-difference chroma, `(Y, Cb, Cr)`, as used by video formats.
+    ///
+    /// The conversion applies the chosen matrix directly to the
+    /// gamma-encoded channels, as video standards do. All three components
+    /// use the full range: luma runs from 0 for black to 1 for white, and
+    /// each chroma component runs from 0 to 1, with 0.5 for greys. For studio
+    /// (or limited) range 8-bit video, scale luma to lie between 16 and 235,
+    /// and chroma between 16 and 240.
+    ///
+    /// # Arguments
+    /// * `matrix` - The standard whose luma weights are used.
+    ///
+    /// # Example
+    /// ```
+    /// use ::sht_colour::rgb::{HexRGB, YCbCrMatrix};
+    ///
+    /// let white = "#FFF".parse::<HexRGB<u16>>().unwrap();
+    /// let (luma, blue, red) = white.to_ycbcr(YCbCrMatrix::Bt709);
+    ///
+    /// assert!((luma - 1.0).abs() < 1e-9);
+    /// assert!((blue - 0.5).abs() < 1e-9);
+    /// assert!((red - 0.5).abs() < 1e-9);
+    /// ```
+    pub fn to_ycbcr(self, matrix: YCbCrMatrix) -> (f64, f64, f64)
+    where
+        T: ToPrimitive,
+    {
+        let (red_weight, blue_weight) = matrix.weights();
+        let green_weight = 1.0 - red_weight - blue_weight;
+        let [red, green, blue] = self.to_f64_channels();
+        let luma = red_weight * red + green_weight * green + blue_weight * blue;
+        (
+            luma,
+            0.5 + (blue - luma) / (2.0 * (1.0 - blue_weight)),
+            0.5 + (red - luma) / (2.0 * (1.0 - red_weight)),
+        )
+    }
+
+    /// Constructs a [`HexRGB`] value from full-range luma and chroma, the
+    /// inverse of [`to_ycbcr`].
+    ///
+    /// Not every combination of luma and chroma is a colour that sRGB can
+    /// show. Channels that fall outside the range 0 to 1 are clamped into it,
+    /// so the result may not convert back to the same values.
+    ///
+    /// # Arguments
+    /// * `luma` - The luma, `Y`, from 0 to 1.
+    /// * `blue_difference` - The blue-difference chroma, `Cb`, from 0 to 1.
+    /// * `red_difference` - The red-difference chroma, `Cr`, from 0 to 1.
+    /// * `matrix` - The standard whose luma weights are used.
+    ///
+    /// # Example
+    /// ```
+    /// use ::sht_colour::rgb::{HexRGB, YCbCrMatrix};
+    ///
+    /// let red = <HexRGB<u16>>::from_ycbcr(0.299, 0.331_264, 1.0, YCbCrMatrix::Bt601);
+    ///
+    /// assert_eq!(red, "#F00".parse().unwrap());
+    /// ```
+    ///
+    /// [`to_ycbcr`]: HexRGB::to_ycbcr
+    pub fn from_ycbcr(
+        luma: f64,
+        blue_difference: f64,
+        red_difference: f64,
+        matrix: YCbCrMatrix,
+    ) -> HexRGB<T>
+    where
+        T: FromPrimitive,
+    {
+        let (red_weight, blue_weight) = matrix.weights();
+        let green_weight = 1.0 - red_weight - blue_weight;
+        let red = luma + 2.0 * (1.0 - red_weight) * (red_difference - 0.5);
+        let blue = luma + 2.0 * (1.0 - blue_weight) * (blue_difference - 0.5);
+        let green = (luma - red_weight * red - blue_weight * blue) / green_weight;
+        HexRGB::from_f64_channels([red, green, blue])
+    }
+}
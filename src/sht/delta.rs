@@ -0,0 +1,164 @@
+use super::{ChannelRatios, SHTValueError, SHT};
+use ::num::{rational::Ratio, Integer, Unsigned, Zero};
+
+/// Represents a signed difference between two numeric components of an
+/// [`SHT`].
+///
+/// A difference of zero is always represented as `Raise(0)`.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub enum Difference<T: Clone + Integer + Unsigned> {
+    /// The component is increased by this amount.
+    Raise(Ratio<T>),
+    /// The component is decreased by this amount.
+    Lower(Ratio<T>),
+}
+
+impl<T: Clone + Integer + Unsigned> Difference<T> {
+    /// Measures the difference needed to change `from` into `to`.
+    fn between(from: Ratio<T>, to: Ratio<T>) -> Self {
+        if to >= from {
+            Difference::Raise(to - from)
+        } else {
+            Difference::Lower(from - to)
+        }
+    }
+
+    /// Applies the difference to a value, returning `None` if the result
+    /// would be negative.
+    fn apply(&self, value: Ratio<T>) -> Option<Ratio<T>> {
+        match self {
+            Difference::Raise(amount) => Some(value + amount.clone()),
+            Difference::Lower(amount) if *amount <= value => Some(value - amount.clone()),
+            Difference::Lower(_) => None,
+        }
+    }
+
+    /// Checks whether the difference leaves a value unchanged.
+    fn is_zero(&self) -> bool {
+        match self {
+            Difference::Raise(amount) | Difference::Lower(amount) => amount.is_zero(),
+        }
+    }
+}
+
+impl<T: Clone + Integer + Unsigned> Default for Difference<T> {
+    fn default() -> Self {
+        Difference::Raise(Ratio::zero())
+    }
+}
+
+/// Represents an [`SHT`] relative to a base colour, as the changes needed to
+/// turn the base into it.
+///
+/// Created by [`SHT::delta_from`], and applied with [`SHT::apply_delta`].
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash, Default)]
+pub struct SHTDelta<T: Clone + Integer + Unsigned> {
+    /// The [`ChannelRatios`] that replace those of the base colour, or `None`
+    /// to keep the base colour's primary, direction or secondary colour.
+    pub channel_ratios: Option<ChannelRatios<T>>,
+    /// The change to the shade.
+    pub shade: Difference<T>,
+    /// The change to the tint.
+    pub tint: Difference<T>,
+    /// The change to the blend, which is only possible for colours with a
+    /// direction.
+    pub blend: Difference<T>,
+}
+
+impl<T: Clone + Integer + Unsigned> SHT<T> {
+    /// Measures the changes needed to turn `base` into this [`SHT`].
+    ///
+    /// If both colours have the same primary, direction or secondary colour,
+    /// the delta keeps that of whatever colour it is applied to, and records
+    /// only the change in shade, tint and blend. Otherwise it replaces them
+    /// with this colour's, including any blend, and records only the change
+    /// in shade and tint.
+    ///
+    /// # Example
+    /// ```
+    /// # #[cfg(feature = "parse")]
+    /// # {
+    /// use sht_colour::{sht::Difference, Ratio, SHT};
+    ///
+    /// let base = "8r".parse::<SHT<u32>>().unwrap();
+    /// let lighter = "8r4".parse::<SHT<u32>>().unwrap();
+    ///
+    /// let delta = lighter.delta_from(&base);
+    /// assert_eq!(delta.channel_ratios, None);
+    /// assert_eq!(delta.tint, Difference::Raise(Ratio::new(1, 3)));
+    ///
+    /// // the same delta lightens other colours too
+    /// let blue = "6b".parse::<SHT<u32>>().unwrap();
+    /// assert_eq!(blue.apply_delta(&delta), Ok("6b4".parse().unwrap()));
+    /// # }
+    /// ```
+    pub fn delta_from(&self, base: &SHT<T>) -> SHTDelta<T> {
+        let blend = |channel_ratios: &ChannelRatios<T>| match channel_ratios {
+            ChannelRatios::OneBrightestChannel {
+                direction_blend: Some((_, blend)),
+                ..
+            } => blend.clone(),
+            _ => Ratio::zero(),
+        };
+        let (channel_ratios, blend) = if self.channel_ratios.same_structure(&base.channel_ratios) {
+            (
+                None,
+                Difference::between(blend(&base.channel_ratios), blend(&self.channel_ratios)),
+            )
+        } else {
+            (Some(self.channel_ratios.clone()), Difference::default())
+        };
+        SHTDelta {
+            channel_ratios,
+            shade: Difference::between(base.shade.clone(), self.shade.clone()),
+            tint: Difference::between(base.tint.clone(), self.tint.clone()),
+            blend,
+        }
+    }
+
+    /// Applies a delta from [`delta_from`] to this [`SHT`], treating it as the
+    /// base colour.
+    ///
+    /// Nothing is clamped: the result must be a valid `SHT` in its own right.
+    ///
+    /// # Errors
+    /// Will return `Err` if any component would fall outside the range 0 to
+    /// 1, if the blend changes on a colour without a direction, or if the
+    /// resulting components are otherwise incompatible, as in [`SHT::new`].
+    ///
+    /// [`delta_from`]: SHT::delta_from
+    pub fn apply_delta(&self, delta: &SHTDelta<T>) -> Result<SHT<T>, Vec<SHTValueError>> {
+        let mut errors = Vec::new();
+        let mut apply = |difference: &Difference<T>, value: Ratio<T>| {
+            difference.apply(value).unwrap_or_else(|| {
+                errors.push(SHTValueError::ValueOutOfBounds);
+                Ratio::zero()
+            })
+        };
+        let shade = apply(&delta.shade, self.shade.clone());
+        let tint = apply(&delta.tint, self.tint.clone());
+        let channel_ratios = match delta
+            .channel_ratios
+            .clone()
+            .unwrap_or_else(|| self.channel_ratios.clone())
+        {
+            ChannelRatios::OneBrightestChannel {
+                primary,
+                direction_blend: Some((direction, blend)),
+            } => ChannelRatios::OneBrightestChannel {
+                primary,
+                direction_blend: Some((direction, apply(&delta.blend, blend))),
+            },
+            other => {
+                if !delta.blend.is_zero() {
+                    errors.push(SHTValueError::BlendZero);
+                }
+                other
+            }
+        };
+        if !errors.is_empty() {
+            return Err(errors);
+        }
+        SHT::new(channel_ratios, shade, tint)
+    }
+}
@@ -1,14 +1,33 @@
-use super::{rgb, round_denominator};
+use super::{
+    checked_ratio_sub, clamp_unit, ratio_to_f64, rgb, round_denominator_with, safe_denominator,
+    write_padded, RoundingMode,
+};
+pub use delta::{Difference, SHTDelta};
+#[cfg(feature = "parse")]
 use nom::error::Error;
-use num::{rational::Ratio, CheckedAdd, CheckedDiv, CheckedMul, Integer, One, Unsigned, Zero};
-use parser::parse_sht;
+use num::{rational::Ratio, CheckedMul, FromPrimitive, Integer, One, ToPrimitive, Unsigned, Zero};
+#[cfg(feature = "parse")]
+use num::{CheckedAdd, CheckedDiv};
+#[cfg(feature = "parse")]
+use parser::{parse_sht, parse_sht_fractions, parse_sht_into, parse_sht_strict};
+#[cfg(feature = "parse")]
+use std::str::FromStr;
 use std::{
+    cmp::Ordering,
     convert::TryInto,
-    fmt::{Display, Formatter, Result as FMTResult},
-    ops::{Div, Rem},
-    str::FromStr,
+    fmt::{Display, Formatter, Result as FMTResult, UpperHex},
+    iter::successors,
+    mem::size_of,
 };
 
+/// Number of base-12 digits used for each component of an [`SHT`] when no
+/// precision is specified.
+///
+/// The codes in the [SHT spec](https://omaitzen.com/sht/spec/) are written with
+/// one or two digits per component, so this is treated as the largest precision
+/// that is meaningful to a reader.
+const DEFAULT_PRECISION: usize = 2;
+
 /// A representation of a colour in [SHT format](https://omaitzen.com/sht/).
 ///
 /// The SHT colour format is intended to be human-readable and human-writable.
@@ -25,6 +44,8 @@ use std::{
 ///
 /// # Examples
 /// ```
+/// # #[cfg(feature = "parse")]
+/// # {
 /// use sht_colour::{
 ///     ChannelRatios::OneBrightestChannel,
 ///     ColourChannel::{Green, Red},
@@ -55,6 +76,7 @@ use std::{
 /// assert_eq!(constructed_colour, parsed_colour);
 /// // The colour's string representation is the same as the original string
 /// assert_eq!(constructed_colour.to_string(), code);
+/// # }
 /// ```
 ///
 /// [`Display` implementation]: SHT#impl-Display
@@ -80,6 +102,8 @@ pub struct SHT<T: Clone + Integer + Unsigned> {
 ///
 /// # Example
 /// ```
+/// # #[cfg(feature = "parse")]
+/// # {
 /// use sht_colour::{ChannelRatios::ThreeBrightestChannels, Ratio, SHT};
 ///
 /// let colour = "W".parse::<SHT<_>>().unwrap();
@@ -92,8 +116,18 @@ pub struct SHT<T: Clone + Integer + Unsigned> {
 /// );
 ///
 /// assert_eq!(colour.components(), colour_components);
+/// # }
 /// ```
-#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+///
+/// # Ordering
+///
+/// Colours with one brightest channel come first, then those with two, then
+/// greys. Colours with one brightest channel are ordered by their primary
+/// colour, then those without a direction come before those with one, which
+/// are ordered by direction and then by blend. Colours with two brightest
+/// channels are ordered by their secondary colour. Primary and secondary
+/// colours are ordered as in [`ColourChannel`] and [`SecondaryColour`].
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Hash)]
 pub enum ChannelRatios<T: Clone + Integer + Unsigned> {
     /// Represents colours where one channel (either [red], [blue] or [green])
     /// is strictly brighter than the other two.
@@ -141,10 +175,126 @@ pub enum ChannelRatios<T: Clone + Integer + Unsigned> {
     ThreeBrightestChannels,
 }
 
+impl<T: Clone + Integer + Unsigned> ChannelRatios<T> {
+    /// Constructs the [`ChannelRatios`] of a primary colour, with no
+    /// direction or blend.
+    ///
+    /// This, [`secondary`] and [`grey`] are `const fn`s, so they can be used
+    /// to define constants, as can the variants of [`ColourChannel`] and
+    /// [`SecondaryColour`]. A [`ChannelRatios`] with a blend cannot be built in
+    /// a `const` context, because [`Ratio::new`] is not a `const fn`. Nor can
+    /// an [`SHT`], which also needs a shade and tint, and checks them with
+    /// [`SHT::new`].
+    ///
+    /// # Example
+    /// ```
+    /// use sht_colour::{ChannelRatios, ColourChannel};
+    ///
+    /// const RED: ChannelRatios<u8> = ChannelRatios::primary(ColourChannel::Red);
+    ///
+    /// assert_eq!(
+    ///     RED,
+    ///     ChannelRatios::OneBrightestChannel {
+    ///         primary: ColourChannel::Red,
+    ///         direction_blend: None,
+    ///     }
+    /// );
+    /// ```
+    ///
+    /// [`secondary`]: ChannelRatios::secondary
+    /// [`grey`]: ChannelRatios::grey
+    pub const fn primary(primary: ColourChannel) -> Self {
+        ChannelRatios::OneBrightestChannel {
+            primary,
+            direction_blend: None,
+        }
+    }
+
+    /// Constructs the [`ChannelRatios`] of a secondary colour.
+    ///
+    /// This is a `const fn`, like [`primary`].
+    ///
+    /// [`primary`]: ChannelRatios::primary
+    pub const fn secondary(secondary: SecondaryColour) -> Self {
+        ChannelRatios::TwoBrightestChannels { secondary }
+    }
+
+    /// Constructs the [`ChannelRatios`] of a grey.
+    ///
+    /// This is a `const fn`, like [`primary`].
+    ///
+    /// [`primary`]: ChannelRatios::primary
+    pub const fn grey() -> Self {
+        ChannelRatios::ThreeBrightestChannels
+    }
+
+    /// Returns the blend of a colour with a direction, or `None` for any other
+    /// colour.
+    fn blend(&self) -> Option<Ratio<T>> {
+        match self {
+            ChannelRatios::OneBrightestChannel {
+                direction_blend: Some((_, blend)),
+                ..
+            } => Some(blend.clone()),
+            _ => None,
+        }
+    }
+
+    /// Checks whether two [`ChannelRatios`] have the same primary, direction
+    /// or secondary colour, ignoring the value of any blend.
+    pub(crate) fn same_structure(&self, other: &ChannelRatios<T>) -> bool {
+        match (self, other) {
+            (
+                ChannelRatios::OneBrightestChannel {
+                    primary: first_primary,
+                    direction_blend: first_blend,
+                },
+                ChannelRatios::OneBrightestChannel {
+                    primary: second_primary,
+                    direction_blend: second_blend,
+                },
+            ) => {
+                let direction = |blend: &Option<(ColourChannel, Ratio<T>)>| {
+                    blend.as_ref().map(|(direction, _)| *direction)
+                };
+                first_primary == second_primary && direction(first_blend) == direction(second_blend)
+            }
+            (
+                ChannelRatios::TwoBrightestChannels {
+                    secondary: first_secondary,
+                },
+                ChannelRatios::TwoBrightestChannels {
+                    secondary: second_secondary,
+                },
+            ) => first_secondary == second_secondary,
+            (ChannelRatios::ThreeBrightestChannels, ChannelRatios::ThreeBrightestChannels) => true,
+            _ => false,
+        }
+    }
+}
+
+/// Every [`ColourChannel`], in the order red, green, blue.
+const PRIMARIES: [ColourChannel; 3] = [
+    ColourChannel::Red,
+    ColourChannel::Green,
+    ColourChannel::Blue,
+];
+
+/// Every [`SecondaryColour`], in the order cyan, yellow, magenta.
+const SECONDARIES: [SecondaryColour; 3] = [
+    SecondaryColour::Cyan,
+    SecondaryColour::Yellow,
+    SecondaryColour::Magenta,
+];
+
 /// Represents a primary colour (using additive mixing).
-#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+///
+/// Primary colours are ordered red, green, blue, the order of the channels in
+/// a hex code.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Hash, Default)]
 pub enum ColourChannel {
     /// The colour red.
+    #[default]
     Red,
     /// The colour green.
     Green,
@@ -153,17 +303,58 @@ pub enum ColourChannel {
 }
 
 /// Represents a secondary colour (using additive mixing).
-#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+///
+/// Secondary colours are ordered cyan, magenta, yellow, by the primary colour
+/// each one lacks: red, green, then blue, as [`ColourChannel`] is ordered. So
+/// every secondary colour sorts in the same place as its complement.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Hash, Default)]
 pub enum SecondaryColour {
     /// The colour cyan, made of green and blue.
+    #[default]
     Cyan,
-    /// The colour yellow, made of red and green.
-    Yellow,
     /// The colour magenta, made of red and blue.
     Magenta,
+    /// The colour yellow, made of red and green.
+    Yellow,
+}
+
+impl ColourChannel {
+    /// Checks whether an array contains each [`ColourChannel`] exactly once.
+    pub(crate) fn is_permutation(order: [ColourChannel; 3]) -> bool {
+        PRIMARIES.iter().all(|channel| order.contains(channel))
+    }
+}
+
+impl SecondaryColour {
+    /// Returns the [`SecondaryColour`] made by adding two distinct primary
+    /// colours.
+    ///
+    /// # Panics
+    /// Will panic if both primary colours are the same.
+    pub(crate) fn from_primaries(first: ColourChannel, second: ColourChannel) -> Self {
+        match (first, second) {
+            (ColourChannel::Green, ColourChannel::Blue)
+            | (ColourChannel::Blue, ColourChannel::Green) => SecondaryColour::Cyan,
+            (ColourChannel::Red, ColourChannel::Green)
+            | (ColourChannel::Green, ColourChannel::Red) => SecondaryColour::Yellow,
+            (ColourChannel::Red, ColourChannel::Blue)
+            | (ColourChannel::Blue, ColourChannel::Red) => SecondaryColour::Magenta,
+            _ => panic!("Cannot add a primary colour to itself! {:?}", first),
+        }
+    }
+
+    /// Returns the two primary colours that add to make a [`SecondaryColour`].
+    fn primaries(self) -> (ColourChannel, ColourChannel) {
+        match self {
+            SecondaryColour::Cyan => (ColourChannel::Green, ColourChannel::Blue),
+            SecondaryColour::Yellow => (ColourChannel::Red, ColourChannel::Green),
+            SecondaryColour::Magenta => (ColourChannel::Red, ColourChannel::Blue),
+        }
+    }
 }
 
 /// Represents possible errors parsing an [`SHT`] from a string.
+#[cfg(feature = "parse")]
 #[derive(Debug, PartialEq)]
 #[non_exhaustive]
 pub enum ParsePropertyError {
@@ -172,9 +363,22 @@ pub enum ParsePropertyError {
     /// Could not parse data from the string.
     ParseFailure(Error<String>),
     /// Parsed data from the string, but with leftover unparsed characters.
-    InputRemaining(String),
+    InputRemaining {
+        /// Length in bytes of the prefix of the string that was parsed
+        /// successfully.
+        parsed_len: usize,
+        /// The unparsed characters, starting where parsing stopped.
+        remaining: String,
+    },
+    /// A number had more digits than can be stored without rounding. Only
+    /// returned by [`SHT::parse_strict`].
+    PrecisionOverflow,
+    /// The input contained bytes that are not ASCII characters. Only returned
+    /// by [`parse_bytes`].
+    NonAscii,
 }
 
+#[cfg(feature = "parse")]
 impl From<Error<&str>> for ParsePropertyError {
     fn from(value: Error<&str>) -> Self {
         let Error { input, code } = value;
@@ -210,6 +414,38 @@ pub enum SHTValueError {
     BlendOne,
 }
 
+/// Represents ways an [`SHT`] can be valid, but still differ from the
+/// canonical form of the spec, as found by [`SHT::assert_spec_conformant`].
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+#[non_exhaustive]
+pub enum SpecViolation {
+    /// A grey has a shade other than 1, or black has a shade other than 0.
+    GreyShade,
+    /// The shade, tint or blend has no finite base-12 expansion, so no code
+    /// can represent it exactly.
+    NonDuodecimal,
+}
+
+/// Represents possible errors converting between [`SHT`] and [`HexRGB`]
+/// values with [`SHT::try_to_rgb`] and [`HexRGB::try_to_sht`].
+///
+/// [`HexRGB`]: rgb::HexRGB
+/// [`HexRGB::try_to_sht`]: rgb::HexRGB::try_to_sht
+#[derive(Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ConversionError {
+    /// An intermediate value, such as the denominator of a rounded channel,
+    /// was too large for the integer type.
+    Overflow,
+    /// The converted components could not make a valid [`SHT`]. This only
+    /// happens when converting a [`HexRGB`] with a channel greater than 1,
+    /// which [`HexRGB::new`] allows.
+    ///
+    /// [`HexRGB`]: rgb::HexRGB
+    /// [`HexRGB::new`]: rgb::HexRGB::new
+    InvalidComponents(Vec<SHTValueError>),
+}
+
 impl<T: Clone + Integer + Unsigned> SHT<T> {
     /// Constructs an [`SHT`] value.
     ///
@@ -223,6 +459,8 @@ impl<T: Clone + Integer + Unsigned> SHT<T> {
     ///
     /// # Example
     /// ```
+    /// # #[cfg(feature = "parse")]
+    /// # {
     /// use sht_colour::{ChannelRatios::OneBrightestChannel, ColourChannel::Red, Ratio, SHT};
     ///
     /// let red_ratio = OneBrightestChannel {
@@ -232,6 +470,7 @@ impl<T: Clone + Integer + Unsigned> SHT<T> {
     /// let dark_red = <SHT<u8>>::new(red_ratio, Ratio::new(4, 12), Ratio::from_integer(0)).unwrap();
     ///
     /// assert_eq!(dark_red, "4r".parse().unwrap());
+    /// # }
     /// ```
     ///
     /// # Errors
@@ -252,10 +491,49 @@ impl<T: Clone + Integer + Unsigned> SHT<T> {
         }
     }
 
+    /// Constructs a grey [`SHT`] value with a given tint, from black at 0 to
+    /// white at 1.
+    ///
+    /// The shade is chosen as in the canonical form of the spec: 0 for black,
+    /// and 1 for every other grey.
+    ///
+    /// Unlike [`ChannelRatios::grey`], this is not a `const fn`, because it
+    /// checks the tint, and computes the shade with [`Zero`] and [`One`],
+    /// which are not `const`.
+    ///
+    /// # Example
+    /// ```
+    /// # #[cfg(feature = "parse")]
+    /// # {
+    /// use sht_colour::{Ratio, SHT};
+    ///
+    /// let grey = SHT::<u8>::grey(Ratio::new(1, 2)).unwrap();
+    ///
+    /// assert_eq!(grey, "6".parse().unwrap());
+    /// assert_eq!(SHT::<u8>::grey(Ratio::from_integer(0)), Ok("0".parse().unwrap()));
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    /// Will return `Err` if the tint is greater than 1.
+    ///
+    /// [`Zero`]: num::Zero
+    /// [`One`]: num::One
+    pub fn grey(tint: Ratio<T>) -> Result<Self, Vec<SHTValueError>> {
+        let shade = if tint.is_zero() {
+            Ratio::zero()
+        } else {
+            Ratio::one()
+        };
+        SHT::new(ChannelRatios::grey(), shade, tint)
+    }
+
     /// Splits an [`SHT`] value into its struct fields.
     ///
     /// # Example
     /// ```
+    /// # #[cfg(feature = "parse")]
+    /// # {
     /// use sht_colour::SHT;
     ///
     /// let colour = "7r5bE".parse::<SHT<u8>>().unwrap();
@@ -264,6 +542,7 @@ impl<T: Clone + Integer + Unsigned> SHT<T> {
     /// let new_colour = <SHT<_>>::new(channel_ratios, shade, tint).unwrap();
     ///
     /// assert_eq!(colour, new_colour);
+    /// # }
     /// ```
     pub fn components(self) -> (ChannelRatios<T>, Ratio<T>, Ratio<T>) {
         let Self {
@@ -274,6 +553,54 @@ impl<T: Clone + Integer + Unsigned> SHT<T> {
         (channel_ratios, shade, tint)
     }
 
+    /// Measures the shade of an [`SHT`] as a percentage, from 0 to 100.
+    ///
+    /// The shade is the leading number of an SHT code, read as a base-12
+    /// fraction, so `"8r"` has a shade of 8/12, or about 66.7%. A shade of
+    /// 100% is written by leaving the number out, and 0% is black.
+    ///
+    /// # Example
+    /// ```
+    /// # #[cfg(feature = "parse")]
+    /// # {
+    /// use sht_colour::SHT;
+    ///
+    /// let colour = "8r6g3".parse::<SHT<u8>>().unwrap();
+    ///
+    /// assert!((colour.shade_percent() - 200.0 / 3.0).abs() < 1e-9);
+    /// # }
+    /// ```
+    pub fn shade_percent(&self) -> f64
+    where
+        T: ToPrimitive,
+    {
+        ratio_to_f64(&self.shade) * 100.0
+    }
+
+    /// Measures the tint of an [`SHT`] as a percentage, from 0 to 100.
+    ///
+    /// The tint is the trailing number of an SHT code, read as a base-12
+    /// fraction, so `"r3"` has a tint of 3/12, or 25%. A tint of 0% is written
+    /// by leaving the number out, and 100% is white.
+    ///
+    /// # Example
+    /// ```
+    /// # #[cfg(feature = "parse")]
+    /// # {
+    /// use sht_colour::SHT;
+    ///
+    /// let colour = "8r6g3".parse::<SHT<u8>>().unwrap();
+    ///
+    /// assert!((colour.tint_percent() - 25.0).abs() < 1e-9);
+    /// # }
+    /// ```
+    pub fn tint_percent(&self) -> f64
+    where
+        T: ToPrimitive,
+    {
+        ratio_to_f64(&self.tint) * 100.0
+    }
+
     /// Check whether an [`SHT`] is valid according to the criteria on
     /// <https://omaitzen.com/sht/spec/>. An `SHT` colour should have a unique
     /// canonical form under those conditions.
@@ -288,8 +615,9 @@ impl<T: Clone + Integer + Unsigned> SHT<T> {
             tint,
         } = self;
         // validate fields:
-        let mut errors = Vec::with_capacity(16); // more than strictly needed
-        match channel_ratios.clone() {
+        // an empty `Vec` does not allocate, so valid codes cost nothing here
+        let mut errors = Vec::new();
+        match &channel_ratios {
             ChannelRatios::OneBrightestChannel {
                 primary,
                 direction_blend,
@@ -312,7 +640,7 @@ impl<T: Clone + Integer + Unsigned> SHT<T> {
                     if blend.is_one() {
                         errors.push(SHTValueError::BlendOne);
                     }
-                    if blend > Ratio::one() {
+                    if *blend > Ratio::one() {
                         errors.push(SHTValueError::ValueOutOfBounds);
                     }
                 }
@@ -345,6 +673,67 @@ impl<T: Clone + Integer + Unsigned> SHT<T> {
         }
     }
 
+    /// Checks whether an [`SHT`] is in the canonical form of the spec at
+    /// <https://omaitzen.com/sht/spec/>, beyond the checks made by
+    /// [`SHT::new`].
+    ///
+    /// Rules about how a code is written, such as leaving out a full shade or
+    /// a tint of 0, and never writing trailing zeros, are always followed by
+    /// the [`Display` implementation]. The rules checked here are those that
+    /// a value can break:
+    /// * The shade of a grey has no effect on its colour, so the spec always
+    ///   leaves it out, giving a grey a full shade. Black is the exception,
+    ///   written `"0"` with a shade of 0, since a code with neither shade nor
+    ///   tint would be empty.
+    /// * Every number in a code is a base-12 fraction with finitely many
+    ///   digits, so the shade, tint and blend must each be a fraction whose
+    ///   denominator divides a power of 12.
+    ///
+    /// Every [`SHT`] parsed from a string or converted from a [`HexRGB`]
+    /// follows these rules, but one constructed with [`SHT::new`] may not.
+    ///
+    /// # Example
+    /// ```
+    /// # #[cfg(feature = "parse")]
+    /// # {
+    /// use sht_colour::{
+    ///     sht::{ChannelRatios, SpecViolation},
+    ///     Ratio, SHT,
+    /// };
+    ///
+    /// assert_eq!("8r6g3".parse::<SHT<u32>>().unwrap().assert_spec_conformant(), Ok(()));
+    ///
+    /// let half = Ratio::new(1, 2);
+    /// let shaded_grey = SHT::<u32>::new(ChannelRatios::ThreeBrightestChannels, half, half).unwrap();
+    /// assert_eq!(shaded_grey.assert_spec_conformant(), Err(SpecViolation::GreyShade));
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    /// Will return `Err` with the first rule that the `SHT` breaks, in the
+    /// order listed above.
+    ///
+    /// [`Display` implementation]: SHT#impl-Display
+    /// [`HexRGB`]: rgb::HexRGB
+    pub fn assert_spec_conformant(&self) -> Result<(), SpecViolation>
+    where
+        u8: Into<T>,
+    {
+        if let ChannelRatios::ThreeBrightestChannels = self.channel_ratios {
+            let canonical_shade = if self.tint.is_zero() {
+                Ratio::zero()
+            } else {
+                Ratio::one()
+            };
+            if self.shade != canonical_shade {
+                return Err(SpecViolation::GreyShade);
+            }
+        }
+        self.exact_precision()
+            .map(|_| ())
+            .ok_or(SpecViolation::NonDuodecimal)
+    }
+
     /// Convert a colour from [`SHT`] format to [`HexRGB`].
     ///
     /// # Arguments
@@ -353,84 +742,2015 @@ impl<T: Clone + Integer + Unsigned> SHT<T> {
     ///
     /// # Example
     /// ```
+    /// # #[cfg(feature = "parse")]
+    /// # {
     /// use sht_colour::{rgb::HexRGB, sht::SHT};
     ///
     /// let red_rgb = "#F00".parse::<HexRGB<u32>>().unwrap();
     /// let red_sht = "r".parse::<SHT<u32>>().unwrap();
     ///
     /// assert_eq!(red_sht.to_rgb(1), red_rgb);
+    /// # }
     /// ```
     ///
+    /// # Panics
+    /// **Panics on overflow!** Use [`try_to_rgb`] to handle it instead.
+    ///
     /// [`HexRGB`]: rgb::HexRGB
+    /// [`try_to_rgb`]: SHT::try_to_rgb
     pub fn to_rgb(self, precision: usize) -> rgb::HexRGB<T>
+    where
+        T: Integer + Unsigned + From<u8> + Clone + CheckedMul,
+    {
+        self.try_to_rgb(precision)
+            .expect("Overflow converting SHT to RGB")
+    }
+
+    /// Convert a colour from [`SHT`] format to [`HexRGB`], as in [`to_rgb`],
+    /// but rounding each channel with the chosen [`RoundingMode`] instead of
+    /// rounding halves up.
+    ///
+    /// # Arguments
+    /// * `precision` - How many hex digits to round the result of conversion
+    ///   to.
+    /// * `mode` - How to round channels that lie between two hex codes.
+    ///
+    /// # Example
+    /// ```
+    /// # #[cfg(feature = "parse")]
+    /// # {
+    /// use sht_colour::{rgb::HexRGB, RoundingMode, SHT};
+    ///
+    /// // the red channel is 7.5 over 0xF
+    /// let red = "6r".parse::<SHT<u32>>().unwrap();
+    ///
+    /// assert_eq!(
+    ///     red.to_rgb_with_rounding(1, RoundingMode::HalfEven),
+    ///     "#800".parse::<HexRGB<u32>>().unwrap()
+    /// );
+    /// assert_eq!(
+    ///     red.to_rgb_with_rounding(1, RoundingMode::Truncate),
+    ///     "#700".parse::<HexRGB<u32>>().unwrap()
+    /// );
+    /// # }
+    /// ```
+    ///
+    /// # Panics
+    /// **Panics on overflow!**
+    ///
+    /// [`HexRGB`]: rgb::HexRGB
+    /// [`to_rgb`]: SHT::to_rgb
+    pub fn to_rgb_with_rounding(self, precision: usize, mode: RoundingMode) -> rgb::HexRGB<T>
+    where
+        T: Integer + Unsigned + From<u8> + Clone + CheckedMul,
+    {
+        self.checked_to_rgb(precision, mode)
+            .expect("Overflow converting SHT to RGB")
+    }
+
+    /// Convert a colour from [`SHT`] format to [`HexRGB`], as in [`to_rgb`],
+    /// but returning an error instead of panicking on overflow.
+    ///
+    /// # Arguments
+    /// * `precision` - How many hex digits to round the result of conversion
+    ///   to.
+    ///
+    /// # Example
+    /// ```
+    /// # #[cfg(feature = "parse")]
+    /// # {
+    /// use sht_colour::{rgb::HexRGB, sht::ConversionError, SHT};
+    ///
+    /// let colour = "8r3".parse::<SHT<u8>>().unwrap();
+    ///
+    /// assert_eq!(colour.try_to_rgb(1), Ok("#B44".parse::<HexRGB<u8>>().unwrap()));
+    /// assert_eq!(colour.try_to_rgb(2), Err(ConversionError::Overflow));
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    /// Will return [`ConversionError::Overflow`] if computing the channels, or
+    /// rounding them to `precision` hex digits, overflows the integer type.
+    ///
+    /// [`HexRGB`]: rgb::HexRGB
+    /// [`to_rgb`]: SHT::to_rgb
+    pub fn try_to_rgb(self, precision: usize) -> Result<rgb::HexRGB<T>, ConversionError>
+    where
+        T: Integer + Unsigned + From<u8> + Clone + CheckedMul,
+    {
+        self.checked_to_rgb(precision, RoundingMode::HalfUp)
+    }
+
+    /// Convert a colour from [`SHT`] format to [`HexRGB`], rounding with
+    /// `mode`, and returning an error on overflow.
+    ///
+    /// # Errors
+    /// Will return [`ConversionError::Overflow`] if computing the channels, or
+    /// rounding them to `precision` hex digits, overflows the integer type.
+    ///
+    /// [`HexRGB`]: rgb::HexRGB
+    fn checked_to_rgb(
+        self,
+        precision: usize,
+        mode: RoundingMode,
+    ) -> Result<rgb::HexRGB<T>, ConversionError>
     where
         T: Integer + Unsigned + From<u8> + Clone + CheckedMul,
     {
         // Round hexadecimal number to precision
-        let round =
-            |ratio: Ratio<T>| round_denominator::<T>(ratio, 16.into(), precision, <_>::one());
+        let round = |ratio: Ratio<T>| {
+            round_denominator_with::<T>(ratio, 16.into(), precision, <_>::one(), mode)
+                .ok_or(ConversionError::Overflow)
+        };
+
+        let (red, green, blue) = self
+            .checked_exact_channels()
+            .ok_or(ConversionError::Overflow)?;
+        Ok(rgb::HexRGB::new(round(red)?, round(green)?, round(blue)?))
+    }
+
+    /// Formats an [`SHT`] as a hex code, computing each digit directly from
+    /// the exact value of each channel, the same way as the reference
+    /// implementation of the spec.
+    ///
+    /// The reference implementation scales each channel by `16^digits` and
+    /// truncates, clamping the result to the largest `digits`-digit number.
+    /// That differs slightly from [`to_rgb`], which rounds to the nearest
+    /// fraction over `16^digits - 1`, the value that the hex code represents
+    /// when parsed. For instance, a channel at 3/4 brightness is written `C0`
+    /// by the spec, but `BF` by [`to_rgb`], because 3/4 of `0xFF` is 191.25.
+    ///
+    /// Use this method to produce the same strings as the spec, and
+    /// [`to_rgb`] to find the closest [`HexRGB`] value.
+    ///
+    /// # Arguments
+    /// * `digits` - How many hex digits to use for each channel.
+    ///
+    /// # Example
+    /// ```
+    /// # #[cfg(feature = "parse")]
+    /// # {
+    /// use sht_colour::{rgb::HexRGB, SHT};
+    ///
+    /// let colour = "8r3".parse::<SHT<u32>>().unwrap();
+    ///
+    /// assert_eq!(colour.to_hex_string(2), "#C04040");
+    /// assert_eq!(colour.to_rgb(2).to_string(), "#BF4040");
+    /// # }
+    /// ```
+    ///
+    /// # Panics
+    /// **Panics on overflow!**
+    ///
+    /// [`to_rgb`]: SHT::to_rgb
+    /// [`HexRGB`]: rgb::HexRGB
+    pub fn to_hex_string(self, digits: usize) -> String
+    where
+        T: CheckedMul + From<u8> + UpperHex,
+    {
+        let scale =
+            safe_denominator(T::from(16), digits).expect("Overflow calculating denominator");
+        let maximum = scale.clone() - T::one();
+        let to_digits = |ratio: Ratio<T>| {
+            ratio
+                .checked_mul(&Ratio::from_integer(scale.clone()))
+                .expect("Overflow calculating hex digits")
+                .to_integer()
+                .min(maximum.clone())
+        };
 
+        let (red, green, blue) = self.exact_channels();
+        format!(
+            "#{:0width$X}{:0width$X}{:0width$X}",
+            to_digits(red),
+            to_digits(green),
+            to_digits(blue),
+            width = digits
+        )
+    }
+
+    /// Calculates the exact brightness of the red, green and blue channels of
+    /// an [`SHT`], without rounding.
+    pub(crate) fn exact_channels(self) -> (Ratio<T>, Ratio<T>, Ratio<T>) {
         let (channel_ratios, shade, tint) = self.components();
         let (max, min) = (
             tint.clone() + shade * (<Ratio<_>>::one() - tint.clone()),
             tint,
         );
+        let centremost = channel_ratios
+            .blend()
+            .map(|blend| min.clone() + blend * (max.clone() - min.clone()));
+        place_channels(channel_ratios, max, min, centremost)
+    }
 
-        let (red, green, blue) = match channel_ratios {
-            ChannelRatios::ThreeBrightestChannels => (min.clone(), min.clone(), min),
-            ChannelRatios::TwoBrightestChannels { secondary } => match secondary {
-                SecondaryColour::Cyan => (min, max.clone(), max),
-                SecondaryColour::Yellow => (max.clone(), max, min),
-                SecondaryColour::Magenta => (max.clone(), min, max),
-            },
+    /// Calculates the exact brightness of each channel, as in
+    /// [`exact_channels`], returning `None` if the arithmetic overflows.
+    ///
+    /// [`exact_channels`]: SHT::exact_channels
+    fn checked_exact_channels(self) -> Option<(Ratio<T>, Ratio<T>, Ratio<T>)>
+    where
+        T: CheckedMul,
+    {
+        let (channel_ratios, shade, tint) = self.components();
+        let one = <Ratio<_>>::one();
+        // `max = tint + shade * (1 - tint)`, written with only subtraction
+        let max = checked_ratio_sub(
+            &one,
+            &checked_ratio_sub(&one, &shade)?.checked_mul(&checked_ratio_sub(&one, &tint)?)?,
+        )?;
+        // `centremost = max - (1 - blend) * (max - min)`, likewise
+        let centremost = match channel_ratios.blend() {
+            Some(blend) => Some(checked_ratio_sub(
+                &max,
+                &checked_ratio_sub(&one, &blend)?.checked_mul(&checked_ratio_sub(&max, &tint)?)?,
+            )?),
+            None => None,
+        };
+        Some(place_channels(channel_ratios, max, tint, centremost))
+    }
+
+    /// Finds the complementary colour of an [`SHT`], with its hue rotated
+    /// halfway around the colour wheel.
+    ///
+    /// Each channel is reflected between the brightest and dimmest channels,
+    /// so the brightest becomes the dimmest and vice versa, which leaves the
+    /// shade and tint unchanged:
+    /// * A primary colour becomes the secondary colour made of the other two
+    ///   primaries, so red becomes cyan, green becomes magenta, and blue
+    ///   becomes yellow, and the reverse.
+    /// * A primary colour with a direction becomes the third primary colour,
+    ///   with the same direction, and the blend subtracted from 1. For
+    ///   instance, `"r4g"` (orange) becomes `"b8g"` (azure).
+    /// * Greys are unchanged.
+    ///
+    /// # Example
+    /// ```
+    /// # #[cfg(feature = "parse")]
+    /// # {
+    /// use sht_colour::SHT;
+    ///
+    /// let colour = "8r4g3".parse::<SHT<u32>>().unwrap();
+    ///
+    /// assert_eq!(colour.complement(), "8b8g3".parse().unwrap());
+    /// assert_eq!("r".parse::<SHT<u32>>().unwrap().complement(), "c".parse().unwrap());
+    /// # }
+    /// ```
+    pub fn complement(&self) -> SHT<T> {
+        let opposite_secondary = |primary| match primary {
+            ColourChannel::Red => SecondaryColour::Cyan,
+            ColourChannel::Green => SecondaryColour::Magenta,
+            ColourChannel::Blue => SecondaryColour::Yellow,
+        };
+        let opposite_primary = |secondary| match secondary {
+            SecondaryColour::Cyan => ColourChannel::Red,
+            SecondaryColour::Magenta => ColourChannel::Green,
+            SecondaryColour::Yellow => ColourChannel::Blue,
+        };
+        let channel_ratios = match self.channel_ratios.clone() {
             ChannelRatios::OneBrightestChannel {
                 primary,
-                direction_blend,
-            } => {
-                let (mut red, mut green, mut blue) = (min.clone(), min.clone(), min.clone());
-                if let Some((direction, blend)) = direction_blend {
-                    let centremost_channel = min.clone() + blend * (max.clone() - min);
-                    match direction {
-                        ColourChannel::Red => red = centremost_channel,
-                        ColourChannel::Green => green = centremost_channel,
-                        ColourChannel::Blue => blue = centremost_channel,
-                    }
-                };
-                match primary {
-                    ColourChannel::Red => red = max,
-                    ColourChannel::Green => green = max,
-                    ColourChannel::Blue => blue = max,
-                };
-                (red, green, blue)
+                direction_blend: None,
+            } => ChannelRatios::secondary(opposite_secondary(primary)),
+            ChannelRatios::OneBrightestChannel {
+                primary,
+                direction_blend: Some((direction, blend)),
+            } => ChannelRatios::OneBrightestChannel {
+                primary: opposite_primary(SecondaryColour::from_primaries(primary, direction)),
+                direction_blend: Some((direction, Ratio::one() - blend)),
+            },
+            ChannelRatios::TwoBrightestChannels { secondary } => {
+                ChannelRatios::primary(opposite_primary(secondary))
             }
+            ChannelRatios::ThreeBrightestChannels => ChannelRatios::grey(),
         };
-        rgb::HexRGB::new(round(red), round(green), round(blue))
+        SHT {
+            channel_ratios,
+            ..self.clone()
+        }
     }
-}
-
-/// Parses an [`SHT`] from a string.
-///
-/// See the [`Display` implementation] for the format.
-///
-/// # Example
-/// ```
-/// use sht_colour::SHT;
-///
-/// let first_colour = "5r600000".parse::<SHT<u8>>().unwrap();
-/// let second_colour = "500r6".parse::<SHT<u8>>().unwrap();
-///
-/// assert_eq!(first_colour, second_colour);
-/// ```
-///
-/// [`Display` implementation]: SHT#impl-Display
-impl<T> FromStr for SHT<T>
-where
-    T: Clone + Integer + Unsigned + FromStr + CheckedMul + CheckedAdd + CheckedDiv,
-    u8: Into<T>,
-{
-    type Err = ParsePropertyError;
 
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
+    /// Converts an [`SHT`] to the grey with the same luma, exactly as
+    /// [`HexRGB::grayscale`] does, but without rounding.
+    ///
+    /// The result is always a valid grey, with the shade of the canonical
+    /// form, and a grey is left unchanged.
+    ///
+    /// # Example
+    /// ```
+    /// # #[cfg(feature = "parse")]
+    /// # {
+    /// use sht_colour::{Ratio, SHT};
+    ///
+    /// let red = "r".parse::<SHT<u32>>().unwrap();
+    /// let grey = red.desaturate();
+    ///
+    /// assert_eq!(grey.components().2, Ratio::new(299, 1000));
+    /// assert_eq!("6".parse::<SHT<u32>>().unwrap().desaturate(), "6".parse().unwrap());
+    /// # }
+    /// ```
+    ///
+    /// # Panics
+    /// **Panics on overflow!**
+    ///
+    /// [`HexRGB::grayscale`]: rgb::HexRGB::grayscale
+    pub fn desaturate(&self) -> SHT<T>
+    where
+        T: CheckedMul + From<u16>,
+    {
+        let (red, green, blue) = self.clone().exact_channels();
+        let luma = rgb::HexRGB::new(red, green, blue).grayscale().red();
+        SHT::grey(luma).expect("Luma of a valid colour should be at most 1!")
+    }
+
+    /// Rearranges the colour channels of an [`SHT`], exactly as
+    /// [`HexRGB::swizzle`] does, but without rounding.
+    ///
+    /// The shade and tint are unchanged, as are any blends.
+    ///
+    /// # Arguments
+    /// * `order` - Which of the original channels to use for the red, green
+    ///   and blue channels of the result, in that order. Must contain each
+    ///   channel exactly once.
+    ///
+    /// # Example
+    /// ```
+    /// # #[cfg(feature = "parse")]
+    /// # {
+    /// use sht_colour::{ColourChannel, SHT};
+    ///
+    /// let colour = "8r6g3".parse::<SHT<u32>>().unwrap();
+    /// let swapped = colour.swizzle([
+    ///     ColourChannel::Blue,
+    ///     ColourChannel::Green,
+    ///     ColourChannel::Red,
+    /// ]);
+    ///
+    /// assert_eq!(swapped, "8b6g3".parse().unwrap());
+    /// # }
+    /// ```
+    ///
+    /// # Panics
+    /// Will panic if `order` is not a permutation of the three channels.
+    ///
+    /// [`HexRGB::swizzle`]: rgb::HexRGB::swizzle
+    pub fn swizzle(self, order: [ColourChannel; 3]) -> SHT<T> {
+        assert!(
+            ColourChannel::is_permutation(order),
+            "Channel order must contain each channel once! {:?}",
+            order
+        );
+        // the channel that an original channel is moved to
+        let destination = |channel| match order.iter().position(|&new| new == channel) {
+            Some(0) => ColourChannel::Red,
+            Some(1) => ColourChannel::Green,
+            _ => ColourChannel::Blue,
+        };
+        let (channel_ratios, shade, tint) = self.components();
+        let channel_ratios = match channel_ratios {
+            ChannelRatios::OneBrightestChannel {
+                primary,
+                direction_blend,
+            } => ChannelRatios::OneBrightestChannel {
+                primary: destination(primary),
+                direction_blend: direction_blend
+                    .map(|(direction, blend)| (destination(direction), blend)),
+            },
+            ChannelRatios::TwoBrightestChannels { secondary } => {
+                let (first, second) = secondary.primaries();
+                ChannelRatios::TwoBrightestChannels {
+                    secondary: SecondaryColour::from_primaries(
+                        destination(first),
+                        destination(second),
+                    ),
+                }
+            }
+            ChannelRatios::ThreeBrightestChannels => ChannelRatios::ThreeBrightestChannels,
+        };
+        SHT {
+            channel_ratios,
+            shade,
+            tint,
+        }
+    }
+
+    /// Finds the three anchor colours that an [`SHT`] is built from, as
+    /// [`HexRGB`] values: the pure hue, the hue with the shade applied, and
+    /// the hue with the tint applied, in that order.
+    ///
+    /// * The pure hue has the same primary, blend and secondary, but a shade of
+    ///   1 and a tint of 0, so its brightest channel is at full brightness and
+    ///   its dimmest is off. For example, `"r"` is the pure hue of `"8r4"`.
+    /// * The shaded anchor keeps the shade but has a tint of 0, darkening the
+    ///   pure hue towards black, like `"8r"`.
+    /// * The tinted anchor keeps the tint but has a shade of 1, lightening the
+    ///   pure hue towards white, like `"r4"`.
+    ///
+    /// Varying the shade moves a colour between its tinted anchor and a grey,
+    /// and varying the tint moves it between its shaded anchor and white, so
+    /// these anchors are the corners of the plane of colours with the same
+    /// hue. Greys have no hue, so their pure hue and shaded anchor are black,
+    /// and their tinted anchor is the grey itself.
+    ///
+    /// # Arguments
+    /// * `precision` - How many hex digits to round each anchor to.
+    ///
+    /// # Example
+    /// ```
+    /// # #[cfg(feature = "parse")]
+    /// # {
+    /// use sht_colour::{rgb::HexRGB, SHT};
+    ///
+    /// let colour = "6r4".parse::<SHT<u32>>().unwrap();
+    /// let (pure, shaded, tinted) = colour.anchors(2);
+    ///
+    /// assert_eq!(pure, "#FF0000".parse::<HexRGB<_>>().unwrap());
+    /// assert_eq!(shaded, "#800000".parse::<HexRGB<_>>().unwrap());
+    /// assert_eq!(tinted, "#FF5555".parse::<HexRGB<_>>().unwrap());
+    /// # }
+    /// ```
+    ///
+    /// # Panics
+    /// **Panics on overflow!**
+    ///
+    /// [`HexRGB`]: rgb::HexRGB
+    pub fn anchors(&self, precision: usize) -> (rgb::HexRGB<T>, rgb::HexRGB<T>, rgb::HexRGB<T>)
+    where
+        T: From<u8> + CheckedMul,
+    {
+        let (channel_ratios, shade, tint) = self.clone().components();
+        let with = |shade, tint| {
+            SHT::collapse(channel_ratios.clone(), shade, tint)
+                .expect("Anchors should always be valid")
+                .to_rgb(precision)
+        };
+        (
+            with(Ratio::one(), Ratio::zero()),
+            with(shade, Ratio::zero()),
+            with(Ratio::one(), tint),
+        )
+    }
+
+    /// Computes a grid of colours with the same hue as an [`SHT`], covering
+    /// every shade and tint, such as for the plane of a colour picker.
+    ///
+    /// The grid is a vector of rows. Each row has a constant tint, starting
+    /// from a tint of 0 in the first row and increasing evenly to a tint of 1,
+    /// which is white, in the last row. Within each row, the shade starts from
+    /// 0, which is black or grey, in the first column, and increases evenly to
+    /// 1, which is the pure hue or a tint of it, in the last column. So the
+    /// first row runs from black to the pure hue, and the [`anchors`] of every
+    /// colour with this hue lie in the grid.
+    ///
+    /// Greys have no hue, so for them each row is a single grey repeated.
+    ///
+    /// # Arguments
+    /// * `shade_steps` - How many columns, each with a different shade. With
+    ///   only one column, its shade is 0.
+    /// * `tint_steps` - How many rows, each with a different tint. With only
+    ///   one row, its tint is 0.
+    /// * `precision` - How many hex digits to round each colour to.
+    ///
+    /// # Example
+    /// ```
+    /// # #[cfg(feature = "parse")]
+    /// # {
+    /// use sht_colour::{rgb::HexRGB, SHT};
+    ///
+    /// let plane = "r".parse::<SHT<u32>>().unwrap().plane(3, 2, 1);
+    /// let hex = |code: &str| code.parse::<HexRGB<u32>>().unwrap();
+    ///
+    /// assert_eq!(plane[0], [hex("#000"), hex("#800"), hex("#F00")]);
+    /// assert_eq!(plane[1], [hex("#FFF"), hex("#FFF"), hex("#FFF")]);
+    /// # }
+    /// ```
+    ///
+    /// # Panics
+    /// **Panics on overflow!** Will also panic if either number of steps does
+    /// not fit in `T`.
+    ///
+    /// [`anchors`]: SHT::anchors
+    pub fn plane(
+        &self,
+        shade_steps: usize,
+        tint_steps: usize,
+        precision: usize,
+    ) -> Vec<Vec<rgb::HexRGB<T>>>
+    where
+        T: From<u8> + CheckedMul + FromPrimitive,
+    {
+        let to_integer = |n: usize| T::from_usize(n).expect("Number of steps should fit in type");
+        let fraction = |step: usize, steps: usize| {
+            Ratio::new(to_integer(step), to_integer(steps.saturating_sub(1).max(1)))
+        };
+        (0..tint_steps)
+            .map(|row| {
+                (0..shade_steps)
+                    .map(|column| {
+                        SHT::collapse(
+                            self.channel_ratios.clone(),
+                            fraction(column, shade_steps),
+                            fraction(row, tint_steps),
+                        )
+                        .expect("Fractions of the steps should always be valid")
+                        .to_rgb(precision)
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// Expresses an [`SHT`] as a CSS `color-mix()` expression that mixes its
+    /// pure hue with black and white, the same way the shade and tint do.
+    ///
+    /// The expression always has the form
+    ///
+    /// ```text
+    /// color-mix(in srgb, color-mix(in srgb, <hue>, black <black>%), white <white>%)
+    /// ```
+    ///
+    /// where `<hue>` is the pure hue from [`anchors`], written as a hex code,
+    /// `<black>` is 100% minus the shade, and `<white>` is the tint. Mixing
+    /// with black first and white second matches the definition of an `SHT`,
+    /// where the tint lightens the colour after it has been shaded. Greys have
+    /// no hue, so theirs is black.
+    ///
+    /// The hue is rounded to `precision` hex digits per channel, as in
+    /// [`to_rgb`], and written with that many digits. The percentages are
+    /// rounded to two decimal places, with trailing zeros removed.
+    ///
+    /// # Arguments
+    /// * `precision` - How many hex digits to round the hue to.
+    ///
+    /// # Example
+    /// ```
+    /// # #[cfg(feature = "parse")]
+    /// # {
+    /// use sht_colour::SHT;
+    ///
+    /// let colour = "8r3".parse::<SHT<u32>>().unwrap();
+    ///
+    /// assert_eq!(
+    ///     colour.to_css_color_mix(2),
+    ///     "color-mix(in srgb, color-mix(in srgb, #FF0000, black 33.33%), white 25%)"
+    /// );
+    /// # }
+    /// ```
+    ///
+    /// # Panics
+    /// **Panics on overflow!**
+    ///
+    /// [`anchors`]: SHT::anchors
+    /// [`to_rgb`]: SHT::to_rgb
+    pub fn to_css_color_mix(&self, precision: usize) -> String
+    where
+        T: From<u8> + CheckedMul + ToPrimitive + UpperHex,
+    {
+        let percentage = |ratio: &Ratio<T>| {
+            let text = format!("{:.2}", ratio_to_f64(ratio) * 100.0);
+            text.trim_end_matches('0').trim_end_matches('.').to_owned()
+        };
+        let (hue, _, _) = self.anchors(precision);
+        format!(
+            "color-mix(in srgb, color-mix(in srgb, {:width$}, black {}%), white {}%)",
+            hue,
+            percentage(&(<Ratio<T>>::one() - self.shade.clone())),
+            percentage(&self.tint),
+            width = precision
+        )
+    }
+
+    /// Parses an [`SHT`] from a string in an extended format where shades,
+    /// tints and blends can also be written as exact fractions.
+    ///
+    /// **This format is not part of the SHT spec**, so strings using it will
+    /// not be understood by other implementations. It is useful for writing
+    /// components that have no short base-12 expansion, such as 1/5.
+    ///
+    /// # Format
+    ///
+    /// The format is the same as the [`Display` implementation], except that
+    /// each of `<shade>`, `<blend>` and `<tint>` may be written either as
+    /// base-12 digits or as a fraction `<numerator>/<denominator>`, where the
+    /// numerator and denominator are *decimal* integers. Whitespace is allowed
+    /// before and after each number, and at the start and end of the string.
+    /// Fractions do not need to be in lowest terms, but must lie between 0
+    /// and 1 inclusive, like other components.
+    ///
+    /// # Example
+    /// ```
+    /// use sht_colour::{Ratio, SHT};
+    ///
+    /// let colour = <SHT<u32>>::parse_fractions("1/2 r").unwrap();
+    /// assert_eq!(colour, "6r".parse().unwrap());
+    ///
+    /// let colour = <SHT<u32>>::parse_fractions("1/2 r 1/5 g 2/6").unwrap();
+    /// let (_, shade, tint) = colour.components();
+    /// assert_eq!((shade, tint), (Ratio::new(1, 2), Ratio::new(1, 3)));
+    /// ```
+    ///
+    /// # Errors
+    /// Will return `Err` if the string could not be parsed or if the `SHT`
+    /// could not be constructed from whatever values were parsed, in the same
+    /// way as [`SHT::from_str`].
+    ///
+    /// [`Display` implementation]: SHT#impl-Display
+    #[cfg(feature = "parse")]
+    pub fn parse_fractions(s: &str) -> Result<SHT<T>, ParsePropertyError>
+    where
+        T: FromStr + CheckedMul + CheckedAdd,
+        u8: Into<T>,
+    {
+        parse_sht_fractions(s)
+    }
+
+    /// Parses an [`SHT`] from a string, like the [`FromStr` implementation],
+    /// but without rounding numbers that have too many digits.
+    ///
+    /// When parsing normally, a number whose denominator would overflow `T`
+    /// is rounded to the nearest number that fits, so for instance
+    /// `"EEEEEEc"` parses as an `SHT<u8>` even though its shade cannot be
+    /// stored exactly. This strict mode instead returns an error, for
+    /// pipelines that must not lose precision silently.
+    ///
+    /// # Example
+    /// ```
+    /// use sht_colour::{sht::ParsePropertyError, SHT};
+    ///
+    /// assert!("EEEEEEc".parse::<SHT<u8>>().is_ok());
+    /// assert_eq!(
+    ///     SHT::<u8>::parse_strict("EEEEEEc"),
+    ///     Err(ParsePropertyError::PrecisionOverflow)
+    /// );
+    /// assert_eq!(SHT::<u8>::parse_strict("8r6g3"), "8r6g3".parse());
+    /// ```
+    ///
+    /// # Errors
+    /// Will return `Err` if the string could not be parsed, if a number would
+    /// need rounding to fit in `T`, or if the `SHT` could not be constructed
+    /// from whatever values were parsed.
+    ///
+    /// [`FromStr` implementation]: SHT#impl-FromStr
+    #[cfg(feature = "parse")]
+    pub fn parse_strict(s: &str) -> Result<SHT<T>, ParsePropertyError>
+    where
+        T: CheckedMul + CheckedAdd,
+        u8: Into<T>,
+    {
+        parse_sht_strict(s)
+    }
+
+    /// Parses an [`SHT`] from a string without allocating, for contexts where
+    /// allocation is unavailable or too slow.
+    ///
+    /// Accepts the same strings as the [`FromStr`] implementation. As well as
+    /// returning the `SHT`, this copies the digits of the shade, blend and
+    /// tint, in that order, into `digits`, each as a value from 0 to 11, or 12
+    /// for a `'W'`. A buffer as long as the string is always large enough.
+    /// Unlike the `FromStr` implementation, no details are given about errors.
+    ///
+    /// # Arguments
+    /// * `s` - The string to parse.
+    /// * `digits` - A buffer to copy the digits into.
+    ///
+    /// Returns the `SHT` along with how many digits were written for each of
+    /// the shade, blend and tint, or `None` if the string could not be parsed
+    /// or `digits` is too small.
+    ///
+    /// # Example
+    /// ```
+    /// use sht_colour::SHT;
+    ///
+    /// let mut digits = [0; 8];
+    /// let (colour, counts) = SHT::<u32>::parse_into("8r6gX3", &mut digits).unwrap();
+    ///
+    /// assert_eq!(colour, "8r6gX3".parse().unwrap());
+    /// assert_eq!(counts, [1, 1, 2]);
+    /// assert_eq!(digits[..4], [8, 6, 10, 3]);
+    /// ```
+    #[cfg(feature = "parse")]
+    pub fn parse_into(s: &str, digits: &mut [u8]) -> Option<(SHT<T>, [usize; 3])>
+    where
+        T: CheckedMul + CheckedAdd,
+        u8: Into<T>,
+    {
+        parse_sht_into(s, digits)
+    }
+
+    /// Converts an [`SHT`] to the hue, whiteness and blackness of the HWB
+    /// colour model, exactly and without converting through RGB.
+    ///
+    /// The components correspond closely:
+    /// * Whiteness is exactly the tint `t`, the brightness of the dimmest
+    ///   channel.
+    /// * Blackness is `(1 - s) * (1 - t)` for a shade `s`, since the brightest
+    ///   channel has brightness `t + s * (1 - t)`. Greys ignore their shade,
+    ///   so their blackness is `1 - t`.
+    /// * The hue, measured in turns as in [`HexRGB::to_hwb`], is `p / 3` for a
+    ///   primary `p` at 0, 1 or 2 thirds of a turn (red, green or blue). A
+    ///   blend `β` moves it `β / 6` towards the direction, and a secondary
+    ///   colour lies halfway between its two primaries. Greys have a hue of 0.
+    ///
+    /// So the shade measures how colourful the colour is relative to the room
+    /// left by the whiteness, and the blend is the position of the hue within
+    /// one sixth of the colour wheel.
+    ///
+    /// # Example
+    /// ```
+    /// # #[cfg(feature = "parse")]
+    /// # {
+    /// use sht_colour::{Ratio, SHT};
+    ///
+    /// let colour = "8r6g3".parse::<SHT<u32>>().unwrap();
+    ///
+    /// assert_eq!(
+    ///     colour.to_hwb_exact(),
+    ///     (Ratio::new(1, 12), Ratio::new(1, 4), Ratio::new(1, 4))
+    /// );
+    /// # }
+    /// ```
+    ///
+    /// [`HexRGB::to_hwb`]: rgb::HexRGB::to_hwb
+    pub fn to_hwb_exact(&self) -> (Ratio<T>, Ratio<T>, Ratio<T>)
+    where
+        T: From<u8>,
+    {
+        let (channel_ratios, shade, tint) = self.clone().components();
+        let blackness = if channel_ratios == ChannelRatios::ThreeBrightestChannels {
+            // greys ignore the shade, and have every channel as bright as the tint
+            <Ratio<T>>::one() - tint.clone()
+        } else {
+            (<Ratio<T>>::one() - shade) * (<Ratio<T>>::one() - tint.clone())
+        };
+        let sixths = |n: u8| Ratio::from_integer(T::from(n));
+        let index = |channel| match channel {
+            ColourChannel::Red => 0,
+            ColourChannel::Green => 1,
+            ColourChannel::Blue => 2,
+        };
+        let hue_sixths = match channel_ratios {
+            ChannelRatios::ThreeBrightestChannels => Ratio::zero(),
+            ChannelRatios::TwoBrightestChannels { secondary } => match secondary {
+                SecondaryColour::Yellow => sixths(1),
+                SecondaryColour::Cyan => sixths(3),
+                SecondaryColour::Magenta => sixths(5),
+            },
+            ChannelRatios::OneBrightestChannel {
+                primary,
+                direction_blend,
+            } => {
+                let position = sixths(2 * index(primary));
+                match direction_blend {
+                    None => position,
+                    Some((direction, blend)) if index(direction) == (index(primary) + 1) % 3 => {
+                        position + blend
+                    }
+                    Some((_, blend)) if position.is_zero() => sixths(6) - blend,
+                    Some((_, blend)) => position - blend,
+                }
+            }
+        };
+        (hue_sixths / T::from(6), tint, blackness)
+    }
+
+    /// Constructs an [`SHT`] from the hue, whiteness and blackness of the HWB
+    /// colour model, exactly and without converting through RGB.
+    ///
+    /// This is the inverse of [`to_hwb_exact`], which describes how the
+    /// components relate. As in [`HexRGB::from_hwb`], the hue is measured in
+    /// turns and only its fractional part is used, and if the whiteness and
+    /// blackness add to 1 or more, they are scaled down to make a grey.
+    ///
+    /// # Arguments
+    /// * `hue` - The hue, as a fraction of a turn starting from red.
+    /// * `whiteness` - The amount of white mixed into the colour.
+    /// * `blackness` - The amount of black mixed into the colour.
+    ///
+    /// # Example
+    /// ```
+    /// # #[cfg(feature = "parse")]
+    /// # {
+    /// use sht_colour::{Ratio, SHT};
+    ///
+    /// let colour = <SHT<u32>>::from_hwb(Ratio::new(1, 12), Ratio::new(1, 4), Ratio::new(1, 4));
+    ///
+    /// assert_eq!(colour, "8r6g3".parse().unwrap());
+    /// # }
+    /// ```
+    ///
+    /// [`to_hwb_exact`]: SHT::to_hwb_exact
+    /// [`HexRGB::from_hwb`]: rgb::HexRGB::from_hwb
+    pub fn from_hwb(hue: Ratio<T>, whiteness: Ratio<T>, blackness: Ratio<T>) -> SHT<T>
+    where
+        T: From<u8>,
+    {
+        let total = whiteness.clone() + blackness;
+        if total >= Ratio::one() {
+            let tint = whiteness / total;
+            // greys are given a full shade unless black
+            let shade = if tint.is_zero() {
+                Ratio::zero()
+            } else {
+                Ratio::one()
+            };
+            return SHT {
+                channel_ratios: ChannelRatios::ThreeBrightestChannels,
+                shade,
+                tint,
+            };
+        }
+        let shade = (<Ratio<T>>::one() - total) / (<Ratio<T>>::one() - whiteness.clone());
+
+        let sixths = hue.fract() * T::from(6);
+        let blend = sixths.fract();
+        let sector = (0..6_u8)
+            .find(|&n| sixths.to_integer() == T::from(n))
+            .expect("Fractional part of hue should be less than one!");
+        let primary = |n: u8| PRIMARIES[usize::from(n / 2 % 3)];
+        let channel_ratios = if blend.is_zero() {
+            if sector % 2 == 0 {
+                ChannelRatios::OneBrightestChannel {
+                    primary: primary(sector),
+                    direction_blend: None,
+                }
+            } else {
+                ChannelRatios::TwoBrightestChannels {
+                    secondary: SecondaryColour::from_primaries(
+                        primary(sector - 1),
+                        primary(sector + 1),
+                    ),
+                }
+            }
+        } else if sector % 2 == 0 {
+            // between a primary and the following secondary
+            ChannelRatios::OneBrightestChannel {
+                primary: primary(sector),
+                direction_blend: Some((primary(sector + 2), blend)),
+            }
+        } else {
+            // between a secondary and the following primary
+            ChannelRatios::OneBrightestChannel {
+                primary: primary(sector + 1),
+                direction_blend: Some((primary(sector - 1), <Ratio<T>>::one() - blend)),
+            }
+        };
+        SHT {
+            channel_ratios,
+            shade,
+            tint: whiteness,
+        }
+    }
+
+    /// Converts an [`SHT`] to the hue, saturation and lightness of the HSL
+    /// colour model, exactly and without converting through RGB.
+    ///
+    /// The hue is the same as in [`to_hwb_exact`], measured in turns starting
+    /// from red, so the secondary colours yellow, cyan and magenta have hues of
+    /// 1/6, 1/2 and 5/6 (60°, 180° and 300°). Greys have a hue and saturation
+    /// of 0. The lightness is the mean of the brightest and dimmest channels,
+    /// and the saturation is their difference relative to the largest
+    /// difference possible at that lightness.
+    ///
+    /// # Example
+    /// ```
+    /// # #[cfg(feature = "parse")]
+    /// # {
+    /// use sht_colour::{Ratio, SHT};
+    ///
+    /// let colour = "8r6g3".parse::<SHT<u32>>().unwrap();
+    ///
+    /// assert_eq!(
+    ///     colour.to_hsl(),
+    ///     (Ratio::new(1, 12), Ratio::new(1, 2), Ratio::new(1, 2))
+    /// );
+    /// # }
+    /// ```
+    ///
+    /// [`to_hwb_exact`]: SHT::to_hwb_exact
+    pub fn to_hsl(&self) -> (Ratio<T>, Ratio<T>, Ratio<T>)
+    where
+        T: From<u8>,
+    {
+        let (hue, minimum, blackness) = self.to_hwb_exact();
+        let maximum = <Ratio<T>>::one() - blackness;
+        let two = || Ratio::from_integer(T::from(2));
+        let sum = maximum.clone() + minimum.clone();
+        let lightness = sum.clone() / two();
+        let saturation = if maximum == minimum {
+            Ratio::zero()
+        } else if sum > Ratio::one() {
+            (maximum - minimum) / (two() - sum)
+        } else {
+            (maximum - minimum) / sum
+        };
+        (hue, saturation, lightness)
+    }
+
+    /// Constructs an [`SHT`] from the hue, saturation and lightness of the HSL
+    /// colour model, exactly and without converting through RGB.
+    ///
+    /// This is the inverse of [`to_hsl`]. The hue is measured in turns and
+    /// only its fractional part is used, as in [`from_hwb`]. A saturation or
+    /// lightness greater than 1 is treated as 1.
+    ///
+    /// # Arguments
+    /// * `hue` - The hue, as a fraction of a turn starting from red.
+    /// * `saturation` - How colourful the colour is, from 0 for grey to 1.
+    /// * `lightness` - How light the colour is, from 0 for black to 1 for
+    ///   white.
+    ///
+    /// # Example
+    /// ```
+    /// # #[cfg(feature = "parse")]
+    /// # {
+    /// use sht_colour::{Ratio, SHT};
+    ///
+    /// let colour = <SHT<u32>>::from_hsl(Ratio::new(1, 2), Ratio::new(1, 1), Ratio::new(1, 4));
+    ///
+    /// assert_eq!(colour, "6c".parse().unwrap());
+    /// # }
+    /// ```
+    ///
+    /// [`to_hsl`]: SHT::to_hsl
+    /// [`from_hwb`]: SHT::from_hwb
+    pub fn from_hsl(hue: Ratio<T>, saturation: Ratio<T>, lightness: Ratio<T>) -> SHT<T>
+    where
+        T: From<u8>,
+    {
+        let (saturation, lightness) = (clamp_unit(saturation), clamp_unit(lightness));
+        let room = (<Ratio<T>>::one() - lightness.clone()).min(lightness.clone());
+        let spread = saturation * room;
+        let maximum = lightness.clone() + spread.clone();
+        let minimum = lightness - spread;
+        SHT::from_hwb(hue, minimum, <Ratio<T>>::one() - maximum)
+    }
+
+    /// Generates colours with evenly spaced hues all the way around the colour
+    /// wheel, at a constant shade and tint, for a rainbow of consistent
+    /// brightness.
+    ///
+    /// The hues are spaced evenly in the hue of the HSL and HWB colour models,
+    /// starting from red and moving towards yellow, so the hue of the `i`th
+    /// colour is `i / steps` of a turn. With 6 steps these are the primary and
+    /// secondary colours, and with 12 steps each primary and secondary colour
+    /// is followed by a blend of 6 towards the next primary.
+    ///
+    /// Each colour is rounded to `precision` digits and validated, so if a
+    /// blend rounds to 0 or 1 then it becomes the nearest primary or
+    /// secondary colour. If the shade is 0 or the tint is 1, every colour is
+    /// the same grey.
+    ///
+    /// # Arguments
+    /// * `steps` - How many colours to generate.
+    /// * `shade` - The shade of every colour.
+    /// * `tint` - The tint of every colour.
+    /// * `precision` - How many base-12 digits to round each component to.
+    ///
+    /// # Example
+    /// ```
+    /// use sht_colour::{Ratio, SHT};
+    ///
+    /// let sweep = SHT::<u32>::hue_sweep(6, Ratio::new(8, 12), Ratio::new(3, 12), 1);
+    /// let codes = sweep.iter().map(|code| code.to_string()).collect::<Vec<_>>();
+    ///
+    /// assert_eq!(codes, ["8r3", "8y3", "8g3", "8c3", "8b3", "8m3"]);
+    /// ```
+    ///
+    /// # Panics
+    /// **Panics on overflow!** Will also panic if `steps` does not fit in `T`.
+    pub fn hue_sweep(steps: usize, shade: Ratio<T>, tint: Ratio<T>, precision: usize) -> Vec<SHT<T>>
+    where
+        T: CheckedMul + From<u8> + FromPrimitive,
+    {
+        let to_integer = |n: usize| T::from_usize(n).expect("Number of steps should fit in type");
+        let whiteness = tint.clone();
+        let blackness = (<Ratio<T>>::one() - shade) * (<Ratio<T>>::one() - tint);
+        (0..steps)
+            .map(|step| {
+                let hue = Ratio::new(to_integer(step), to_integer(steps));
+                SHT::from_hwb(hue, whiteness.clone(), blackness.clone()).rounded(precision)
+            })
+            .collect()
+    }
+
+    /// Formats an [`SHT`] with enough precision that parsing the resulting
+    /// string gives back exactly the same colour.
+    ///
+    /// The [`Display` implementation] rounds to 2 digits unless told
+    /// otherwise, which silently loses information for more precise colours.
+    /// This method instead uses the smallest precision that represents every
+    /// component exactly.
+    ///
+    /// Returns `None` if a component has no finite base-12 expansion (for
+    /// instance a shade of 1/5), since no string could represent the colour
+    /// exactly.
+    ///
+    /// # Example
+    /// ```
+    /// # #[cfg(feature = "parse")]
+    /// # {
+    /// use sht_colour::SHT;
+    ///
+    /// let colour = "5EX4r39Xb0E1".parse::<SHT<u64>>().unwrap();
+    ///
+    /// // Default formatting loses precision
+    /// assert_ne!(colour.to_string().parse::<SHT<u64>>().unwrap(), colour);
+    ///
+    /// let string = colour.to_roundtrip_string().unwrap();
+    /// assert_eq!(string, "5EX4r39Xb0E1");
+    /// assert_eq!(string.parse::<SHT<u64>>().unwrap(), colour);
+    /// # }
+    /// ```
+    ///
+    /// [`Display` implementation]: SHT#impl-Display
+    pub fn to_roundtrip_string(&self) -> Option<String>
+    where
+        T: TryInto<usize> + Display,
+        u8: Into<T>,
+    {
+        let precision = self.exact_precision()?.max(1);
+        Some(format!("{:.*}", precision, self))
+    }
+
+    /// Finds the fewest base-12 digits needed to write every numeric
+    /// component of an [`SHT`] exactly, or `None` if a component has no
+    /// finite base-12 expansion.
+    pub(crate) fn exact_precision(&self) -> Option<usize>
+    where
+        u8: Into<T>,
+    {
+        self.quantities().iter().try_fold(0, |precision, ratio| {
+            Some(precision.max(min_precision(ratio)?))
+        })
+    }
+
+    /// Counts the base-12 digits needed to write every numeric component of
+    /// an [`SHT`] exactly, summed across shade, tint and blend.
+    ///
+    /// Components equal to 0 or 1 need no digits, since they are either
+    /// omitted from the code or written as a single `'0'` or `'W'`.
+    ///
+    /// Returns `None` if a component has no finite base-12 expansion.
+    ///
+    /// # Example
+    /// ```
+    /// # #[cfg(feature = "parse")]
+    /// # {
+    /// use sht_colour::SHT;
+    ///
+    /// let red = "r".parse::<SHT<u32>>().unwrap();
+    /// let precise = "5EXr39Xb0E1".parse::<SHT<u32>>().unwrap();
+    ///
+    /// assert_eq!(red.total_digit_count(), Some(0));
+    /// assert_eq!(precise.total_digit_count(), Some(9));
+    /// # }
+    /// ```
+    pub fn total_digit_count(&self) -> Option<usize>
+    where
+        u8: Into<T>,
+    {
+        self.quantities().iter().try_fold(0_usize, |total, ratio| {
+            total.checked_add(min_precision(ratio)?)
+        })
+    }
+
+    /// Checks whether an [`SHT`] is a single-digit refinement of another,
+    /// coarser `SHT`.
+    ///
+    /// Each numeric component is considered as the digits it is written with
+    /// in the shortest exact code, where a shade of 1 or a tint of 0 is
+    /// omitted and so has no digits. The colour is a single-digit refinement
+    /// if it has the same primary, secondary or direction as `coarser`, and
+    /// exactly one of its components is written with the digits of the same
+    /// component of `coarser` followed by one more digit. For example, `"1r"`
+    /// and `"r4"` are both refinements of `"r"`, and `"14r"` is a refinement
+    /// of `"1r"`.
+    ///
+    /// Adding a blend also adds a direction, so `"r6g"` is not a refinement of
+    /// `"r"`. Black (`"0"`) and white (`"W"`) have no refinements, and a
+    /// colour is never a refinement of itself.
+    ///
+    /// # Example
+    /// ```
+    /// # #[cfg(feature = "parse")]
+    /// # {
+    /// use sht_colour::SHT;
+    ///
+    /// let red = "r".parse::<SHT<u32>>().unwrap();
+    ///
+    /// assert!("1r".parse::<SHT<u32>>().unwrap().is_refinement_of(&red));
+    /// assert!(!"8b".parse::<SHT<u32>>().unwrap().is_refinement_of(&red));
+    /// # }
+    /// ```
+    pub fn is_refinement_of(&self, coarser: &SHT<T>) -> bool
+    where
+        T: TryInto<usize>,
+        u8: Into<T>,
+    {
+        if !self.channel_ratios.same_structure(&coarser.channel_ratios) {
+            return false;
+        }
+
+        // omitted components: shade defaults to 1, tint to 0, and blend is
+        // never omitted when present, so never equals its placeholder 0
+        let defaults = [Ratio::one(), Ratio::zero(), Ratio::zero()];
+        let mut refined_components = 0;
+        for ((fine, coarse), default) in self
+            .quantities()
+            .iter()
+            .zip(coarser.quantities().iter())
+            .zip(defaults.iter())
+        {
+            if fine == coarse {
+                continue;
+            }
+            match (
+                refinable_digits(fine, default),
+                refinable_digits(coarse, default),
+            ) {
+                (Some(fine_digits), Some(coarse_digits))
+                    if fine_digits.len() == coarse_digits.len() + 1
+                        && fine_digits.starts_with(&coarse_digits) =>
+                {
+                    refined_components += 1;
+                }
+                _ => return false,
+            }
+        }
+        refined_components == 1
+    }
+
+    /// Lists the numeric components of an [`SHT`]: the shade, the tint, and
+    /// the blend if there is one.
+    fn quantities(&self) -> Vec<Ratio<T>> {
+        let (channel_ratios, shade, tint) = self.clone().components();
+        let mut quantities = vec![shade, tint];
+        if let ChannelRatios::OneBrightestChannel {
+            direction_blend: Some((_, blend)),
+            ..
+        } = channel_ratios
+        {
+            quantities.push(blend);
+        }
+        quantities
+    }
+
+    /// Rounds each component of an [`SHT`] to the precision used for codes
+    /// in the spec, two base-12 digits, discarding detail that would not be
+    /// shown when the colour is displayed.
+    ///
+    /// If a component rounds onto the edge of its range, the colour collapses
+    /// to the equivalent simpler code: a blend that rounds to 0 is dropped, a
+    /// blend that rounds to 1 becomes a secondary colour, and a shade of 0 or
+    /// tint of 1 becomes a grey.
+    ///
+    /// # Example
+    /// ```
+    /// # #[cfg(feature = "parse")]
+    /// # {
+    /// use sht_colour::SHT;
+    ///
+    /// let colour = "5EX4r39Xb0E1".parse::<SHT<u32>>().unwrap();
+    /// let rounded = "6r3Xb0E".parse::<SHT<u32>>().unwrap();
+    ///
+    /// assert_eq!(colour.to_spec_precision(), rounded);
+    /// # }
+    /// ```
+    ///
+    /// # Panics
+    /// **Panics on overflow!**
+    pub fn to_spec_precision(self) -> SHT<T>
+    where
+        T: CheckedMul + From<u8>,
+    {
+        self.rounded(DEFAULT_PRECISION)
+    }
+
+    /// Rounds each numeric component of an [`SHT`] to `precision` base-12
+    /// digits, replacing components that round onto the edges of their ranges
+    /// with the equivalent simpler code.
+    ///
+    /// # Panics
+    /// **Panics on overflow!**
+    fn rounded(self, precision: usize) -> SHT<T>
+    where
+        T: CheckedMul + From<u8>,
+    {
+        self.rounded_with(precision, RoundingMode::HalfUp)
+    }
+
+    /// Rounds each numeric component of an [`SHT`] to `precision` base-12
+    /// digits with the chosen [`RoundingMode`], as in [`rounded`].
+    ///
+    /// # Panics
+    /// **Panics on overflow!**
+    ///
+    /// [`rounded`]: SHT::rounded
+    fn rounded_with(self, precision: usize, mode: RoundingMode) -> SHT<T>
+    where
+        T: CheckedMul + From<u8>,
+    {
+        let round = |ratio: Ratio<T>| {
+            round_denominator_with::<T>(ratio, 12.into(), precision, <_>::zero(), mode)
+                .expect("Overflow calculating denominator")
+        };
+
+        let (channel_ratios, shade, tint) = self.components();
+        let channel_ratios = match channel_ratios {
+            ChannelRatios::OneBrightestChannel {
+                primary,
+                direction_blend,
+            } => ChannelRatios::OneBrightestChannel {
+                primary,
+                direction_blend: direction_blend
+                    .map(|(direction, blend)| (direction, round(blend))),
+            },
+            other => other,
+        };
+        SHT::collapse(channel_ratios, round(shade), round(tint))
+            .expect("Rounding a valid SHT should only create valid codes!")
+    }
+
+    /// Encodes an [`SHT`] value as four bytes, for compact storage.
+    ///
+    /// The code is first rounded to two base-12 digits per component, as in
+    /// [`to_spec_precision`], so this is lossy for more precise codes. Each
+    /// number is then stored as its numerator over 144. The layout is:
+    /// * Byte 0 - The structure of the code. The high four bits are 0 for a
+    ///   grey, 1 for a primary colour, 2 for a primary colour with a direction
+    ///   and blend, and 3 for a secondary colour. For a primary colour, the
+    ///   next two bits are the primary, counting red, green and blue from 0.
+    ///   The lowest two bits are the direction for a primary colour with a
+    ///   blend, or the secondary colour, counting cyan, yellow and magenta
+    ///   from 0.
+    /// * Byte 1 - The shade.
+    /// * Byte 2 - The tint.
+    /// * Byte 3 - The blend, or 0 if there is none.
+    ///
+    /// # Example
+    /// ```
+    /// # #[cfg(feature = "parse")]
+    /// # {
+    /// use sht_colour::SHT;
+    ///
+    /// let colour = "8r6g3".parse::<SHT<u32>>().unwrap();
+    ///
+    /// assert_eq!(colour.to_bytes(), [0x21, 96, 36, 72]);
+    /// assert_eq!(SHT::from_bytes(colour.to_bytes()), Some(colour));
+    /// # }
+    /// ```
+    ///
+    /// # Panics
+    /// **Panics on overflow!**
+    ///
+    /// [`to_spec_precision`]: SHT::to_spec_precision
+    pub fn to_bytes(self) -> [u8; 4]
+    where
+        T: CheckedMul + From<u8> + ToPrimitive,
+    {
+        let byte = |ratio: Ratio<T>| {
+            (ratio * T::from(144))
+                .to_integer()
+                .to_u8()
+                .expect("A rounded ratio on the unit interval should fit in a byte")
+        };
+        let (channel_ratios, shade, tint) = self.to_spec_precision().components();
+        let (structure, blend) = encode_structure(channel_ratios);
+        [structure, byte(shade), byte(tint), byte(blend)]
+    }
+
+    /// Decodes an [`SHT`] value from the four bytes written by [`to_bytes`].
+    ///
+    /// Returns `None` if the bytes do not describe a valid code, for instance
+    /// if the structure byte is unrecognised or a number is larger than 144.
+    ///
+    /// [`to_bytes`]: SHT::to_bytes
+    pub fn from_bytes([structure, shade, tint, blend]: [u8; 4]) -> Option<SHT<T>>
+    where
+        T: From<u8>,
+    {
+        let ratio = |byte: u8| Ratio::new(byte.into(), 144.into());
+        let channel_ratios = decode_structure(structure, ratio(blend))?;
+        SHT::new(channel_ratios, ratio(shade), ratio(tint)).ok()
+    }
+
+    /// Encodes an [`SHT`] value losslessly as a single integer, such as for a
+    /// compact key that is cheap to compare and hash.
+    ///
+    /// Every number in the code must be exactly a multiple of `1/12^precision`,
+    /// that is, it must be written with at most `precision` base-12 digits.
+    /// Each number is stored as its numerator over `12^precision`, in a field
+    /// of `w` bits, the fewest that can hold `12^precision`. The fields are
+    /// packed from the most significant bit down, with unused high bits left
+    /// as 0:
+    /// * The structure of the code, in 8 bits, exactly as in byte 0 of
+    ///   [`to_bytes`].
+    /// * The shade, in `w` bits.
+    /// * The tint, in `w` bits.
+    /// * The blend, or 0 if there is none, in `w` bits.
+    ///
+    /// So the packed value takes `8 + 3w` bits. By precision, `w` and the
+    /// total are:
+    ///
+    /// | `precision` | `w` | Total bits |
+    /// |-------------|-----|------------|
+    /// | 0           | 1   | 11         |
+    /// | 1           | 4   | 20         |
+    /// | 2           | 8   | 32         |
+    /// | 3           | 11  | 41         |
+    /// | 4           | 15  | 53         |
+    /// | 5           | 18  | 62         |
+    ///
+    /// Higher precisions do not fit in 64 bits. At precision 2, the packed
+    /// value is the four bytes of [`to_bytes`] read as a big-endian integer.
+    ///
+    /// Returns `None` if the code is too precise to be written with
+    /// `precision` digits, or if `precision` is greater than 5.
+    ///
+    /// # Example
+    /// ```
+    /// # #[cfg(feature = "parse")]
+    /// # {
+    /// use sht_colour::SHT;
+    ///
+    /// let colour = "8r6g3".parse::<SHT<u32>>().unwrap();
+    ///
+    /// assert_eq!(colour.to_packed(1), Some(0x21836));
+    /// assert_eq!(SHT::from_packed(0x21836, 1), Some(colour));
+    /// assert_eq!("8r6g31".parse::<SHT<u32>>().unwrap().to_packed(1), None);
+    /// # }
+    /// ```
+    ///
+    /// [`to_bytes`]: SHT::to_bytes
+    pub fn to_packed(&self, precision: usize) -> Option<u64>
+    where
+        T: ToPrimitive,
+    {
+        let (denominator, width) = packed_field(precision)?;
+        let field = |ratio: &Ratio<T>| {
+            let (numerator, ratio_denominator) = (ratio.numer().to_u64()?, ratio.denom().to_u64()?);
+            if denominator % ratio_denominator == 0 {
+                Some(numerator * (denominator / ratio_denominator))
+            } else {
+                None
+            }
+        };
+        let (channel_ratios, shade, tint) = self.clone().components();
+        let (structure, blend) = encode_structure(channel_ratios);
+        [shade, tint, blend]
+            .iter()
+            .try_fold(u64::from(structure), |packed, ratio| {
+                Some(packed << width | field(ratio)?)
+            })
+    }
+
+    /// Decodes an [`SHT`] value from an integer written by [`to_packed`] with
+    /// the same `precision`.
+    ///
+    /// Returns `None` if the integer does not describe a valid code, for
+    /// instance if a number is larger than `12^precision`, or if `precision`
+    /// is greater than 5.
+    ///
+    /// [`to_packed`]: SHT::to_packed
+    pub fn from_packed(packed: u64, precision: usize) -> Option<SHT<T>>
+    where
+        T: FromPrimitive,
+    {
+        let (denominator, width) = packed_field(precision)?;
+        if packed >> (3 * width + 8) != 0 {
+            return None;
+        }
+        let field = |index: u32| {
+            let numerator = packed >> (width * (2 - index)) & ((1 << width) - 1);
+            if numerator > denominator {
+                return None;
+            }
+            Some(Ratio::new(
+                T::from_u64(numerator)?,
+                T::from_u64(denominator)?,
+            ))
+        };
+        let structure = (packed >> (3 * width)) as u8;
+        let channel_ratios = decode_structure(structure, field(2)?)?;
+        SHT::new(channel_ratios, field(0)?, field(1)?).ok()
+    }
+
+    /// Constructs an [`SHT`] from components that may be invalid, by nudging
+    /// each invalid component to the nearest valid value.
+    ///
+    /// Unlike [`SHT::new`], this never fails, which suits code that does
+    /// arithmetic on components. Each kind of [`SHTValueError`] is corrected
+    /// as follows:
+    /// * [`ValueOutOfBounds`] - A shade, tint or blend greater than 1 is
+    ///   reduced to 1, and may then be corrected by the rules below.
+    /// * [`DirectionEqualsPrimary`] - The direction and blend are dropped,
+    ///   since blending a primary colour towards itself leaves it unchanged.
+    /// * [`BlendZero`] - The direction and blend are dropped, leaving just the
+    ///   primary colour.
+    /// * [`BlendOne`] - The primary colour and direction are replaced by the
+    ///   secondary colour they make together.
+    /// * [`PrimaryShadeZero`] and [`SecondaryShadeZero`] - The colour has no
+    ///   hue, so it becomes the grey whose tint is its tint, or black if that
+    ///   is 0.
+    /// * [`PrimaryTintOne`] and [`SecondaryTintOne`] - The colour becomes
+    ///   white.
+    ///
+    /// Valid components are returned unchanged, as if by [`SHT::new`].
+    ///
+    /// # Example
+    /// ```
+    /// # #[cfg(feature = "parse")]
+    /// # {
+    /// use sht_colour::{ChannelRatios::OneBrightestChannel, ColourChannel, Ratio, SHT};
+    ///
+    /// let red_to_green = OneBrightestChannel {
+    ///     primary: ColourChannel::Red,
+    ///     direction_blend: Some((ColourChannel::Green, Ratio::from_integer(1))),
+    /// };
+    /// let colour = SHT::<u32>::coerce_valid(red_to_green, Ratio::new(3, 2), Ratio::new(1, 4));
+    ///
+    /// assert_eq!(colour, "y3".parse().unwrap());
+    /// # }
+    /// ```
+    ///
+    /// [`ValueOutOfBounds`]: SHTValueError::ValueOutOfBounds
+    /// [`DirectionEqualsPrimary`]: SHTValueError::DirectionEqualsPrimary
+    /// [`BlendZero`]: SHTValueError::BlendZero
+    /// [`BlendOne`]: SHTValueError::BlendOne
+    /// [`PrimaryShadeZero`]: SHTValueError::PrimaryShadeZero
+    /// [`SecondaryShadeZero`]: SHTValueError::SecondaryShadeZero
+    /// [`PrimaryTintOne`]: SHTValueError::PrimaryTintOne
+    /// [`SecondaryTintOne`]: SHTValueError::SecondaryTintOne
+    pub fn coerce_valid(
+        channel_ratios: ChannelRatios<T>,
+        shade: Ratio<T>,
+        tint: Ratio<T>,
+    ) -> SHT<T> {
+        let channel_ratios = match channel_ratios {
+            ChannelRatios::OneBrightestChannel {
+                primary,
+                direction_blend: Some((direction, blend)),
+            } => ChannelRatios::OneBrightestChannel {
+                primary,
+                direction_blend: (direction != primary).then(|| (direction, clamp_unit(blend))),
+            },
+            other => other,
+        };
+        SHT::collapse(channel_ratios, clamp_unit(shade), clamp_unit(tint))
+            .expect("Coerced components should always be valid")
+    }
+
+    /// Constructs an [`SHT`] from components that may lie on the edges of
+    /// their ranges, for instance after rounding, by replacing them with the
+    /// equivalent simpler code.
+    ///
+    /// # Errors
+    /// Will return `Err` if the components are invalid for reasons other than
+    /// lying on the edges of their ranges, such as being out of bounds.
+    pub(crate) fn collapse(
+        channel_ratios: ChannelRatios<T>,
+        shade: Ratio<T>,
+        tint: Ratio<T>,
+    ) -> Result<Self, Vec<SHTValueError>> {
+        let channel_ratios = match channel_ratios {
+            ChannelRatios::OneBrightestChannel {
+                primary,
+                direction_blend: Some((direction, blend)),
+            } if direction != primary && (blend.is_zero() || blend.is_one()) => {
+                if blend.is_zero() {
+                    ChannelRatios::OneBrightestChannel {
+                        primary,
+                        direction_blend: None,
+                    }
+                } else {
+                    ChannelRatios::TwoBrightestChannels {
+                        secondary: SecondaryColour::from_primaries(primary, direction),
+                    }
+                }
+            }
+            other => other,
+        };
+        let has_hue = !matches!(channel_ratios, ChannelRatios::ThreeBrightestChannels);
+        if has_hue && (shade.is_zero() || tint.is_one()) {
+            // only grey remains, and greys are given a full shade unless black
+            let shade = if tint.is_zero() {
+                Ratio::zero()
+            } else {
+                Ratio::one()
+            };
+            SHT::new(ChannelRatios::ThreeBrightestChannels, shade, tint)
+        } else {
+            SHT::new(channel_ratios, shade, tint)
+        }
+    }
+
+    /// Enumerates every canonical [`SHT`] whose shade, tint and blend can be
+    /// written with `precision` base-12 digits, each exactly once.
+    ///
+    /// Greys come first, from black to white, followed by the colours with
+    /// hues: primary colours without a blend, then secondary colours, then
+    /// primary colours with a blend. Only valid combinations are produced, so
+    /// no colour with a hue has a shade of 0 or a tint of 1, and no blend is 0
+    /// or 1. Each grey appears once, with the canonical shade of the spec.
+    ///
+    /// With `n = 12^precision`, there are `6 * n^3 + n + 1` codes, as counted
+    /// by [`sht_code_count`], which explains the formula. That is 10381 codes
+    /// for a `precision` of 1.
+    ///
+    /// # Example
+    /// ```
+    /// # #[cfg(feature = "parse")]
+    /// # {
+    /// use sht_colour::SHT;
+    ///
+    /// let mut codes = SHT::<u32>::all_codes(1);
+    ///
+    /// assert_eq!(codes.next(), Some("0".parse().unwrap()));
+    /// assert_eq!(codes.next(), Some("1".parse().unwrap()));
+    /// assert_eq!(SHT::<u32>::all_codes(1).count(), 10381);
+    /// # }
+    /// ```
+    ///
+    /// # Panics
+    /// Will panic if `12^precision` overflows `T`.
+    pub fn all_codes(precision: usize) -> impl Iterator<Item = SHT<T>>
+    where
+        T: From<u8> + CheckedMul,
+    {
+        let denominator = safe_denominator(T::from(12), precision)
+            .expect("Denominator should not overflow the integer type!");
+
+        let greys = fractions(denominator.clone(), true, true).map(|tint| {
+            // greys are given a full shade unless black
+            let shade = if tint.is_zero() {
+                Ratio::zero()
+            } else {
+                Ratio::one()
+            };
+            SHT {
+                channel_ratios: ChannelRatios::ThreeBrightestChannels,
+                shade,
+                tint,
+            }
+        });
+
+        let blend_denominator = denominator.clone();
+        let blended = PRIMARIES.iter().flat_map(move |&primary| {
+            let blend_denominator = blend_denominator.clone();
+            PRIMARIES
+                .iter()
+                .filter(move |&&direction| direction != primary)
+                .flat_map(move |&direction| {
+                    fractions(blend_denominator.clone(), false, false).map(move |blend| {
+                        ChannelRatios::OneBrightestChannel {
+                            primary,
+                            direction_blend: Some((direction, blend)),
+                        }
+                    })
+                })
+        });
+        let hues = PRIMARIES
+            .iter()
+            .map(|&primary| ChannelRatios::OneBrightestChannel {
+                primary,
+                direction_blend: None,
+            })
+            .chain(
+                SECONDARIES
+                    .iter()
+                    .map(|&secondary| ChannelRatios::TwoBrightestChannels { secondary }),
+            )
+            .chain(blended);
+
+        let coloured = hues.flat_map(move |channel_ratios| {
+            let tint_denominator = denominator.clone();
+            fractions(denominator.clone(), false, true).flat_map(move |shade| {
+                let channel_ratios = channel_ratios.clone();
+                fractions(tint_denominator.clone(), true, false).map(move |tint| SHT {
+                    channel_ratios: channel_ratios.clone(),
+                    shade: shade.clone(),
+                    tint,
+                })
+            })
+        });
+        greys.chain(coloured)
+    }
+}
+
+/// Parses an [`SHT`] from a slice of bytes, such as data read from a socket,
+/// without copying it into a [`String`].
+///
+/// Every byte must be an ASCII character, since SHT codes are written only in
+/// ASCII. The bytes are then parsed exactly as by the [`FromStr`
+/// implementation].
+///
+/// # Example
+/// ```
+/// use sht_colour::sht::{parse_bytes, SHT};
+///
+/// let colour = parse_bytes::<u8>(b"8r6g3").unwrap();
+///
+/// assert_eq!(colour, "8r6g3".parse::<SHT<u8>>().unwrap());
+/// ```
+///
+/// # Errors
+/// Will return [`ParsePropertyError::NonAscii`] if any byte is not ASCII, and
+/// otherwise will return `Err` in the same way as [`SHT::from_str`].
+///
+/// [`FromStr` implementation]: SHT#impl-FromStr
+#[cfg(feature = "parse")]
+pub fn parse_bytes<T>(input: &[u8]) -> Result<SHT<T>, ParsePropertyError>
+where
+    T: Clone + Integer + Unsigned + FromStr + CheckedMul + CheckedAdd + CheckedDiv,
+    u8: Into<T>,
+{
+    if !input.is_ascii() {
+        return Err(ParsePropertyError::NonAscii);
+    }
+    std::str::from_utf8(input)
+        .expect("ASCII should be valid UTF-8")
+        .parse()
+}
+
+/// Arranges the brightest and dimmest channel values of a colour into red,
+/// green and blue, according to its [`ChannelRatios`].
+///
+/// `centremost` is the value of the channel between the two, used only for a
+/// colour with a direction.
+fn place_channels<T: Clone + Integer + Unsigned>(
+    channel_ratios: ChannelRatios<T>,
+    max: Ratio<T>,
+    min: Ratio<T>,
+    centremost: Option<Ratio<T>>,
+) -> (Ratio<T>, Ratio<T>, Ratio<T>) {
+    match channel_ratios {
+        ChannelRatios::ThreeBrightestChannels => (min.clone(), min.clone(), min),
+        ChannelRatios::TwoBrightestChannels { secondary } => match secondary {
+            SecondaryColour::Cyan => (min, max.clone(), max),
+            SecondaryColour::Yellow => (max.clone(), max, min),
+            SecondaryColour::Magenta => (max.clone(), min, max),
+        },
+        ChannelRatios::OneBrightestChannel {
+            primary,
+            direction_blend,
+        } => {
+            let (mut red, mut green, mut blue) = (min.clone(), min.clone(), min);
+            if let (Some((direction, _)), Some(centremost_channel)) = (direction_blend, centremost)
+            {
+                match direction {
+                    ColourChannel::Red => red = centremost_channel,
+                    ColourChannel::Green => green = centremost_channel,
+                    ColourChannel::Blue => blue = centremost_channel,
+                }
+            };
+            match primary {
+                ColourChannel::Red => red = max,
+                ColourChannel::Green => green = max,
+                ColourChannel::Blue => blue = max,
+            };
+            (red, green, blue)
+        }
+    }
+}
+
+/// Encodes the structure of an [`SHT`] as a byte, as described in
+/// [`SHT::to_bytes`], along with its blend, or 0 if there is none.
+fn encode_structure<T>(channel_ratios: ChannelRatios<T>) -> (u8, Ratio<T>)
+where
+    T: Clone + Integer + Unsigned,
+{
+    let index = |channel| {
+        PRIMARIES
+            .iter()
+            .position(|&primary| primary == channel)
+            .expect("Every channel is a primary") as u8
+    };
+    match channel_ratios {
+        ChannelRatios::ThreeBrightestChannels => (0x00, Ratio::zero()),
+        ChannelRatios::OneBrightestChannel {
+            primary,
+            direction_blend: None,
+        } => (0x10 | index(primary) << 2, Ratio::zero()),
+        ChannelRatios::OneBrightestChannel {
+            primary,
+            direction_blend: Some((direction, blend)),
+        } => (0x20 | index(primary) << 2 | index(direction), blend),
+        ChannelRatios::TwoBrightestChannels { secondary } => {
+            let position = SECONDARIES
+                .iter()
+                .position(|&other| other == secondary)
+                .expect("Every secondary colour is listed") as u8;
+            (0x30 | position, Ratio::zero())
+        }
+    }
+}
+
+/// Decodes the structure of an [`SHT`] from a byte written by
+/// [`encode_structure`], attaching the blend if it has a direction.
+///
+/// Returns `None` if the byte is unrecognised, or if there is a nonzero blend
+/// but no direction.
+fn decode_structure<T>(structure: u8, blend: Ratio<T>) -> Option<ChannelRatios<T>>
+where
+    T: Clone + Integer + Unsigned,
+{
+    let primary = |bits: u8| PRIMARIES.get(usize::from(bits & 0b11)).copied();
+    let no_blend = blend.is_zero();
+    Some(match structure >> 4 {
+        0x0 if structure == 0x00 && no_blend => ChannelRatios::ThreeBrightestChannels,
+        0x1 if structure & 0b11 == 0 && no_blend => ChannelRatios::OneBrightestChannel {
+            primary: primary(structure >> 2)?,
+            direction_blend: None,
+        },
+        0x2 => ChannelRatios::OneBrightestChannel {
+            primary: primary(structure >> 2)?,
+            direction_blend: Some((primary(structure)?, blend)),
+        },
+        0x3 if structure & 0b1100 == 0 && no_blend => ChannelRatios::TwoBrightestChannels {
+            secondary: *SECONDARIES.get(usize::from(structure & 0b11))?,
+        },
+        _ => return None,
+    })
+}
+
+/// Finds the denominator `12^precision` of each number packed by
+/// [`SHT::to_packed`], and the width in bits of the field that holds it.
+///
+/// Returns `None` if the packed value would not fit in 64 bits.
+fn packed_field(precision: usize) -> Option<(u64, u32)> {
+    let denominator = safe_denominator(12_u64, precision).ok()?;
+    let width = u64::BITS - denominator.leading_zeros();
+    if 3 * width + 8 <= u64::BITS {
+        Some((denominator, width))
+    } else {
+        None
+    }
+}
+
+/// Rewrites an [`SHT`] code as the shortest code for the same colour.
+///
+/// Redundant trailing zeros are dropped from each component, and components
+/// with default values are left out entirely, as by
+/// [`SHT::to_roundtrip_string`]. Lowercase `'w'`, `'x'` and `'e'` are written
+/// in uppercase.
+///
+/// Components are parsed with 128-bit integers, so codes with more than 35
+/// digits in a single component are rounded.
+///
+/// # Example
+/// ```
+/// use sht_colour::sht::minify;
+///
+/// assert_eq!(minify("500r6").unwrap(), "5r6");
+/// assert_eq!(minify("5r600000").unwrap(), "5r6");
+/// assert_eq!(minify("w").unwrap(), "W");
+/// ```
+///
+/// # Errors
+/// Will return `Err` if the code could not be parsed, in the same way as
+/// [`SHT::from_str`].
+#[cfg(feature = "parse")]
+pub fn minify(s: &str) -> Result<String, ParsePropertyError> {
+    let colour = s.parse::<SHT<u128>>()?;
+    Ok(colour
+        .to_roundtrip_string()
+        .expect("Parsed codes should have finite base-12 expansions!"))
+}
+
+/// Lists the named colours of the SHT format, with their [`SHT`] values, for
+/// instance as swatches in a colour picker.
+///
+/// These are the colours whose codes are a single letter or digit. In order,
+/// they are:
+/// * The primary and secondary colours around the colour wheel: `"red"`
+///   (`r`), `"yellow"` (`y`), `"green"` (`g`), `"cyan"` (`c`), `"blue"` (`b`)
+///   and `"magenta"` (`m`).
+/// * The greys from dark to light: `"black"` (`0`), `"grey"` (`6`) and
+///   `"white"` (`W`).
+///
+/// # Example
+/// ```
+/// # #[cfg(feature = "parse")]
+/// # {
+/// use sht_colour::sht::{named_hues, SHT};
+///
+/// let hues = named_hues::<u8>();
+///
+/// assert_eq!(hues[0], ("red", "r".parse::<SHT<u8>>().unwrap()));
+/// assert_eq!(hues.len(), 9);
+/// # }
+/// ```
+pub fn named_hues<T>() -> Vec<(&'static str, SHT<T>)>
+where
+    T: Clone + Integer + Unsigned + From<u8>,
+{
+    let hue = |channel_ratios| SHT {
+        channel_ratios,
+        shade: Ratio::one(),
+        tint: Ratio::zero(),
+    };
+    let primary = |primary| hue(ChannelRatios::primary(primary));
+    let secondary = |secondary| hue(ChannelRatios::secondary(secondary));
+    let grey = |shade, tint| SHT {
+        channel_ratios: ChannelRatios::grey(),
+        shade,
+        tint,
+    };
+    vec![
+        ("red", primary(ColourChannel::Red)),
+        ("yellow", secondary(SecondaryColour::Yellow)),
+        ("green", primary(ColourChannel::Green)),
+        ("cyan", secondary(SecondaryColour::Cyan)),
+        ("blue", primary(ColourChannel::Blue)),
+        ("magenta", secondary(SecondaryColour::Magenta)),
+        ("black", grey(Ratio::zero(), Ratio::zero())),
+        ("grey", grey(Ratio::one(), Ratio::new(1.into(), 2.into()))),
+        ("white", grey(Ratio::one(), Ratio::one())),
+    ]
+}
+
+/// Counts the distinct colours that can be written as [`SHT`] codes with
+/// `precision` base-12 digits per component.
+///
+/// With `n = 12^precision`, the count is `6 * n^3 + n + 1`:
+/// * `n + 1` greys, one for each tint from `0` to `W`.
+/// * `3 * n^2` primary colours without a blend, one for each of `n` nonzero
+///   shades and `n` tints short of white.
+/// * `3 * n^2` secondary colours, with the same shades and tints.
+/// * `6 * (n - 1) * n^2` primary colours with a blend, for each of the 6
+///   pairs of primary and direction, and each of `n - 1` blends strictly
+///   between zero and one.
+///
+/// By comparison, there are `16^(3 * digits)` hex codes with `digits` digits
+/// per channel.
+///
+/// # Example
+/// ```
+/// use sht_colour::sht::sht_code_count;
+///
+/// assert_eq!(sht_code_count(0), 8);
+/// assert_eq!(sht_code_count(1), 10381);
+/// assert_eq!(sht_code_count(2), 17916049);
+/// ```
+///
+/// # Panics
+/// **Panics on overflow!**
+pub fn sht_code_count(precision: usize) -> u128 {
+    let count = || {
+        let denominator = safe_denominator(12_u128, precision).ok()?;
+        denominator
+            .checked_pow(3)?
+            .checked_mul(6)?
+            .checked_add(denominator)?
+            .checked_add(1)
+    };
+    count().expect("Code count should not overflow!")
+}
+
+/// Builds a lookup table converting every [`SHT`] code with `precision`
+/// base-12 digits per component into a [`HexRGB`] value.
+///
+/// Each hex code is rounded to two hex digits per channel, like a typical
+/// `#RRGGBB` code, exactly as by [`SHT::to_rgb`]. The table can then be
+/// serialised and embedded, so that colours can be converted without any
+/// arithmetic at runtime.
+///
+/// The table has [`sht_code_count`] entries: 10381 for `precision` 1, and
+/// 17916049 for `precision` 2. Codes appear in a fixed order, with greys first.
+///
+/// # Example
+/// ```
+/// # #[cfg(feature = "parse")]
+/// # {
+/// use sht_colour::sht::{generate_lut, SHT};
+///
+/// let table = generate_lut::<u32>(1);
+///
+/// assert_eq!(table.len(), 10381);
+/// assert_eq!(table[0], ("0".parse().unwrap(), "#000000".parse().unwrap()));
+/// # }
+/// ```
+///
+/// # Panics
+/// **Panics on overflow!**
+///
+/// [`HexRGB`]: rgb::HexRGB
+pub fn generate_lut<T>(precision: usize) -> Vec<(SHT<T>, rgb::HexRGB<T>)>
+where
+    T: Clone + Integer + Unsigned + From<u8> + CheckedMul,
+{
+    SHT::all_codes(precision)
+        .map(|code| (code.clone(), code.to_rgb(2)))
+        .collect()
+}
+
+/// Formats a ratio between 0 and 1 as base-12 digits, as they are written in
+/// an [`SHT`] code.
+///
+/// The digits are those after the point, with no `'.'` written, using `'X'`
+/// for 10 and `'E'` for 11. At most `precision` digits are written, and
+/// trailing zeros are dropped, but at least one digit is always written if
+/// `precision` is not 0. If the ratio needs more digits, the last digit is
+/// rounded to nearest, with halves rounded up. A ratio of 1 or more, including
+/// one that rounds up to 1, is written as `'W'`.
+///
+/// # Example
+/// ```
+/// use sht_colour::{sht::format_duodecimal, Ratio};
+///
+/// assert_eq!(format_duodecimal(Ratio::new(1_u32, 2), 4), "6");
+/// assert_eq!(format_duodecimal(Ratio::new(1_u32, 3), 4), "4");
+/// assert_eq!(format_duodecimal(Ratio::new(11310_u32, 20736), 2), "67");
+/// assert_eq!(format_duodecimal(Ratio::new(143_u32, 144), 1), "W");
+/// assert_eq!(format_duodecimal(Ratio::new(1_u32, 1), 4), "W");
+/// ```
+pub fn format_duodecimal<T>(ratio: Ratio<T>, precision: usize) -> String
+where
+    T: Integer + Clone,
+{
+    duodecimal(ratio, precision)
+}
+
+/// Formats a ratio between 0 and 1 as base-12 digits, as in
+/// [`format_duodecimal`], but rounding the last digit with the chosen
+/// [`RoundingMode`] instead of rounding halves up.
+///
+/// # Example
+/// ```
+/// use sht_colour::{sht::format_duodecimal_with_rounding, Ratio, RoundingMode};
+///
+/// // 0.46 in base 12, halfway between 0.4 and 0.5
+/// let ratio = Ratio::new(54_u32, 144);
+///
+/// assert_eq!(format_duodecimal_with_rounding(ratio, 1, RoundingMode::HalfUp), "5");
+/// assert_eq!(format_duodecimal_with_rounding(ratio, 1, RoundingMode::HalfEven), "4");
+/// assert_eq!(format_duodecimal_with_rounding(ratio, 1, RoundingMode::Truncate), "4");
+/// ```
+pub fn format_duodecimal_with_rounding<T>(
+    ratio: Ratio<T>,
+    precision: usize,
+    mode: RoundingMode,
+) -> String
+where
+    T: Integer + Clone,
+{
+    duodecimal_with(ratio, precision, mode)
+}
+
+/// Parses base-12 digits, as written in an [`SHT`] code, into a ratio between
+/// 0 and 1. The inverse of [`format_duodecimal`].
+///
+/// The digits are read as those after the point, with `'X'` for 10 and `'E'`
+/// for 11, or the whole string may be `'W'` for 1. If there are too many
+/// digits for the denominator to fit in `T`, the ratio is rounded to the
+/// nearest fraction with the largest power of 12 that does fit, as when
+/// parsing an `SHT` code.
+///
+/// # Example
+/// ```
+/// use sht_colour::{sht::parse_duodecimal, Ratio};
+///
+/// assert_eq!(parse_duodecimal::<u32>("6"), Ok(Ratio::new(1, 2)));
+/// assert_eq!(parse_duodecimal::<u32>("6666"), Ok(Ratio::new(11310, 20736)));
+/// assert_eq!(parse_duodecimal::<u32>("W"), Ok(Ratio::new(1, 1)));
+/// assert!(parse_duodecimal::<u32>("6A").is_err());
+/// ```
+///
+/// # Errors
+/// Will return `Err` if the string is empty or contains anything other than
+/// base-12 digits, or is not exactly `'W'`.
+#[cfg(feature = "parse")]
+pub fn parse_duodecimal<T>(s: &str) -> Result<Ratio<T>, ParsePropertyError>
+where
+    T: Clone + Integer + CheckedMul + CheckedAdd,
+    u8: Into<T>,
+{
+    parser::parse_duodecimal(s)
+}
+
+/// Parses an [`SHT`] from a string.
+///
+/// See the [`Display` implementation] for the format.
+///
+/// # Example
+/// ```
+/// use sht_colour::SHT;
+///
+/// let first_colour = "5r600000".parse::<SHT<u8>>().unwrap();
+/// let second_colour = "500r6".parse::<SHT<u8>>().unwrap();
+///
+/// assert_eq!(first_colour, second_colour);
+/// ```
+///
+/// [`Display` implementation]: SHT#impl-Display
+#[cfg(feature = "parse")]
+impl<T> FromStr for SHT<T>
+where
+    T: Clone + Integer + Unsigned + FromStr + CheckedMul + CheckedAdd + CheckedDiv,
+    u8: Into<T>,
+{
+    type Err = ParsePropertyError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
         parse_sht(s)
     }
 }
@@ -467,6 +2787,75 @@ fn round(input: &[u8], round_up: bool) -> Vec<u8> {
     }
 }
 
+/// Finds how many base-12 digits are needed to represent a ratio exactly.
+///
+/// Returns `None` if the ratio has no finite base-12 expansion, i.e. if its
+/// denominator has a prime factor other than 2 or 3.
+///
+/// # Example
+/// ```ignore
+/// use sht_colour::Ratio;
+///
+/// assert_eq!(min_precision(&Ratio::new(1_u32, 2)), Some(1));
+/// assert_eq!(min_precision(&Ratio::new(1_u32, 144)), Some(2));
+/// assert_eq!(min_precision(&Ratio::new(1_u32, 5)), None);
+/// ```
+fn min_precision<T>(ratio: &Ratio<T>) -> Option<usize>
+where
+    T: Integer + Clone,
+    u8: Into<T>,
+{
+    let base = 12.into();
+    let mut denominator = ratio.denom().clone();
+    let mut digits = 0_usize;
+    while !denominator.is_one() {
+        let common_factor = denominator.gcd(&base);
+        if common_factor.is_one() {
+            return None;
+        }
+        denominator = denominator / common_factor;
+        digits = digits.checked_add(1)?;
+    }
+    Some(digits)
+}
+
+/// Iterates over the fractions `0/denominator` to `denominator/denominator` in
+/// increasing order, optionally leaving out zero and one.
+fn fractions<T>(denominator: T, with_zero: bool, with_one: bool) -> impl Iterator<Item = Ratio<T>>
+where
+    T: Clone + Integer,
+{
+    let first = if with_zero { T::zero() } else { T::one() };
+    let last = if with_one {
+        denominator.clone()
+    } else {
+        denominator.clone() - T::one()
+    };
+    successors(Some(first), |numerator| Some(numerator.clone() + T::one()))
+        .take_while(move |numerator| numerator <= &last)
+        .map(move |numerator| Ratio::new(numerator, denominator.clone()))
+}
+
+/// Writes a component of an [`SHT`] with the base-12 digits it has in the
+/// shortest exact code, or with no digits if it equals `default` and so is
+/// omitted.
+///
+/// Returns `None` if more digits could not be added to the component, because
+/// it is written as `'0'` or `'W'`, or has no finite base-12 expansion.
+fn refinable_digits<T>(ratio: &Ratio<T>, default: &Ratio<T>) -> Option<String>
+where
+    T: TryInto<usize> + Integer + Clone,
+    u8: Into<T>,
+{
+    if ratio == default {
+        Some(String::new())
+    } else if ratio.is_zero() || ratio >= &Ratio::one() {
+        None
+    } else {
+        Some(duodecimal(ratio.clone(), min_precision(ratio)?))
+    }
+}
+
 /// Converts a ratio to a fixed-point base-12 string.
 ///
 /// Output uses 'X' to represent decimal 10, and 'E' to represent decimal digit
@@ -478,35 +2867,39 @@ fn round(input: &[u8], round_up: bool) -> Vec<u8> {
 ///
 /// assert_eq!(duodecimal(Ratio::new(11310, 20736), 2), "67");
 /// ```
-fn duodecimal<T>(mut input: Ratio<T>, precision: usize) -> String
+fn duodecimal<T>(input: Ratio<T>, precision: usize) -> String
 where
-    T: TryInto<usize> + Integer + Zero + Rem<T, Output = T> + Div<T, Output = T> + Clone,
-    u8: Into<T>,
+    T: Integer + Clone,
+{
+    duodecimal_with(input, precision, RoundingMode::HalfUp)
+}
+
+/// Converts a ratio to a fixed-point base-12 string, as in [`duodecimal`], but
+/// rounding the last digit with the chosen [`RoundingMode`].
+fn duodecimal_with<T>(input: Ratio<T>, precision: usize, mode: RoundingMode) -> String
+where
+    T: Integer + Clone,
 {
-    let half = || Ratio::new(1.into(), 2.into());
     let digit_characters = ['0', '1', '2', '3', '4', '5', '6', '7', '8', '9', 'X', 'E'];
-    let mut digits = Vec::with_capacity(precision);
     if input >= <_>::one() {
         return "W".to_owned();
     }
+    let denominator = input.denom().clone();
+    // fractional part of the input, as a numerator over `denominator`
+    let mut remainder = input.numer().clone();
+    let mut digits = Vec::with_capacity(precision);
     let mut round_up = false;
     for digits_left in (0..precision).rev() {
-        let scaled = input * Ratio::from_integer(12.into());
-        input = scaled.fract();
+        let (next_digit, next_remainder) = times_twelve(remainder, &denominator);
+        remainder = next_remainder;
         if digits_left.is_zero() {
             // round because no more digits
             // comparing remainder to 0.5
-            round_up = input >= half();
-        }
-        let integer_part = scaled.to_integer();
-        let next_digit = match integer_part.try_into() {
-            Ok(n) if n < 12 => n
-                .try_into()
-                .expect("usize < 12 could not be converted to u8"),
-            _ => 12_u8,
-        };
+            let half = remainder.cmp(&(denominator.clone() - remainder.clone()));
+            round_up = mode.rounds_up(half, next_digit % 2 == 1);
+        }
         digits.push(next_digit);
-        if input.is_zero() {
+        if remainder.is_zero() {
             break;
         }
     }
@@ -517,10 +2910,52 @@ where
         .collect()
 }
 
+/// Multiplies a fraction `numerator / denominator` by 12, returning the
+/// integer part and the numerator of the fractional part.
+///
+/// The fraction must be less than 1. The product is built up by adding the
+/// numerator twelve times, wrapping around the denominator, so no intermediate
+/// value exceeds the denominator and nothing can overflow.
+fn times_twelve<T>(numerator: T, denominator: &T) -> (u8, T)
+where
+    T: Integer + Clone,
+{
+    let mut integer_part = 0;
+    let mut accumulated = T::zero();
+    for _ in 0..12 {
+        let room = denominator.clone() - accumulated.clone();
+        if numerator >= room {
+            accumulated = numerator.clone() - room;
+            integer_part += 1;
+        } else {
+            accumulated = accumulated + numerator.clone();
+        }
+    }
+    (integer_part, accumulated)
+}
+
+/// Finds the largest precision that is meaningful when formatting an
+/// [`SHT<T>`](SHT).
+///
+/// A component stored as a [`Ratio<T>`] has a denominator below `2^bits`,
+/// where `bits` is the size of `T`. If it has a finite base-12 expansion, that
+/// expansion has at most `log_3(2^bits)` digits, because each digit can only
+/// remove a single factor of 3 from the denominator. So this many digits are
+/// always enough to write any such component exactly, and beyond it more
+/// digits only extend the expansions of components that cannot be written
+/// exactly anyway.
+fn recommended_precision<T>() -> usize {
+    let bits = 8 * size_of::<T>();
+    (bits as f64 / 3_f64.log2()).ceil() as usize
+}
+
 /// Formats the colour per the [`SHT`] format on <https://omaitzen.com/sht/spec/>:
 ///
 /// Supports an optional `precision` parameter, which determines the maximum
-/// number of digits.
+/// number of digits. Precisions beyond the number of digits needed to write
+/// any component of an `SHT<T>` exactly are capped at that number, which is
+/// 6 for `u8`, 21 for `u32` and 41 for `u64`. So formatting never overflows,
+/// however large the precision.
 ///
 /// # Format
 ///
@@ -534,7 +2969,8 @@ where
 ///
 /// Numbers are represented using one or more base-12 digits (where `'X'` and
 /// `'E'` are 10 and 11 respectively). Tint is represented by `'W'` if it is
-/// equal to 12/12, i.e. the colour is pure white.
+/// equal to 12/12, i.e. the colour is pure white. `'W'` is only valid as the
+/// whole code, never alongside other digits or colours.
 ///
 /// Primary colours are `'r'`, `'g'` or `'b'`, representing red, blue and green
 /// respectively.
@@ -542,23 +2978,101 @@ where
 /// Secondary colours are `'c'`, `'y'` or `'m'`, representing cyan, yellow and
 /// magenta respectively.
 ///
+/// If the blend is so small that it rounds to zero at the chosen precision, it
+/// is left out along with its direction, since a blend of zero is invalid.
+/// Likewise a blend that rounds to one is written as the secondary colour
+/// between the primary and the direction, a shade that rounds to zero leaves
+/// only the grey of the tint, a tint that rounds to zero is left out, and a
+/// tint that rounds to one makes the whole code `'W'`. So every code this
+/// writes can be parsed again.
+///
+/// Colours are written in lowercase, and `'X'`, `'E'` and `'W'` in uppercase.
+/// When parsing, every letter is case-insensitive, so `"w"` is also pure white
+/// and `"x"` is the same shade as `"X"`.
+///
+/// # Flags
+///
+/// * `precision` - The maximum number of digits, as above.
+/// * `#` - The alternate flag writes every letter in lowercase, as in
+///   `"e6rw"` rather than `"E6rW"`, which still parses as the same colour. This
+///   matches the alternate flag of [`HexRGB`].
+/// * `width`, fill and alignment - Pad the code to at least `width`
+///   characters with the fill character, aligning it to the left unless
+///   specified otherwise, as for strings.
+///
+/// Other flags, such as `+` and `0`, have no effect.
+///
 /// # Example
 /// ```
+/// # #[cfg(feature = "parse")]
+/// # {
 /// use sht_colour::SHT;
 ///
 /// let colour = "8r6g3".parse::<SHT<u8>>().unwrap();
 ///
 /// assert_eq!(format!("{}", colour), "8r6g3");
+/// assert_eq!(format!("{:#}", colour), "8r6g3");
+/// assert_eq!(format!("{:#}", "EmX".parse::<SHT<u8>>().unwrap()), "emx");
+/// assert_eq!(format!("{:*>8.1}", colour), "***8r6g3");
+/// # }
 /// ```
+///
+/// [`HexRGB`]: rgb::HexRGB
 impl<T> Display for SHT<T>
 where
     T: TryInto<usize> + Unsigned + Integer + Clone + Display + One,
     u8: Into<T>,
 {
     fn fmt(&self, formatter: &mut Formatter) -> FMTResult {
-        let precision = formatter.precision().unwrap_or(2);
+        let precision = formatter.precision().unwrap_or(DEFAULT_PRECISION);
+        let code = self.format_code(precision, RoundingMode::HalfUp);
+        if formatter.alternate() {
+            write_padded(formatter, &code.to_lowercase())
+        } else {
+            write_padded(formatter, &code)
+        }
+    }
+}
+
+impl<T> SHT<T>
+where
+    T: TryInto<usize> + Unsigned + Integer + Clone + Display + One,
+    u8: Into<T>,
+{
+    /// Formats an [`SHT`] as a code, as in the [`Display` implementation],
+    /// but rounding each component with the chosen [`RoundingMode`] instead
+    /// of rounding halves up.
+    ///
+    /// # Arguments
+    /// * `precision` - The maximum number of digits for each component, as
+    ///   for the [`Display` implementation].
+    /// * `mode` - How to round components that need more digits.
+    ///
+    /// # Example
+    /// ```
+    /// # #[cfg(feature = "parse")]
+    /// # {
+    /// use sht_colour::{RoundingMode, SHT};
+    ///
+    /// let colour = "r46".parse::<SHT<u32>>().unwrap();
+    ///
+    /// assert_eq!(colour.to_string_with_rounding(1, RoundingMode::HalfUp), "r5");
+    /// assert_eq!(colour.to_string_with_rounding(1, RoundingMode::HalfEven), "r4");
+    /// assert_eq!(colour.to_string_with_rounding(1, RoundingMode::Truncate), "r4");
+    /// # }
+    /// ```
+    ///
+    /// [`Display` implementation]: SHT#impl-Display
+    pub fn to_string_with_rounding(&self, precision: usize, mode: RoundingMode) -> String {
+        self.format_code(precision, mode)
+    }
+
+    /// Formats an [`SHT`] as a code with at most `precision` digits per
+    /// component, rounding with `mode`, without any padding.
+    fn format_code(&self, precision: usize, mode: RoundingMode) -> String {
+        let precision = precision.min(recommended_precision::<T>());
 
-        let ratio_to_str = |ratio: Ratio<T>| duodecimal(ratio, precision);
+        let ratio_to_str = |ratio: Ratio<T>| duodecimal_with(ratio, precision, mode);
         let primary_to_str = |primary| match primary {
             ColourChannel::Red => "r".to_owned(),
             ColourChannel::Green => "g".to_owned(),
@@ -571,8 +3085,8 @@ where
         };
 
         let (channel_ratios, shade_ratio, tint_ratio) = self.clone().components();
-        let tint = (!tint_ratio.is_zero()).then(|| tint_ratio);
-        let shade = (!shade_ratio.is_one()).then(|| shade_ratio);
+        let tint = (!tint_ratio.is_zero()).then_some(tint_ratio);
+        let shade = (!shade_ratio.is_one()).then_some(shade_ratio);
         let (primary, secondary, direction, blend) = match channel_ratios {
             ChannelRatios::OneBrightestChannel {
                 primary,
@@ -589,16 +3103,100 @@ where
             }
             ChannelRatios::ThreeBrightestChannels => (None, None, None, None),
         };
-        write!(
-            formatter,
+        let is_zero = |digits: &String| digits.chars().all(|digit| digit == '0');
+        let tint = tint.map(ratio_to_str);
+        if tint.as_deref() == Some("W") {
+            // a tint that rounds to one is pure white, whatever the hue
+            return "W".to_owned();
+        }
+        // a tint that rounds to zero is left out, as for a tint of exactly zero
+        let tint = tint.filter(|digits| !is_zero(digits));
+        // a shade that rounds to one is left out, as for a shade of exactly one
+        let shade = shade.map(ratio_to_str).filter(|digits| digits != "W");
+        let has_hue = primary.is_some() || secondary.is_some();
+        if has_hue
+            && shade
+                .as_ref()
+                .is_some_and(|digits| !digits.is_empty() && is_zero(digits))
+        {
+            // a shade that rounds to zero leaves only the grey of the tint
+            return tint.unwrap_or_else(|| "0".to_owned());
+        }
+        // a blend that rounds to zero would not parse, so drop it along with
+        // its direction, leaving only the primary, and a blend that rounds to
+        // one leaves the secondary colour between the primary and direction
+        let blend = blend.map(ratio_to_str).filter(|digits| !is_zero(digits));
+        let (primary, secondary, direction, blend) = match (primary, direction, blend) {
+            (Some(primary), Some(direction), Some(blend)) if blend == "W" => (
+                None,
+                Some(SecondaryColour::from_primaries(primary, direction)),
+                None,
+                None,
+            ),
+            (primary, direction, Some(blend)) => (primary, secondary, direction, Some(blend)),
+            (primary, _, None) => (primary, secondary, None, None),
+        };
+        let code = format!(
             "{}{}{}{}{}{}",
-            shade.map_or_else(String::new, ratio_to_str),
+            shade.unwrap_or_default(),
             primary.map_or_else(String::new, primary_to_str),
-            blend.map_or_else(String::new, ratio_to_str),
+            blend.unwrap_or_default(),
             direction.map_or_else(String::new, primary_to_str),
             secondary.map_or_else(String::new, secondary_to_str),
-            tint.map_or_else(String::new, ratio_to_str)
-        )
+            tint.unwrap_or_default()
+        );
+        if code.is_empty() {
+            // a grey whose tint rounds to zero is black
+            "0".to_owned()
+        } else {
+            code
+        }
+    }
+}
+
+/// Orders [`SHT`] values from darkest to lightest, roughly.
+///
+/// Colours are compared by tint first, then by shade, and then by their
+/// [`ChannelRatios`], as described in its [ordering]. So black comes before
+/// every other colour, and white after, and colours with the same tint and
+/// shade are grouped by hue.
+///
+/// # Example
+/// ```
+/// # #[cfg(feature = "parse")]
+/// # {
+/// use sht_colour::SHT;
+///
+/// let mut colours = ["W", "r", "6", "8r", "b", "0", "r3"]
+///     .iter()
+///     .map(|code| code.parse::<SHT<u32>>().unwrap())
+///     .collect::<Vec<_>>();
+/// colours.sort();
+///
+/// let codes = colours.iter().map(|colour| colour.to_string()).collect::<Vec<_>>();
+/// assert_eq!(codes, ["0", "8r", "r", "b", "r3", "6", "W"]);
+/// # }
+/// ```
+///
+/// [ordering]: ChannelRatios#ordering
+impl<T> Ord for SHT<T>
+where
+    T: Clone + Integer + Unsigned,
+{
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.tint
+            .cmp(&other.tint)
+            .then_with(|| self.shade.cmp(&other.shade))
+            .then_with(|| self.channel_ratios.cmp(&other.channel_ratios))
+    }
+}
+
+impl<T> PartialOrd for SHT<T>
+where
+    T: Clone + Integer + Unsigned,
+{
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
     }
 }
 
@@ -627,21 +3225,12 @@ where
     }
 }
 
-impl Default for ColourChannel {
-    fn default() -> Self {
-        ColourChannel::Red
-    }
-}
-
-impl Default for SecondaryColour {
-    fn default() -> Self {
-        SecondaryColour::Cyan
-    }
-}
-
-#[cfg(test)]
+#[cfg(all(test, feature = "parse"))]
 mod tests;
 
+/// Describes [`SHT`] values relative to a base colour.
+mod delta;
 /// Contains functions for parsing [`SHT`] values and their components from
 /// strings.
+#[cfg(feature = "parse")]
 mod parser;
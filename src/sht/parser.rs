@@ -2,16 +2,18 @@ use super::{ChannelRatios, ColourChannel, ParsePropertyError, SecondaryColour, S
 use ::nom::{
     branch::alt,
     bytes::complete::{tag_no_case, take},
-    character::complete::digit1,
-    combinator::{fail, map, map_res, opt, success, value, verify},
+    character::complete::{char, digit1, multispace0},
+    combinator::{fail, map, map_opt, map_res, opt, success, value, verify},
+    error::{Error, ErrorKind},
     multi::fold_many1,
-    sequence::{pair, tuple},
-    Finish, IResult,
+    sequence::{delimited, pair, separated_pair, tuple},
+    Err, Finish, IResult,
 };
 use ::num::{rational::Ratio, CheckedAdd, CheckedDiv, CheckedMul, Integer, One, Unsigned, Zero};
+use ::std::str::FromStr;
 
 /// Accept a duodecimal digit, either a standard numeral from `'0'` to `'9'`, or
-/// `'X'` or `'E'`.
+/// `'X'` or `'E'` in either case.
 pub(super) fn duodecimal_digit(input: &str) -> IResult<&str, &str> {
     // ensure only one digit is taken
     let (input, first) = take(1_u8)(input)?;
@@ -23,15 +25,15 @@ pub(super) fn duodecimal_digit(input: &str) -> IResult<&str, &str> {
 }
 
 /// Accept base-12 digit, and convert it to an integer, using `'X'` for 10 and
-/// `'E'` for 11.
+/// `'E'` for 11, in either case.
 pub(super) fn number_from_digit<T>(input: &str) -> IResult<&str, T>
 where
     u8: Into<T>,
 {
     map(
         map_res(duodecimal_digit, |character| match character {
-            "E" => Ok(11),
-            "X" => Ok(10),
+            "E" | "e" => Ok(11),
+            "X" | "x" => Ok(10),
             c => c.parse(),
         }),
         u8::into,
@@ -62,6 +64,10 @@ pub(super) fn primary_colour(input: &str) -> IResult<&str, ColourChannel> {
 /// count of the number of times the fraction has been divided in this way
 /// before.
 ///
+/// The fraction is reduced after each division, rather than dividing by a
+/// power from `safe_denominator`, so that it only overflows if the reduced
+/// fraction does not fit in the integer type.
+///
 /// # Errors
 /// Will return `None` if an overflow occurs.
 fn try_shift_fraction<T>(base: &T, digit: T, index: u8) -> Option<(u8, Ratio<T>)>
@@ -81,6 +87,30 @@ where
 /// overflows, the number is rounded so that the denominator is the maximal
 /// power of 12 that does not overflow.
 pub(super) fn quantity<T>(input: &str) -> IResult<&str, Ratio<T>>
+where
+    u8: Into<T>,
+    T: CheckedMul + CheckedAdd + Clone + Integer,
+{
+    map(rounded_quantity, |(number, _)| number)(input)
+}
+
+/// Parse a base-12 number as a ratio between 0 and 1, as in [`quantity`], but
+/// fail with [`ErrorKind::TooLarge`] rather than rounding if the denominator
+/// overflows.
+pub(super) fn strict_quantity<T>(input: &str) -> IResult<&str, Ratio<T>>
+where
+    u8: Into<T>,
+    T: CheckedMul + CheckedAdd + Clone + Integer,
+{
+    match rounded_quantity(input)? {
+        (_, (_, true)) => Err(Err::Failure(Error::new(input, ErrorKind::TooLarge))),
+        (input, (number, false)) => Ok((input, number)),
+    }
+}
+
+/// Parse a base-12 number as a ratio between 0 and 1, as in [`quantity`],
+/// along with whether it had to be rounded.
+fn rounded_quantity<T>(input: &str) -> IResult<&str, (Ratio<T>, bool)>
 where
     u8: Into<T>,
     T: CheckedMul + CheckedAdd + Clone + Integer,
@@ -99,7 +129,13 @@ where
                 })
                 // if unwrapping, it means denominator exceeded maximum size for type
                 // so check if we need to round up (unless already calculated)
-                .unwrap_or_else(|| (length, number, round_up.or_else(|| Some(digit >= half_base()))))
+                .unwrap_or_else(|| {
+                    (
+                        length,
+                        number,
+                        round_up.or_else(|| Some(digit >= half_base())),
+                    )
+                })
         },
     );
     let (input, (length, number, round_up)) = digit_folder(input)?;
@@ -107,32 +143,89 @@ where
         Some(true) => {
             let correction =
                 try_shift_fraction(&base, 1.into(), length - 1).map_or_else(<_>::zero, |(_, n)| n);
-            Ok((input, number + correction))
+            Ok((input, (number + correction, true)))
         }
-        _ => Ok((input, number)),
+        Some(false) => Ok((input, (number, true))),
+        None => Ok((input, (number, false))),
     }
 }
 
+/// Parse a whole string as a base-12 number between 0 and 1, as in
+/// [`quantity`], or as `'W'` for 1.
+///
+/// # Errors
+/// Will return `Err` if the string is not a number, or has characters left
+/// over after the number.
+pub(super) fn parse_duodecimal<T>(input: &str) -> Result<Ratio<T>, ParsePropertyError>
+where
+    u8: Into<T>,
+    T: CheckedMul + CheckedAdd + Clone + Integer,
+{
+    match alt((value(Ratio::one(), tag_no_case("W")), quantity))(input).finish() {
+        Ok(("", number)) => Ok(number),
+        Ok((remaining, _)) => Err(ParsePropertyError::InputRemaining {
+            parsed_len: input.len() - remaining.len(),
+            remaining: remaining.to_owned(),
+        }),
+        Err(error) => Err(error.into()),
+    }
+}
+
+/// Parse a fraction written as a decimal numerator and denominator separated
+/// by `'/'`, such as `"1/2"`, as a ratio. Fails if the denominator is zero.
+pub(super) fn fraction<T>(input: &str) -> IResult<&str, Ratio<T>>
+where
+    T: Clone + Integer + FromStr,
+{
+    let decimal = || map_res(digit1, str::parse::<T>);
+    map_opt(
+        separated_pair(decimal(), char('/'), decimal()),
+        |(numerator, denominator)| {
+            (!denominator.is_zero()).then(|| Ratio::new(numerator, denominator))
+        },
+    )(input)
+}
+
+/// Parse a number that is either a fraction, as in [`fraction`], or a base-12
+/// number, as in [`quantity`], ignoring any surrounding whitespace.
+fn fraction_or_quantity<T>(input: &str) -> IResult<&str, Ratio<T>>
+where
+    T: Clone + Integer + CheckedMul + CheckedAdd + FromStr,
+    u8: Into<T>,
+{
+    delimited(multispace0, alt((fraction, quantity)), multispace0)(input)
+}
+
+/// Type abbreviation for a parser of the numeric components of an [`SHT`].
+type NumberParser<T> = fn(&str) -> IResult<&str, Ratio<T>>;
+
 /// Parse a pair of a blend number and a primary colour representing an [`SHT`]
-/// direction.
-pub(super) fn direction_blend<T>(input: &str) -> IResult<&str, (ColourChannel, Ratio<T>)>
+/// direction, parsing the blend with `number`.
+pub(super) fn direction_blend<T>(
+    number: NumberParser<T>,
+    input: &str,
+) -> IResult<&str, (ColourChannel, Ratio<T>)>
 where
     T: Clone + Integer + CheckedMul + CheckedAdd,
     u8: Into<T>,
 {
-    let (input, (blend, direction)) = pair(quantity, primary_colour)(input)?;
+    let (input, (blend, direction)) = pair(number, primary_colour)(input)?;
     Ok((input, (direction, blend)))
 }
 
-/// Parse a [`ChannelRatios`] enum from a string.
-pub(super) fn channel_ratios<T>(input: &str) -> IResult<&str, ChannelRatios<T>>
+/// Parse a [`ChannelRatios`] enum from a string, parsing any blend with
+/// `number`.
+pub(super) fn channel_ratios<T>(
+    number: NumberParser<T>,
+    input: &str,
+) -> IResult<&str, ChannelRatios<T>>
 where
     T: Clone + Integer + CheckedMul + CheckedAdd + Unsigned,
     u8: Into<T>,
 {
     alt((
         map(
-            pair(primary_colour, opt(direction_blend)),
+            pair(primary_colour, opt(|input| direction_blend(number, input))),
             |(primary, direction_blend)| ChannelRatios::OneBrightestChannel {
                 primary,
                 direction_blend,
@@ -155,15 +248,30 @@ where
     T: Clone + Integer + CheckedMul + CheckedAdd + Unsigned,
     u8: Into<T>,
 {
-    let zero_shade = map(verify(quantity, |v| v.is_zero()), Some);
-    let shade = quantity;
+    sht_data_with(quantity, input)
+}
+
+/// Parse the components of an [`SHT`] from a string, performing rudimentary
+/// checking for impossible `SHT`s, and parsing numeric components with
+/// `number`.
+fn sht_data_with<T>(number: NumberParser<T>, input: &str) -> IResult<&str, SHTParts<T>>
+where
+    T: Clone + Integer + CheckedMul + CheckedAdd + Unsigned,
+    u8: Into<T>,
+{
+    let zero_shade = map(verify(number, |v| v.is_zero()), Some);
+    let shade = number;
     let empty_channel = || success(ChannelRatios::ThreeBrightestChannels);
     let empty_quantity = || success(None);
-    let tint = || verify(quantity, |v| !v.is_zero());
+    let tint = || verify(number, |v| !v.is_zero());
     alt((
         // attempt to parse maximally many numeric components
         // separated by colours
-        tuple((opt(shade), channel_ratios, opt(tint()))),
+        tuple((
+            opt(shade),
+            |input| channel_ratios(number, input),
+            opt(tint()),
+        )),
         // fall back to parsing one numeric component
         tuple((zero_shade, empty_channel(), empty_quantity())),
         tuple((empty_quantity(), empty_channel(), map(tint(), Some))),
@@ -179,24 +287,151 @@ where
     ))(input)
 }
 
-/// Parse an [`SHT`] from a string.
+/// Construct an [`SHT`] from the components in a string, which are parsed
+/// with `parts`.
 ///
 /// # Errors
 /// Will return `Err` if the string could not be parsed or if the `SHT` could
 /// not be constructed from whatever values were parsed.
-pub(super) fn parse_sht<T>(input: &str) -> Result<SHT<T>, ParsePropertyError>
+fn parse_with<'a, T>(
+    mut parts: impl FnMut(&'a str) -> IResult<&'a str, SHTParts<T>>,
+    input: &'a str,
+) -> Result<SHT<T>, ParsePropertyError>
 where
-    T: Clone + Integer + CheckedMul + CheckedAdd + Unsigned,
-    u8: Into<T>,
+    T: Clone + Integer + Unsigned,
 {
-    match sht_data(input).finish() {
+    match parts(input).finish() {
         Ok(("", (shade, channel_ratios, tint))) => SHT::new(
             channel_ratios,
             shade.unwrap_or_else(<_>::one),
             tint.unwrap_or_else(<_>::zero),
         )
         .map_err(ParsePropertyError::ValueErrors),
-        Ok((remaining, _)) => Err(ParsePropertyError::InputRemaining(remaining.to_owned())),
+        Ok((remaining, _)) => Err(ParsePropertyError::InputRemaining {
+            parsed_len: input.len() - remaining.len(),
+            remaining: remaining.to_owned(),
+        }),
         Err(y) => Err(y.into()),
     }
 }
+
+/// Parse an [`SHT`] from a string.
+///
+/// # Errors
+/// Will return `Err` if the string could not be parsed or if the `SHT` could
+/// not be constructed from whatever values were parsed.
+pub(super) fn parse_sht<T>(input: &str) -> Result<SHT<T>, ParsePropertyError>
+where
+    T: Clone + Integer + CheckedMul + CheckedAdd + Unsigned,
+    u8: Into<T>,
+{
+    parse_with(sht_data, input)
+}
+
+/// Find the value of a base-12 digit character, treating `'W'` as 12.
+fn digit_value(digit: char) -> Option<u8> {
+    match digit.to_ascii_uppercase() {
+        'X' => Some(10),
+        'E' => Some(11),
+        'W' => Some(12),
+        other => other.to_digit(10).map(|value| value as u8),
+    }
+}
+
+/// Parse an [`SHT`] from a string without allocating, copying the value of
+/// each digit of the shade, blend and tint, in that order, into `digits`.
+///
+/// Returns the `SHT` along with how many digits were written for each of
+/// those components, or `None` if the string could not be parsed or
+/// `digits` is too small.
+pub(super) fn parse_sht_into<T>(input: &str, digits: &mut [u8]) -> Option<(SHT<T>, [usize; 3])>
+where
+    T: Clone + Integer + CheckedMul + CheckedAdd + Unsigned,
+    u8: Into<T>,
+{
+    let (shade, channel_ratios, tint) = match sht_data(input) {
+        Ok(("", parts)) => parts,
+        _ => return None,
+    };
+    let has_blend = matches!(
+        channel_ratios,
+        ChannelRatios::OneBrightestChannel {
+            direction_blend: Some(_),
+            ..
+        }
+    );
+    // each present component is written as one run of digits
+    let mut runs = input
+        .split(|character| digit_value(character).is_none())
+        .filter(|run| !run.is_empty());
+    let mut counts = [0; 3];
+    let mut written = 0;
+    for (count, &present) in counts
+        .iter_mut()
+        .zip([shade.is_some(), has_blend, tint.is_some()].iter())
+    {
+        if present {
+            let run = runs.next()?;
+            let target = digits.get_mut(written..written + run.len())?;
+            for (slot, digit) in target.iter_mut().zip(run.chars()) {
+                *slot = digit_value(digit)?;
+            }
+            written += run.len();
+            *count = run.len();
+        }
+    }
+    let code = SHT::new(
+        channel_ratios,
+        shade.unwrap_or_else(<_>::one),
+        tint.unwrap_or_else(<_>::zero),
+    )
+    .ok()?;
+    Some((code, counts))
+}
+
+/// Parse an [`SHT`] from a string, failing rather than rounding if a numeric
+/// component has too many digits for `T`.
+///
+/// # Errors
+/// Will return `Err` if the string could not be parsed, if a numeric component
+/// would need rounding, or if the `SHT` could not be constructed from
+/// whatever values were parsed.
+pub(super) fn parse_sht_strict<T>(input: &str) -> Result<SHT<T>, ParsePropertyError>
+where
+    T: Clone + Integer + CheckedMul + CheckedAdd + Unsigned,
+    u8: Into<T>,
+{
+    parse_with(|input| sht_data_with(strict_quantity, input), input).map_err(|err| match err {
+        ParsePropertyError::ParseFailure(Error {
+            code: ErrorKind::TooLarge,
+            ..
+        }) => ParsePropertyError::PrecisionOverflow,
+        err => err,
+    })
+}
+
+/// Parse an [`SHT`] from a string, where numeric components may also be
+/// written as fractions, and may be surrounded by whitespace.
+///
+/// # Errors
+/// Will return `Err` if the string could not be parsed or if the `SHT` could
+/// not be constructed from whatever values were parsed.
+pub(super) fn parse_sht_fractions<T>(input: &str) -> Result<SHT<T>, ParsePropertyError>
+where
+    T: Clone + Integer + CheckedMul + CheckedAdd + Unsigned + FromStr,
+    u8: Into<T>,
+{
+    let trimmed = input.trim();
+    let leading_whitespace = input.len() - input.trim_start().len();
+    parse_with(|input| sht_data_with(fraction_or_quantity, input), trimmed).map_err(|err| match err
+    {
+        ParsePropertyError::InputRemaining {
+            parsed_len,
+            remaining,
+        } => ParsePropertyError::InputRemaining {
+            parsed_len: leading_whitespace + parsed_len,
+            remaining,
+        },
+        err => err,
+    })
+}
@@ -250,6 +250,28 @@ fn value_failure_blend_one() {
     );
 }
 
+#[test]
+fn value_failure_multiple_in_order() {
+    use super::{ChannelRatios, ColourChannel, SHTValueError, SHT};
+    use num::rational::Ratio;
+    assert_eq!(
+        SHT::<u32>::new(
+            ChannelRatios::OneBrightestChannel {
+                primary: ColourChannel::Blue,
+                direction_blend: Some((ColourChannel::Blue, Ratio::new(0, 1))) // errors
+            },
+            Ratio::new(0, 1), // error
+            Ratio::new(2, 1), // errors
+        ),
+        Err(vec![
+            SHTValueError::PrimaryShadeZero,
+            SHTValueError::DirectionEqualsPrimary,
+            SHTValueError::BlendZero,
+            SHTValueError::ValueOutOfBounds,
+        ])
+    );
+}
+
 #[test]
 fn parse_success_shade_blend_tint() {
     use super::{ChannelRatios, ColourChannel, SHT};
@@ -468,60 +490,80 @@ fn parse_failure_leftover_symbols() {
 #[allow(non_snake_case)]
 fn parse_failure_extra_W() {
     use super::{ParsePropertyError, SHT};
-    let leftover = |s: &str| Err(ParsePropertyError::InputRemaining(s.to_string()));
-    assert_eq!("8r6g3W".parse::<SHT<u8>>(), leftover("W"));
-    assert_eq!("rW".parse::<SHT<u8>>(), leftover("W"));
-    assert_eq!("8rW".parse::<SHT<u8>>(), leftover("W"));
-    assert_eq!("r3W".parse::<SHT<u8>>(), leftover("W"));
-    assert_eq!("6r3W".parse::<SHT<u8>>(), leftover("W"));
-    assert_eq!("r6gW".parse::<SHT<u8>>(), leftover("W"));
-    assert_eq!("8r6gW".parse::<SHT<u8>>(), leftover("W"));
-    assert_eq!("8r6g3W".parse::<SHT<u8>>(), leftover("W"));
-    assert_eq!("8y3W".parse::<SHT<u8>>(), leftover("W"));
-    assert_eq!("6W".parse::<SHT<u8>>(), leftover("W"));
-    assert_eq!("0W".parse::<SHT<u8>>(), leftover("W"));
-    assert_eq!("WW".parse::<SHT<u8>>(), leftover("W"));
+    let leftover = |code: &str, s: &str| {
+        Err(ParsePropertyError::InputRemaining {
+            parsed_len: code.len() - s.len(),
+            remaining: s.to_string(),
+        })
+    };
+    assert_eq!("8r6g3W".parse::<SHT<u8>>(), leftover("8r6g3W", "W"));
+    assert_eq!("rW".parse::<SHT<u8>>(), leftover("rW", "W"));
+    assert_eq!("8rW".parse::<SHT<u8>>(), leftover("8rW", "W"));
+    assert_eq!("r3W".parse::<SHT<u8>>(), leftover("r3W", "W"));
+    assert_eq!("6r3W".parse::<SHT<u8>>(), leftover("6r3W", "W"));
+    assert_eq!("r6gW".parse::<SHT<u8>>(), leftover("r6gW", "W"));
+    assert_eq!("8r6gW".parse::<SHT<u8>>(), leftover("8r6gW", "W"));
+    assert_eq!("8r6g3W".parse::<SHT<u8>>(), leftover("8r6g3W", "W"));
+    assert_eq!("8y3W".parse::<SHT<u8>>(), leftover("8y3W", "W"));
+    assert_eq!("6W".parse::<SHT<u8>>(), leftover("6W", "W"));
+    assert_eq!("0W".parse::<SHT<u8>>(), leftover("0W", "W"));
+    assert_eq!("WW".parse::<SHT<u8>>(), leftover("WW", "W"));
 }
 
 #[test]
 fn parse_failure_extra_r() {
     use super::{ParsePropertyError, SHT};
-    let leftover = |s: &str| Err(ParsePropertyError::InputRemaining(s.to_string()));
-    assert_eq!("8r6g3r".parse::<SHT<u8>>(), leftover("r"));
-    assert_eq!("rr".parse::<SHT<u8>>(), leftover("r"));
-    assert_eq!("8rr".parse::<SHT<u8>>(), leftover("r"));
-    assert_eq!("r6gr".parse::<SHT<u8>>(), leftover("r"));
-    assert_eq!("8r6gr".parse::<SHT<u8>>(), leftover("r"));
-    assert_eq!("8r6g3r".parse::<SHT<u8>>(), leftover("r"));
-    assert_eq!("8y3r".parse::<SHT<u8>>(), leftover("r"));
-    assert_eq!("Wr".parse::<SHT<u8>>(), leftover("r"));
+    let leftover = |code: &str, s: &str| {
+        Err(ParsePropertyError::InputRemaining {
+            parsed_len: code.len() - s.len(),
+            remaining: s.to_string(),
+        })
+    };
+    assert_eq!("8r6g3r".parse::<SHT<u8>>(), leftover("8r6g3r", "r"));
+    assert_eq!("rr".parse::<SHT<u8>>(), leftover("rr", "r"));
+    assert_eq!("8rr".parse::<SHT<u8>>(), leftover("8rr", "r"));
+    assert_eq!("r6gr".parse::<SHT<u8>>(), leftover("r6gr", "r"));
+    assert_eq!("8r6gr".parse::<SHT<u8>>(), leftover("8r6gr", "r"));
+    assert_eq!("8r6g3r".parse::<SHT<u8>>(), leftover("8r6g3r", "r"));
+    assert_eq!("8y3r".parse::<SHT<u8>>(), leftover("8y3r", "r"));
+    assert_eq!("Wr".parse::<SHT<u8>>(), leftover("Wr", "r"));
 }
 
 #[test]
 fn parse_failure_extra_c() {
     use super::{ParsePropertyError, SHT};
-    let leftover = |s: &str| Err(ParsePropertyError::InputRemaining(s.to_string()));
-    assert_eq!("8r6g3c".parse::<SHT<u8>>(), leftover("c"));
-    assert_eq!("rc".parse::<SHT<u8>>(), leftover("c"));
-    assert_eq!("8rc".parse::<SHT<u8>>(), leftover("c"));
-    assert_eq!("r3c".parse::<SHT<u8>>(), leftover("c"));
-    assert_eq!("6r3c".parse::<SHT<u8>>(), leftover("c"));
-    assert_eq!("r6gc".parse::<SHT<u8>>(), leftover("c"));
-    assert_eq!("8r6gc".parse::<SHT<u8>>(), leftover("c"));
-    assert_eq!("8r6g3c".parse::<SHT<u8>>(), leftover("c"));
-    assert_eq!("8y3c".parse::<SHT<u8>>(), leftover("c"));
-    assert_eq!("Wc".parse::<SHT<u8>>(), leftover("c"));
+    let leftover = |code: &str, s: &str| {
+        Err(ParsePropertyError::InputRemaining {
+            parsed_len: code.len() - s.len(),
+            remaining: s.to_string(),
+        })
+    };
+    assert_eq!("8r6g3c".parse::<SHT<u8>>(), leftover("8r6g3c", "c"));
+    assert_eq!("rc".parse::<SHT<u8>>(), leftover("rc", "c"));
+    assert_eq!("8rc".parse::<SHT<u8>>(), leftover("8rc", "c"));
+    assert_eq!("r3c".parse::<SHT<u8>>(), leftover("r3c", "c"));
+    assert_eq!("6r3c".parse::<SHT<u8>>(), leftover("6r3c", "c"));
+    assert_eq!("r6gc".parse::<SHT<u8>>(), leftover("r6gc", "c"));
+    assert_eq!("8r6gc".parse::<SHT<u8>>(), leftover("8r6gc", "c"));
+    assert_eq!("8r6g3c".parse::<SHT<u8>>(), leftover("8r6g3c", "c"));
+    assert_eq!("8y3c".parse::<SHT<u8>>(), leftover("8y3c", "c"));
+    assert_eq!("Wc".parse::<SHT<u8>>(), leftover("Wc", "c"));
 }
 
 #[test]
 fn parse_failure_extra_0() {
     use super::{ParsePropertyError, SHT};
-    let leftover = |s: &str| Err(ParsePropertyError::InputRemaining(s.to_string()));
-    assert_eq!("r0".parse::<SHT<u8>>(), leftover("0"));
-    assert_eq!("8r0".parse::<SHT<u8>>(), leftover("0"));
-    assert_eq!("r6g0".parse::<SHT<u8>>(), leftover("0"));
-    assert_eq!("8r6g0".parse::<SHT<u8>>(), leftover("0"));
-    assert_eq!("W0".parse::<SHT<u8>>(), leftover("0"));
+    let leftover = |code: &str, s: &str| {
+        Err(ParsePropertyError::InputRemaining {
+            parsed_len: code.len() - s.len(),
+            remaining: s.to_string(),
+        })
+    };
+    assert_eq!("r0".parse::<SHT<u8>>(), leftover("r0", "0"));
+    assert_eq!("8r0".parse::<SHT<u8>>(), leftover("8r0", "0"));
+    assert_eq!("r6g0".parse::<SHT<u8>>(), leftover("r6g0", "0"));
+    assert_eq!("8r6g0".parse::<SHT<u8>>(), leftover("8r6g0", "0"));
+    assert_eq!("W0".parse::<SHT<u8>>(), leftover("W0", "0"));
 }
 
 #[test]
@@ -529,7 +571,10 @@ fn parse_failure_extra_1() {
     use super::{ParsePropertyError, SHT};
     assert_eq!(
         "W1".parse::<SHT<u8>>(),
-        Err(ParsePropertyError::InputRemaining("1".to_string()))
+        Err(ParsePropertyError::InputRemaining {
+            parsed_len: 1,
+            remaining: "1".to_string()
+        })
     );
 }
 
@@ -563,7 +608,7 @@ fn parse_only_digits() {
         );
         assert_eq!(
             number_from_digit::<u8>(&c.to_string()).is_ok(),
-            digits.contains(&c)
+            digits.contains(&c.to_ascii_uppercase())
         )
     }
 }
@@ -651,75 +696,81 @@ fn parse_quantity_case_error() {
 
 #[test]
 fn parse_direction_blend_success() {
-    use super::{parser::direction_blend, ColourChannel};
+    use super::{
+        parser::{direction_blend, quantity},
+        ColourChannel,
+    };
     use num::rational::Ratio;
     assert_eq!(
-        direction_blend("34EXRC"),
+        direction_blend(quantity, "34EXRC"),
         Ok(("C", (ColourChannel::Red, Ratio::new(5902_u32, 20736))))
     );
     assert_eq!(
-        direction_blend("3GC"),
+        direction_blend(quantity, "3GC"),
         Ok(("C", (ColourChannel::Green, Ratio::new(3_u32, 12))))
     );
 }
 
 #[test]
 fn parse_direction_blend_failure_empty() {
-    use super::parser::direction_blend;
+    use super::parser::{direction_blend, quantity};
     use nom::{
         error::{Error, ErrorKind},
         Err,
     };
     assert_eq!(
-        direction_blend::<u8>("..."),
+        direction_blend::<u8>(quantity, "..."),
         Err(Err::Error(Error::new("...", ErrorKind::Many1)))
     );
 }
 
 #[test]
 fn parse_direction_blend_failure_wrong_order_direction_blend() {
-    use super::parser::direction_blend;
+    use super::parser::{direction_blend, quantity};
     use nom::{
         error::{Error, ErrorKind},
         Err,
     };
     assert_eq!(
-        direction_blend::<u8>("r1..."),
+        direction_blend::<u8>(quantity, "r1..."),
         Err(Err::Error(Error::new("r1...", ErrorKind::Many1)))
     );
 }
 
 #[test]
 fn parse_direction_blend_failure_direction_no_blend() {
-    use super::parser::direction_blend;
+    use super::parser::{direction_blend, quantity};
     use nom::{
         error::{Error, ErrorKind},
         Err,
     };
     assert_eq!(
-        direction_blend::<u8>("r..."),
+        direction_blend::<u8>(quantity, "r..."),
         Err(Err::Error(Error::new("r...", ErrorKind::Many1)))
     );
 }
 
 #[test]
 fn parse_direction_blend_failure_blend_no_direction() {
-    use super::parser::direction_blend;
+    use super::parser::{direction_blend, quantity};
     use nom::{
         error::{Error, ErrorKind},
         Err,
     };
     assert_eq!(
-        direction_blend::<u8>("1..."),
+        direction_blend::<u8>(quantity, "1..."),
         Err(Err::Error(Error::new("...", ErrorKind::Tag)))
     );
 }
 
 #[test]
 fn parse_channel_ratios_uppercase_primary_u8() {
-    use super::{parser::channel_ratios, ChannelRatios, ColourChannel};
+    use super::{
+        parser::{channel_ratios, quantity},
+        ChannelRatios, ColourChannel,
+    };
     assert_eq!(
-        channel_ratios::<u8>("R..."),
+        channel_ratios::<u8>(quantity, "R..."),
         Ok((
             "...",
             ChannelRatios::OneBrightestChannel {
@@ -732,10 +783,13 @@ fn parse_channel_ratios_uppercase_primary_u8() {
 
 #[test]
 fn parse_channel_ratios_uppercase_direction_blend() {
-    use super::{parser::channel_ratios, ChannelRatios, ColourChannel};
+    use super::{
+        parser::{channel_ratios, quantity},
+        ChannelRatios, ColourChannel,
+    };
     use num::rational::Ratio;
     assert_eq!(
-        channel_ratios::<u16>("R123G..."),
+        channel_ratios::<u16>(quantity, "R123G..."),
         Ok((
             "...",
             ChannelRatios::OneBrightestChannel {
@@ -748,9 +802,12 @@ fn parse_channel_ratios_uppercase_direction_blend() {
 
 #[test]
 fn parse_channel_ratios_uppercase_tint() {
-    use super::{parser::channel_ratios, ChannelRatios, ColourChannel};
+    use super::{
+        parser::{channel_ratios, quantity},
+        ChannelRatios, ColourChannel,
+    };
     assert_eq!(
-        channel_ratios::<u16>("R123..."),
+        channel_ratios::<u16>(quantity, "R123..."),
         Ok((
             "123...",
             ChannelRatios::OneBrightestChannel {
@@ -763,9 +820,12 @@ fn parse_channel_ratios_uppercase_tint() {
 
 #[test]
 fn parse_channel_ratios_uppercase_primary_u16() {
-    use super::{parser::channel_ratios, ChannelRatios, ColourChannel};
+    use super::{
+        parser::{channel_ratios, quantity},
+        ChannelRatios, ColourChannel,
+    };
     assert_eq!(
-        channel_ratios::<u16>("G..."),
+        channel_ratios::<u16>(quantity, "G..."),
         Ok((
             "...",
             ChannelRatios::OneBrightestChannel {
@@ -778,9 +838,12 @@ fn parse_channel_ratios_uppercase_primary_u16() {
 
 #[test]
 fn parse_channel_ratios_uppercase_secondary() {
-    use super::{parser::channel_ratios, ChannelRatios, SecondaryColour};
+    use super::{
+        parser::{channel_ratios, quantity},
+        ChannelRatios, SecondaryColour,
+    };
     assert_eq!(
-        channel_ratios::<u16>("C..."),
+        channel_ratios::<u16>(quantity, "C..."),
         Ok((
             "...",
             ChannelRatios::TwoBrightestChannels {
@@ -792,26 +855,26 @@ fn parse_channel_ratios_uppercase_secondary() {
 
 #[test]
 fn parse_channel_ratios_empty() {
-    use super::parser::channel_ratios;
+    use super::parser::{channel_ratios, quantity};
     use nom::{
         error::{Error, ErrorKind},
         Err,
     };
     assert_eq!(
-        channel_ratios::<u8>("..."),
+        channel_ratios::<u8>(quantity, "..."),
         Err(Err::Error(Error::new("...", ErrorKind::Tag)))
     );
 }
 
 #[test]
 fn parse_channel_ratios_uppercase_shade_primary() {
-    use super::parser::channel_ratios;
+    use super::parser::{channel_ratios, quantity};
     use nom::{
         error::{Error, ErrorKind},
         Err,
     };
     assert_eq!(
-        channel_ratios::<u16>("123R..."),
+        channel_ratios::<u16>(quantity, "123R..."),
         Err(Err::Error(Error::new("123R...", ErrorKind::Tag)))
     );
 }
@@ -1068,6 +1131,26 @@ fn display_precision_1() {
     assert_eq!(&format!("{:.1}", "W".parse::<SHT<u8>>().unwrap()), "W");
 }
 
+#[test]
+fn display_flags() {
+    use super::SHT;
+    let colour = "8r6g3".parse::<SHT<u8>>().unwrap();
+    assert_eq!(format!("{:#}", colour), "8r6g3");
+    let mixed = "EmX".parse::<SHT<u8>>().unwrap();
+    assert_eq!(format!("{:#}", mixed), "emx");
+    assert_eq!(format!("{:#}", mixed).parse::<SHT<u8>>().unwrap(), mixed);
+    assert_eq!(format!("{:#}", "W".parse::<SHT<u8>>().unwrap()), "w");
+    assert_eq!(format!("{:#}", colour).parse::<SHT<u8>>().unwrap(), colour);
+    assert_eq!(format!("{:8}", colour), "8r6g3   ");
+    assert_eq!(format!("{:>8}", colour), "   8r6g3");
+    assert_eq!(format!("{:-^9}", colour), "--8r6g3--");
+    assert_eq!(format!("{:*<7.1}", colour), "8r6g3**");
+    assert_eq!(format!("{:3}", colour), "8r6g3");
+    // other flags do nothing
+    assert_eq!(format!("{:+}", colour), "8r6g3");
+    assert_eq!(format!("{:08}", colour), "8r6g3   ");
+}
+
 #[test]
 fn display_no_precision() {
     use super::SHT;
@@ -1149,3 +1232,1318 @@ fn round_over_max() {
     assert_eq!(round(&[13], true), [12]);
     // assert_eq!(round(&[13], false), [12]); not implemented
 }
+
+#[test]
+fn min_precision_exact() {
+    use super::min_precision;
+    use num::rational::Ratio;
+    assert_eq!(min_precision(&Ratio::new(0_u32, 1)), Some(0));
+    assert_eq!(min_precision(&Ratio::new(1_u32, 1)), Some(0));
+    assert_eq!(min_precision(&Ratio::new(1_u32, 2)), Some(1));
+    assert_eq!(min_precision(&Ratio::new(1_u32, 8)), Some(2));
+    assert_eq!(min_precision(&Ratio::new(5_u32, 1728)), Some(3));
+}
+
+#[test]
+fn min_precision_inexact() {
+    use super::min_precision;
+    use num::rational::Ratio;
+    assert_eq!(min_precision(&Ratio::new(1_u32, 5)), None);
+    assert_eq!(min_precision(&Ratio::new(1_u32, 255)), None);
+}
+
+#[test]
+fn roundtrip_string_high_precision() {
+    use super::SHT;
+    for code in &[
+        "r",
+        "W",
+        "0",
+        "8r6g3",
+        "123456",
+        "7r5bE",
+        "5EX4r39Xb0E1",
+        "X01y000001",
+        "E1c7",
+    ] {
+        let colour = code.parse::<SHT<u64>>().unwrap();
+        let string = colour.to_roundtrip_string().unwrap();
+        assert_eq!(&string, code);
+        assert_eq!(string.parse::<SHT<u64>>(), Ok(colour));
+    }
+}
+
+#[test]
+fn roundtrip_string_inexact() {
+    use super::{ChannelRatios, SHT};
+    use num::rational::Ratio;
+    let colour = SHT::<u32>::new(
+        ChannelRatios::ThreeBrightestChannels,
+        Ratio::new(1, 5),
+        Ratio::new(0, 1),
+    )
+    .unwrap();
+    assert_eq!(colour.to_roundtrip_string(), None);
+}
+
+#[test]
+fn spec_precision_rounds_fields() {
+    use super::SHT;
+    let parse = |s: &str| s.parse::<SHT<u32>>().unwrap();
+    assert_eq!(parse("5EX4r39Xb0E1").to_spec_precision(), parse("6r3Xb0E"));
+    assert_eq!(parse("8r6g3").to_spec_precision(), parse("8r6g3"));
+    assert_eq!(parse("12345").to_spec_precision(), parse("12"));
+}
+
+#[test]
+fn spec_precision_collapses_edges() {
+    use super::SHT;
+    let parse = |s: &str| s.parse::<SHT<u32>>().unwrap();
+    // blend rounds to zero
+    assert_eq!(parse("r001g").to_spec_precision(), parse("r"));
+    // blend rounds to one
+    assert_eq!(parse("rEEEg").to_spec_precision(), parse("y"));
+    // tint rounds to one
+    assert_eq!(parse("rEEE").to_spec_precision(), parse("W"));
+    // shade rounds to zero
+    assert_eq!(parse("001r").to_spec_precision(), parse("0"));
+    assert_eq!(parse("001r6").to_spec_precision(), parse("6"));
+}
+
+#[test]
+fn total_digit_count_simple() {
+    use super::SHT;
+    for code in &["r", "c", "W", "0"] {
+        assert_eq!(
+            code.parse::<SHT<u32>>().unwrap().total_digit_count(),
+            Some(0)
+        );
+    }
+}
+
+#[test]
+fn total_digit_count_complex() {
+    use super::SHT;
+    let count = |s: &str| s.parse::<SHT<u32>>().unwrap().total_digit_count();
+    assert_eq!(count("8r6g3"), Some(3));
+    assert_eq!(count("80r60g30"), Some(3));
+    assert_eq!(count("12y345"), Some(5));
+    assert_eq!(count("5EXr39Xb0E1"), Some(9));
+}
+
+#[test]
+fn hex_string_digits() {
+    use super::SHT;
+    let colour = "8r6g3".parse::<SHT<u32>>().unwrap();
+    assert_eq!(colour.to_hex_string(1), "#C84");
+    assert_eq!(colour.to_hex_string(3), "#C00800400");
+    assert_eq!(
+        "W".parse::<SHT<u32>>().unwrap().to_hex_string(3),
+        "#FFFFFFFFF"
+    );
+}
+
+#[test]
+fn swizzle_matches_rgb() {
+    use super::{ColourChannel::*, SHT};
+    let orders = [
+        [Red, Green, Blue],
+        [Red, Blue, Green],
+        [Green, Red, Blue],
+        [Green, Blue, Red],
+        [Blue, Red, Green],
+        [Blue, Green, Red],
+    ];
+    for code in &["8r6g3", "4b3", "6c", "y2", "W", "0", "5", "X8g3r4"] {
+        let colour = code.parse::<SHT<u32>>().unwrap();
+        for order in &orders {
+            assert_eq!(
+                colour.swizzle(*order).to_rgb(3),
+                colour.to_rgb(3).swizzle(*order),
+                "{} {:?}",
+                code,
+                order
+            );
+        }
+    }
+}
+
+#[test]
+fn parse_white_case_insensitive() {
+    use super::SHT;
+    let white = "W".parse::<SHT<u8>>().unwrap();
+    assert_eq!("w".parse::<SHT<u8>>(), Ok(white));
+    assert_eq!(white.to_string(), "W");
+    assert_eq!("w".parse::<SHT<u8>>().unwrap().to_string(), "W");
+}
+
+#[test]
+fn parse_failure_extra_lowercase_w() {
+    use super::{ParsePropertyError, SHT};
+    let leftover = |code: &str, s: &str| {
+        Err(ParsePropertyError::InputRemaining {
+            parsed_len: code.len() - s.len(),
+            remaining: s.to_string(),
+        })
+    };
+    assert_eq!("ww".parse::<SHT<u8>>(), leftover("ww", "w"));
+    assert_eq!("Ww".parse::<SHT<u8>>(), leftover("Ww", "w"));
+    assert_eq!("wW".parse::<SHT<u8>>(), leftover("wW", "W"));
+    assert_eq!("6w".parse::<SHT<u8>>(), leftover("6w", "w"));
+    assert_eq!("rw".parse::<SHT<u8>>(), leftover("rw", "w"));
+    assert_eq!("8r6g3w".parse::<SHT<u8>>(), leftover("8r6g3w", "w"));
+}
+
+#[test]
+fn parse_failure_white_as_digit() {
+    use super::SHT;
+    for code in &["wr", "Wr", "rWg", "r6w", "1W", "W0"] {
+        assert!(code.parse::<SHT<u8>>().is_err(), "{}", code);
+    }
+}
+
+#[test]
+fn lut_matches_conversion() {
+    use super::{generate_lut, SHT};
+    let table = generate_lut::<u32>(1);
+    assert_eq!(table.len(), 10381);
+    for (code, hex) in &table {
+        let parsed = code.to_string().parse::<SHT<u32>>().unwrap();
+        assert_eq!(&parsed, code);
+        assert_eq!(parsed.to_rgb(2), *hex);
+    }
+}
+
+#[test]
+fn all_codes_distinct_and_valid() {
+    use super::SHT;
+    use std::collections::HashSet;
+    let codes = SHT::<u32>::all_codes(1).collect::<Vec<_>>();
+    let strings = codes
+        .iter()
+        .map(ToString::to_string)
+        .collect::<HashSet<_>>();
+    assert_eq!(strings.len(), codes.len());
+    let colours = codes
+        .iter()
+        .map(|code| code.to_rgb(3))
+        .collect::<HashSet<_>>();
+    assert_eq!(colours.len(), codes.len());
+    for code in &codes {
+        let (channel_ratios, shade, tint) = code.components();
+        assert_eq!(SHT::new(channel_ratios, shade, tint).as_ref(), Ok(code));
+    }
+}
+
+#[test]
+fn all_codes_precision_one() {
+    use super::SHT;
+    let codes = SHT::<u32>::all_codes(1).collect::<Vec<_>>();
+    assert_eq!(codes.len(), 6 * 12 * 12 * 12 + 12 + 1);
+    assert_eq!(codes.len(), 10381);
+    let greys = codes.iter().take_while(|code| code.to_string().len() == 1);
+    assert_eq!(
+        greys.map(ToString::to_string).collect::<String>(),
+        "0123456789XEW"
+    );
+    assert!(codes
+        .iter()
+        .all(|code| code.assert_spec_conformant().is_ok()));
+}
+
+#[test]
+fn code_count_matches_enumeration() {
+    use super::{sht_code_count, SHT};
+    for precision in 0..=1 {
+        assert_eq!(
+            SHT::<u32>::all_codes(precision).count() as u128,
+            sht_code_count(precision)
+        );
+    }
+    assert_eq!(
+        sht_code_count(11),
+        6 * 12_u128.pow(33) + 12_u128.pow(11) + 1
+    );
+}
+
+#[test]
+#[should_panic]
+fn code_count_overflow() {
+    let _ = super::sht_code_count(12);
+}
+
+#[test]
+fn minify_equivalent_codes() {
+    use super::minify;
+    assert_eq!(minify("500r6"), Ok("5r6".to_owned()));
+    assert_eq!(minify("5r600000"), Ok("5r6".to_owned()));
+    assert_eq!(minify("R"), Ok("r".to_owned()));
+    assert_eq!(minify("000"), Ok("0".to_owned()));
+    assert_eq!(minify("w"), Ok("W".to_owned()));
+    assert_eq!(minify("X0r60G30"), Ok("Xr6g3".to_owned()));
+    assert_eq!(minify("5EX4r39Xb0E1"), Ok("5EX4r39Xb0E1".to_owned()));
+}
+
+#[test]
+fn minify_invalid_code() {
+    use super::{minify, ParsePropertyError};
+    assert_eq!(
+        minify("rW"),
+        Err(ParsePropertyError::InputRemaining {
+            parsed_len: 1,
+            remaining: "W".to_owned()
+        })
+    );
+    assert!(minify("0r").is_err());
+}
+
+#[test]
+fn refinement_single_digit() {
+    use super::SHT;
+    let refines = |fine: &str, coarse: &str| {
+        let fine = fine.parse::<SHT<u32>>().unwrap();
+        let coarse = coarse.parse::<SHT<u32>>().unwrap();
+        fine.is_refinement_of(&coarse)
+    };
+    assert!(refines("1r", "r"));
+    assert!(refines("r4", "r"));
+    assert!(refines("14r", "1r"));
+    assert!(refines("8r6g3", "8r6g"));
+    assert!(refines("8r64g", "8r6g"));
+    assert!(refines("8c1", "8c"));
+    assert!(refines("61", "6"));
+}
+
+#[test]
+fn refinement_rejected() {
+    use super::SHT;
+    let refines = |fine: &str, coarse: &str| {
+        let fine = fine.parse::<SHT<u32>>().unwrap();
+        let coarse = coarse.parse::<SHT<u32>>().unwrap();
+        fine.is_refinement_of(&coarse)
+    };
+    assert!(!refines("8b", "r"));
+    assert!(!refines("r", "r"));
+    assert!(!refines("r", "1r"));
+    assert!(!refines("r6g", "r"));
+    assert!(!refines("8r6b", "8r6g"));
+    assert!(!refines("14r", "r"));
+    assert!(!refines("1r1", "r"));
+    assert!(!refines("15r", "14r"));
+    assert!(!refines("W", "6"));
+    assert!(!refines("6", "W"));
+    assert!(!refines("8c", "8y"));
+    assert!(!refines("4", "0"));
+}
+
+#[test]
+fn display_drops_vanishing_blend() {
+    use super::{ChannelRatios, ColourChannel, SHT};
+    use num::rational::Ratio;
+    let faint = SHT::<u32>::new(
+        ChannelRatios::OneBrightestChannel {
+            primary: ColourChannel::Red,
+            direction_blend: Some((ColourChannel::Green, Ratio::new(1, 200))),
+        },
+        Ratio::new(1, 2),
+        Ratio::new(0, 1),
+    )
+    .unwrap();
+    assert_eq!(format!("{:.1}", faint), "6r");
+    assert_eq!(format!("{:.0}", faint), "r");
+    assert_eq!(
+        format!("{:.1}", faint)
+            .parse::<SHT<u32>>()
+            .unwrap()
+            .to_string(),
+        "6r"
+    );
+    // enough precision keeps the blend
+    assert_eq!(format!("{:.3}", faint), "6r009g");
+    assert_eq!(format!("{:.2}", faint), "6r01g");
+}
+
+#[test]
+fn display_collapses_rounded_edges() {
+    use super::{ChannelRatios, ColourChannel, SHT};
+    use num::rational::Ratio;
+    let blended = SHT::<u32>::new(
+        ChannelRatios::OneBrightestChannel {
+            primary: ColourChannel::Red,
+            direction_blend: Some((ColourChannel::Green, Ratio::new(143, 144))),
+        },
+        Ratio::from_integer(1),
+        Ratio::from_integer(0),
+    )
+    .unwrap();
+    let parse = |s: &str| s.parse::<SHT<u32>>().unwrap();
+    // blend rounds to one
+    assert_eq!(format!("{:.1}", blended), "y");
+    // tint rounds to one
+    assert_eq!(format!("{:.1}", parse("rEE")), "W");
+    assert_eq!(format!("{:.1}", parse("8r6gE6")), "W");
+    // shade rounds to zero
+    assert_eq!(format!("{:.1}", parse("01r")), "0");
+    assert_eq!(format!("{:.1}", parse("01r6")), "6");
+    // shade rounds to one
+    assert_eq!(format!("{:.1}", parse("EEr")), "r");
+    // tint rounds to zero
+    assert_eq!(format!("{:.1}", parse("8r01g01")), "8r");
+    assert_eq!(format!("{:.1}", parse("01")), "0");
+    for colour in &[
+        blended,
+        parse("rEE"),
+        parse("8r6gE6"),
+        parse("01r"),
+        parse("01r6"),
+        parse("EEr"),
+        parse("8r01g01"),
+        parse("01"),
+        parse("8"),
+    ] {
+        for precision in 0..4 {
+            let code = format!("{:.*}", precision, colour);
+            assert!(code.parse::<SHT<u32>>().is_ok(), "{}", code);
+        }
+    }
+}
+
+#[test]
+fn hwb_exact_matches_rgb() {
+    use super::SHT;
+    use crate::rgb::HexRGB;
+    for code in &[
+        "8r6g3", "r", "b", "g4r", "6b2r", "4gXb", "E1g1b", "c", "y3", "5m", "0", "W", "6", "X3b6g5",
+    ] {
+        let colour = code.parse::<SHT<u64>>().unwrap();
+        let (red, green, blue) = colour.exact_channels();
+        assert_eq!(
+            colour.to_hwb_exact(),
+            HexRGB::new(red, green, blue).to_hwb(),
+            "{}",
+            code
+        );
+    }
+}
+
+#[test]
+fn hwb_exact_round_trip() {
+    use super::SHT;
+    for colour in SHT::<u32>::all_codes(1) {
+        let (hue, whiteness, blackness) = colour.to_hwb_exact();
+        assert_eq!(SHT::from_hwb(hue, whiteness, blackness), colour);
+    }
+}
+
+#[test]
+fn hwb_exact_normalises_greys() {
+    use super::SHT;
+    use num::rational::Ratio;
+    let grey = SHT::<u32>::from_hwb(Ratio::new(1, 3), Ratio::new(1, 2), Ratio::new(3, 2));
+    assert_eq!(grey, "3".parse().unwrap());
+    let black = SHT::<u32>::from_hwb(Ratio::new(1, 3), Ratio::new(0, 1), Ratio::new(1, 1));
+    assert_eq!(black, "0".parse().unwrap());
+}
+
+#[test]
+fn display_huge_precision() {
+    use super::{ChannelRatios, SHT};
+    use num::rational::Ratio;
+    let colour = "5EX4r39Xb0E1".parse::<SHT<u8>>().unwrap();
+    assert_eq!(format!("{:.99}", colour), "6r3Xb0E");
+    assert_eq!(format!("{:.99}", colour), format!("{:.6}", colour));
+    // components with large denominators, and no finite expansion
+    let colour = SHT::<u8>::new(
+        ChannelRatios::ThreeBrightestChannels,
+        Ratio::new(1, 1),
+        Ratio::new(254, 255),
+    )
+    .unwrap();
+    assert_eq!(format!("{:.1}", colour), "W");
+    assert_eq!(format!("{:.2}", colour), "EE");
+    assert_eq!(format!("{:.99}", colour), format!("{:.6}", colour));
+    assert_eq!(format!("{:.65535}", colour).len(), 6);
+}
+
+#[test]
+fn recommended_precision_by_type() {
+    use super::recommended_precision;
+    assert_eq!(recommended_precision::<u8>(), 6);
+    assert_eq!(recommended_precision::<u16>(), 11);
+    assert_eq!(recommended_precision::<u32>(), 21);
+    assert_eq!(recommended_precision::<u64>(), 41);
+    assert_eq!(recommended_precision::<u128>(), 81);
+}
+
+#[test]
+fn anchors_of_red() {
+    use super::SHT;
+    use crate::rgb::HexRGB;
+    let hex = |s: &str| s.parse::<HexRGB<u32>>().unwrap();
+    let anchors = |s: &str| s.parse::<SHT<u32>>().unwrap().anchors(2);
+    assert_eq!(anchors("r"), (hex("#F00"), hex("#F00"), hex("#F00")));
+    assert_eq!(
+        anchors("6r4"),
+        (hex("#F00"), hex("#800000"), hex("#FF5555"))
+    );
+    let (pure, shaded, tinted) = anchors("8r6g3");
+    assert_eq!(pure, hex("#FF8000"));
+    assert_eq!(shaded, hex("#AA5500"));
+    assert_eq!(tinted, hex("#FF9F40"));
+    assert_eq!(anchors("4c"), (hex("#0FF"), hex("#005555"), hex("#0FF")));
+    assert_eq!(anchors("6"), (hex("#000"), hex("#000"), hex("#808080")));
+}
+
+#[test]
+fn parse_fractions_matches_digits() {
+    use super::SHT;
+    let fractions = |s: &str| SHT::<u32>::parse_fractions(s);
+    let digits = |s: &str| s.parse::<SHT<u32>>();
+    assert_eq!(fractions("1/2 r"), digits("6r"));
+    assert_eq!(fractions("1/2r"), digits("6r"));
+    assert_eq!(fractions("2/3 r 1/2 g 1/4"), digits("8r6g3"));
+    assert_eq!(fractions(" 8 r 6 g 3 "), digits("8r6g3"));
+    assert_eq!(fractions("8r6g3"), digits("8r6g3"));
+    assert_eq!(fractions("1/3 c"), digits("4c"));
+    assert_eq!(fractions("0/5"), digits("0"));
+    assert_eq!(fractions("6/12"), digits("6"));
+    assert_eq!(fractions("W"), digits("W"));
+}
+
+#[test]
+fn parse_fractions_exact() {
+    use super::{ChannelRatios, ColourChannel, SHT};
+    use num::rational::Ratio;
+    assert_eq!(
+        SHT::<u32>::parse_fractions("4/5 r 1/7 b 1/5")
+            .unwrap()
+            .components(),
+        (
+            ChannelRatios::OneBrightestChannel {
+                primary: ColourChannel::Red,
+                direction_blend: Some((ColourChannel::Blue, Ratio::new(1, 7))),
+            },
+            Ratio::new(4, 5),
+            Ratio::new(1, 5)
+        )
+    );
+}
+
+#[test]
+fn parse_fractions_failures() {
+    use super::{ParsePropertyError, SHTValueError, SHT};
+    let fractions = |s: &str| SHT::<u32>::parse_fractions(s);
+    assert_eq!(
+        fractions("3/2 r"),
+        Err(ParsePropertyError::ValueErrors(vec![
+            SHTValueError::ValueOutOfBounds
+        ]))
+    );
+    assert!(fractions("1/0 r").is_err());
+    assert!(fractions("1/ r").is_err());
+    assert!(fractions("1/2 r 0/3").is_err());
+    // fractions are not accepted by the standard parser
+    assert!("1/2 r".parse::<SHT<u32>>().is_err());
+}
+
+#[test]
+fn shade_and_tint_percent() {
+    use super::SHT;
+    let colour = "8r6g3".parse::<SHT<u8>>().unwrap();
+    assert!((colour.shade_percent() - 66.7).abs() < 0.05);
+    assert!((colour.tint_percent() - 25.0).abs() < 1e-9);
+    let white = "W".parse::<SHT<u8>>().unwrap();
+    assert_eq!(white.tint_percent(), 100.0);
+    let red = "r".parse::<SHT<u8>>().unwrap();
+    assert_eq!(red.shade_percent(), 100.0);
+    assert_eq!(red.tint_percent(), 0.0);
+}
+
+#[test]
+fn parse_failure_reports_parsed_len() {
+    use super::{ParsePropertyError, SHT};
+    match "8r6g3W".parse::<SHT<u8>>() {
+        Err(ParsePropertyError::InputRemaining {
+            parsed_len,
+            remaining,
+        }) => {
+            assert_eq!(parsed_len, 5);
+            assert_eq!(remaining, "W");
+        }
+        other => panic!("unexpected result {:?}", other),
+    }
+    assert_eq!(
+        SHT::<u8>::parse_fractions("  1/2 r q"),
+        Err(ParsePropertyError::InputRemaining {
+            parsed_len: 7,
+            remaining: " q".to_owned()
+        })
+    );
+}
+
+#[test]
+fn bytes_round_trip() {
+    use super::SHT;
+    for code in &[
+        "0",
+        "W",
+        "6",
+        "r",
+        "8r6g3",
+        "4c",
+        "y3",
+        "EmX",
+        "b4r",
+        "5EX4r39Xb0E1",
+        "11g1",
+    ] {
+        let colour = code.parse::<SHT<u32>>().unwrap();
+        assert_eq!(
+            SHT::from_bytes(colour.to_bytes()),
+            Some(colour.to_spec_precision()),
+            "{}",
+            code
+        );
+    }
+}
+
+#[test]
+fn bytes_invalid() {
+    use super::SHT;
+    assert_eq!(SHT::<u32>::from_bytes([0x40, 0, 0, 0]), None);
+    assert_eq!(SHT::<u32>::from_bytes([0x1C, 144, 0, 0]), None);
+    assert_eq!(SHT::<u32>::from_bytes([0x10, 145, 0, 0]), None);
+    assert_eq!(SHT::<u32>::from_bytes([0x25, 144, 0, 72]), None);
+    assert_eq!(SHT::<u32>::from_bytes([0x00, 144, 0, 72]), None);
+}
+
+#[test]
+fn parse_into_matches_parse() {
+    use super::SHT;
+    for code in &[
+        "0",
+        "W",
+        "w",
+        "6",
+        "r",
+        "8r6g3",
+        "4c",
+        "y3",
+        "EmX",
+        "b4r",
+        "5EX4r39Xb0E1",
+        "rW",
+        "0r",
+        "x",
+        "",
+    ] {
+        let mut digits = [0; 16];
+        assert_eq!(
+            SHT::<u32>::parse_into(code, &mut digits).map(|(colour, _)| colour),
+            code.parse::<SHT<u32>>().ok(),
+            "{}",
+            code
+        );
+    }
+}
+
+#[test]
+fn parse_into_digits() {
+    use super::SHT;
+    let mut digits = [0; 12];
+    let (_, counts) = SHT::<u64>::parse_into("5EX4r39Xb0E1", &mut digits).unwrap();
+    assert_eq!(counts, [4, 3, 3]);
+    assert_eq!(digits[..10], [5, 11, 10, 4, 3, 9, 10, 0, 11, 1]);
+    let (_, counts) = SHT::<u64>::parse_into("W", &mut digits).unwrap();
+    assert_eq!(counts, [0, 0, 1]);
+    assert_eq!(digits[0], 12);
+    // buffer too small
+    assert_eq!(SHT::<u64>::parse_into("8r6g3", &mut [0; 2]), None);
+    assert!(SHT::<u64>::parse_into("8r6g3", &mut [0; 3]).is_some());
+}
+
+#[test]
+fn delta_round_trip() {
+    use super::SHT;
+    let codes = [
+        "0",
+        "W",
+        "6",
+        "r",
+        "8r6g3",
+        "4c",
+        "y3",
+        "EmX",
+        "b4r",
+        "5EX4r39Xb0E1",
+        "r6g",
+        "8r4g",
+    ];
+    for base in &codes {
+        for target in &codes {
+            let (base, target) = (
+                base.parse::<SHT<u64>>().unwrap(),
+                target.parse::<SHT<u64>>().unwrap(),
+            );
+            assert_eq!(base.apply_delta(&target.delta_from(&base)), Ok(target));
+        }
+    }
+}
+
+#[test]
+fn delta_validation() {
+    use super::{Difference, SHTDelta, SHTValueError, SHT};
+    use num::rational::Ratio;
+    let parse = |s: &str| s.parse::<SHT<u32>>().unwrap();
+    let darken = SHTDelta {
+        shade: Difference::Lower(Ratio::new(1, 2)),
+        ..SHTDelta::default()
+    };
+    assert_eq!(parse("8r").apply_delta(&darken), Ok(parse("2r")));
+    assert_eq!(
+        parse("4r").apply_delta(&darken),
+        Err(vec![SHTValueError::ValueOutOfBounds])
+    );
+    assert_eq!(
+        parse("6r").apply_delta(&darken),
+        Err(vec![SHTValueError::PrimaryShadeZero])
+    );
+    let blend = parse("r6g").delta_from(&parse("r4g"));
+    assert_eq!(blend.blend, Difference::Raise(Ratio::new(1, 6)));
+    assert_eq!(parse("b2r").apply_delta(&blend), Ok(parse("b4r")));
+    assert_eq!(
+        parse("b").apply_delta(&blend),
+        Err(vec![SHTValueError::BlendZero])
+    );
+}
+
+#[test]
+fn parse_strict_overflow() {
+    use super::{ParsePropertyError, SHT};
+    let lenient = "EEEEEEc".parse::<SHT<u8>>();
+    assert!(lenient.is_ok());
+    assert_eq!(
+        SHT::<u8>::parse_strict("EEEEEEc"),
+        Err(ParsePropertyError::PrecisionOverflow)
+    );
+    assert_eq!(
+        SHT::<u8>::parse_strict("r6gEEEEEE"),
+        Err(ParsePropertyError::PrecisionOverflow)
+    );
+    // wider types hold the same digits exactly
+    assert_eq!(
+        SHT::<u64>::parse_strict("EEEEEEc"),
+        "EEEEEEc".parse::<SHT<u64>>()
+    );
+}
+
+#[test]
+fn parse_strict_matches_parse() {
+    use super::SHT;
+    for code in &[
+        "0", "W", "6", "r", "8r6g3", "4c", "EmX", "rW", "0r", "x", "",
+    ] {
+        assert_eq!(
+            SHT::<u32>::parse_strict(code),
+            code.parse::<SHT<u32>>(),
+            "{}",
+            code
+        );
+    }
+}
+
+#[test]
+fn coerce_valid_each_error() {
+    use super::{ChannelRatios, ColourChannel, SecondaryColour, SHT};
+    use num::rational::Ratio;
+    let parse = |s: &str| s.parse::<SHT<u32>>().unwrap();
+    let (zero, half, one) = (
+        Ratio::from_integer(0),
+        Ratio::new(1, 2),
+        Ratio::from_integer(1),
+    );
+    let primary = |direction_blend| ChannelRatios::OneBrightestChannel {
+        primary: ColourChannel::Red,
+        direction_blend,
+    };
+    let secondary = ChannelRatios::TwoBrightestChannels {
+        secondary: SecondaryColour::Cyan,
+    };
+    // valid components are unchanged
+    assert_eq!(SHT::coerce_valid(primary(None), half, half), parse("6r6"));
+    // out of bounds
+    assert_eq!(
+        SHT::coerce_valid(primary(None), Ratio::new(3, 2), half),
+        parse("r6")
+    );
+    assert_eq!(
+        SHT::coerce_valid(primary(None), half, Ratio::new(3, 2)),
+        parse("W")
+    );
+    assert_eq!(
+        SHT::coerce_valid(
+            primary(Some((ColourChannel::Blue, Ratio::new(5, 2)))),
+            half,
+            zero
+        ),
+        parse("6m")
+    );
+    // direction equals primary
+    assert_eq!(
+        SHT::coerce_valid(primary(Some((ColourChannel::Red, half))), half, zero),
+        parse("6r")
+    );
+    // blend zero and one
+    assert_eq!(
+        SHT::coerce_valid(primary(Some((ColourChannel::Green, zero))), half, zero),
+        parse("6r")
+    );
+    assert_eq!(
+        SHT::coerce_valid(primary(Some((ColourChannel::Green, one))), half, zero),
+        parse("6y")
+    );
+    // shade zero
+    assert_eq!(SHT::coerce_valid(primary(None), zero, half), parse("6"));
+    assert_eq!(SHT::coerce_valid(secondary, zero, zero), parse("0"));
+    // tint one
+    assert_eq!(SHT::coerce_valid(primary(None), half, one), parse("W"));
+    assert_eq!(SHT::coerce_valid(secondary, half, one), parse("W"));
+}
+
+#[test]
+fn hue_sweep_six_steps() {
+    use super::SHT;
+    use num::rational::Ratio;
+    let parse = |s: &str| s.parse::<SHT<u32>>().unwrap();
+    assert_eq!(
+        SHT::<u32>::hue_sweep(6, Ratio::new(1, 2), Ratio::from_integer(0), 2),
+        ["6r", "6y", "6g", "6c", "6b", "6m"]
+            .iter()
+            .map(|s| parse(s))
+            .collect::<Vec<_>>()
+    );
+    assert_eq!(
+        SHT::<u32>::hue_sweep(4, Ratio::from_integer(1), Ratio::from_integer(0), 2),
+        ["r", "g6r", "c", "b6r"]
+            .iter()
+            .map(|s| parse(s))
+            .collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn hue_sweep_edge_cases() {
+    use super::SHT;
+    use num::rational::Ratio;
+    let parse = |s: &str| s.parse::<SHT<u32>>().unwrap();
+    assert_eq!(
+        SHT::<u32>::hue_sweep(0, Ratio::new(1, 2), Ratio::new(1, 2), 2),
+        []
+    );
+    assert_eq!(
+        SHT::<u32>::hue_sweep(3, Ratio::from_integer(0), Ratio::new(1, 2), 2),
+        vec![parse("6"); 3]
+    );
+    assert_eq!(
+        SHT::<u32>::hue_sweep(2, Ratio::new(1, 2), Ratio::from_integer(1), 2),
+        vec![parse("W"); 2]
+    );
+    // a blend of 1/7 rounds to 2/12 at one digit
+    assert_eq!(
+        SHT::<u32>::hue_sweep(42, Ratio::from_integer(1), Ratio::from_integer(0), 1)[1],
+        parse("r2g")
+    );
+    // valid at every step and precision
+    for code in SHT::<u32>::hue_sweep(100, Ratio::new(5, 7), Ratio::new(1, 3), 3) {
+        let (channel_ratios, shade, tint) = code.components();
+        assert_eq!(SHT::new(channel_ratios, shade, tint), Ok(code));
+    }
+}
+
+#[test]
+fn css_color_mix() {
+    use super::SHT;
+    let colour = "6r3".parse::<SHT<u32>>().unwrap();
+    assert_eq!(
+        colour.to_css_color_mix(2),
+        "color-mix(in srgb, color-mix(in srgb, #FF0000, black 50%), white 25%)"
+    );
+    let colour = "r".parse::<SHT<u32>>().unwrap();
+    assert_eq!(
+        colour.to_css_color_mix(1),
+        "color-mix(in srgb, color-mix(in srgb, #F00, black 0%), white 0%)"
+    );
+    let colour = "4g6b1".parse::<SHT<u32>>().unwrap();
+    assert_eq!(
+        colour.to_css_color_mix(2),
+        "color-mix(in srgb, color-mix(in srgb, #00FF80, black 66.67%), white 8.33%)"
+    );
+    let grey = "6".parse::<SHT<u32>>().unwrap();
+    assert_eq!(
+        grey.to_css_color_mix(2),
+        "color-mix(in srgb, color-mix(in srgb, #000000, black 0%), white 50%)"
+    );
+}
+
+#[test]
+fn plane_corners() {
+    use super::{ChannelRatios, SHT};
+    use crate::rgb::HexRGB;
+    use ::num::{rational::Ratio, One, Zero};
+    let hex = |code: &str| code.parse::<HexRGB<u32>>().unwrap();
+    let plane = "6r4".parse::<SHT<u32>>().unwrap().plane(5, 4, 2);
+    assert_eq!(plane.len(), 4);
+    assert!(plane.iter().all(|row| row.len() == 5));
+    assert_eq!(plane[0][0], hex("#000000"));
+    assert_eq!(plane[0][4], hex("#FF0000"));
+    assert_eq!(plane[3][0], hex("#FFFFFF"));
+    assert_eq!(plane[3][4], hex("#FFFFFF"));
+    assert_eq!(plane[0][2], hex("#800000"));
+    assert_eq!(plane[1][4], hex("#FF5555"));
+    // the edges of the plane are built as the equivalent valid greys
+    let black = SHT::new(ChannelRatios::grey(), Ratio::zero(), Ratio::zero()).unwrap();
+    let white = SHT::new(ChannelRatios::grey(), Ratio::one(), Ratio::one()).unwrap();
+    assert_eq!(plane[0][0], black.to_rgb(2));
+    assert_eq!(plane[3][2], white.to_rgb(2));
+
+    let grey = "6".parse::<SHT<u32>>().unwrap().plane(3, 3, 1);
+    assert_eq!(grey[1], vec![hex("#888"); 3]);
+
+    assert_eq!(
+        "r".parse::<SHT<u32>>().unwrap().plane(1, 1, 1),
+        [[hex("#000")]]
+    );
+    assert!("r".parse::<SHT<u32>>().unwrap().plane(3, 0, 1).is_empty());
+}
+
+#[test]
+fn parse_bytes_matches_str() {
+    use super::{parse_bytes, ParsePropertyError, SHT};
+    for code in &[
+        "r", "8r6g3", "W", "0", "4c", "b6r3", " r", "r ", "", "x", "8r6r",
+    ] {
+        assert_eq!(
+            parse_bytes::<u32>(code.as_bytes()),
+            code.parse::<SHT<u32>>()
+        );
+    }
+    assert_eq!(
+        parse_bytes::<u32>(b"8r\xFF"),
+        Err(ParsePropertyError::NonAscii)
+    );
+    assert_eq!(
+        parse_bytes::<u32>("8r\u{e9}".as_bytes()),
+        Err(ParsePropertyError::NonAscii)
+    );
+}
+
+#[test]
+fn packed_round_trip() {
+    use super::SHT;
+    let parse = |s: &str| s.parse::<SHT<u32>>().unwrap();
+    for code in &["r", "8r6g3", "W", "0", "4c", "b6r3", "6", "Xm1", "1y"] {
+        for &precision in &[1, 2, 3, 5] {
+            let packed = parse(code).to_packed(precision).expect(code);
+            assert_eq!(SHT::from_packed(packed, precision), Some(parse(code)));
+        }
+    }
+    for code in &["8r6g31", "X6r", "r16", "b13g"] {
+        assert_eq!(parse(code).to_packed(1), None);
+        let packed = parse(code).to_packed(2).unwrap();
+        assert_eq!(SHT::from_packed(packed, 2), Some(parse(code)));
+    }
+    assert_eq!(parse("5EXr39Xb0E1").to_packed(2), None);
+    let packed = parse("5EXr39Xb0E1").to_packed(3).unwrap();
+    assert_eq!(SHT::from_packed(packed, 3), Some(parse("5EXr39Xb0E1")));
+    assert_eq!(parse("r").to_packed(0), Some(0x10 << 3 | 0b100));
+    assert_eq!(parse("6r").to_packed(0), None);
+    assert_eq!(parse("r").to_packed(6), None);
+}
+
+#[test]
+fn packed_matches_bytes() {
+    use super::SHT;
+    for code in &["r", "8r6g3", "W", "4c", "b6r3", "33"] {
+        let colour = code.parse::<SHT<u32>>().unwrap();
+        assert_eq!(
+            colour.to_packed(2),
+            Some(u64::from(u32::from_be_bytes(colour.to_bytes())))
+        );
+    }
+}
+
+#[test]
+fn packed_invalid() {
+    use super::SHT;
+    // numerator larger than the denominator
+    assert_eq!(SHT::<u32>::from_packed(0x10D00, 1), None);
+    // bits above the structure byte
+    assert_eq!(SHT::<u32>::from_packed(0x110C00, 1), None);
+    // unrecognised structure
+    assert_eq!(SHT::<u32>::from_packed(0x40C00, 1), None);
+    // blend without a direction
+    assert_eq!(SHT::<u32>::from_packed(0x10C06, 1), None);
+    // invalid values
+    assert_eq!(SHT::<u32>::from_packed(0x10000, 1), None);
+    assert_eq!(SHT::<u32>::from_packed(0x10C00, 1), "r".parse().ok());
+    assert_eq!(SHT::<u32>::from_packed(0, 6), None);
+}
+
+#[test]
+fn hsl_round_trip() {
+    use super::SHT;
+    use num::rational::Ratio;
+    let parse = |s: &str| s.parse::<SHT<u32>>().unwrap();
+    for (code, hue) in &[
+        ("r", Ratio::new(0, 1)),
+        ("y", Ratio::new(1, 6)),
+        ("g", Ratio::new(1, 3)),
+        ("c", Ratio::new(1, 2)),
+        ("b", Ratio::new(2, 3)),
+        ("m", Ratio::new(5, 6)),
+    ] {
+        let (actual_hue, saturation, lightness) = parse(code).to_hsl();
+        assert_eq!(actual_hue, *hue);
+        assert_eq!(saturation, Ratio::from_integer(1));
+        assert_eq!(lightness, Ratio::new(1, 2));
+        assert_eq!(
+            SHT::from_hsl(actual_hue, saturation, lightness),
+            parse(code)
+        );
+    }
+    for code in &["8r6g3", "4c2", "Xb3r", "3y9", "6", "W", "0", "1m"] {
+        let (hue, saturation, lightness) = parse(code).to_hsl();
+        assert_eq!(SHT::from_hsl(hue, saturation, lightness), parse(code));
+    }
+}
+
+#[test]
+fn hsl_greys() {
+    use super::SHT;
+    use num::rational::Ratio;
+    let parse = |s: &str| s.parse::<SHT<u32>>().unwrap();
+    assert_eq!(
+        parse("6").to_hsl(),
+        (
+            Ratio::from_integer(0),
+            Ratio::from_integer(0),
+            Ratio::new(1, 2)
+        )
+    );
+    assert_eq!(
+        parse("0").to_hsl(),
+        (
+            Ratio::from_integer(0),
+            Ratio::from_integer(0),
+            Ratio::from_integer(0)
+        )
+    );
+    assert_eq!(
+        parse("W").to_hsl(),
+        (
+            Ratio::from_integer(0),
+            Ratio::from_integer(0),
+            Ratio::from_integer(1)
+        )
+    );
+    assert_eq!(
+        SHT::from_hsl(Ratio::new(1, 3), Ratio::from_integer(0), Ratio::new(1, 4)),
+        parse("3")
+    );
+    assert_eq!(
+        SHT::from_hsl(
+            Ratio::new(1, 3),
+            Ratio::from_integer(2),
+            Ratio::from_integer(2)
+        ),
+        parse("W")
+    );
+}
+
+#[test]
+fn named_hues_codes() {
+    use super::{named_hues, SHT};
+    let hues = named_hues::<u32>();
+    let names = hues.iter().map(|(name, _)| *name).collect::<Vec<_>>();
+    assert_eq!(
+        names,
+        ["red", "yellow", "green", "cyan", "blue", "magenta", "black", "grey", "white"]
+    );
+    for (name, colour) in &hues {
+        assert_eq!(
+            SHT::new(colour.channel_ratios, colour.shade, colour.tint),
+            Ok(*colour),
+            "{}",
+            name
+        );
+    }
+    let lookup = |wanted: &str| {
+        hues.iter()
+            .find(|(name, _)| *name == wanted)
+            .map(|(_, colour)| colour.to_string())
+    };
+    assert_eq!(lookup("red").as_deref(), Some("r"));
+    assert_eq!(lookup("cyan").as_deref(), Some("c"));
+    assert_eq!(lookup("white").as_deref(), Some("W"));
+    assert_eq!(lookup("black").as_deref(), Some("0"));
+    assert_eq!(lookup("grey").as_deref(), Some("6"));
+}
+
+#[test]
+fn try_to_rgb_overflow() {
+    use super::{ConversionError, SHT};
+    let colour = "8r3".parse::<SHT<u8>>().unwrap();
+    assert_eq!(colour.try_to_rgb(1), Ok(colour.to_rgb(1)));
+    assert_eq!(colour.try_to_rgb(2), Err(ConversionError::Overflow));
+    let colour = "8r6g3".parse::<SHT<u32>>().unwrap();
+    for precision in 1..=4 {
+        assert_eq!(colour.try_to_rgb(precision), Ok(colour.to_rgb(precision)));
+    }
+    assert_eq!(colour.try_to_rgb(9), Err(ConversionError::Overflow));
+}
+
+#[test]
+fn spec_conformance() {
+    use super::{ChannelRatios, ColourChannel, SpecViolation, SHT};
+    use ::num::rational::Ratio;
+    for code in ["0", "6", "W", "r", "8r6g3", "y4", "5EXr39Xb0E1"].iter() {
+        let colour = code.parse::<SHT<u64>>().unwrap();
+        assert_eq!(colour.assert_spec_conformant(), Ok(()), "{}", code);
+    }
+    let grey =
+        |shade, tint| SHT::<u64>::new(ChannelRatios::ThreeBrightestChannels, shade, tint).unwrap();
+    assert_eq!(
+        grey(Ratio::new(1, 2), Ratio::new(1, 2)).assert_spec_conformant(),
+        Err(SpecViolation::GreyShade)
+    );
+    assert_eq!(
+        grey(Ratio::from_integer(1), Ratio::from_integer(0)).assert_spec_conformant(),
+        Err(SpecViolation::GreyShade)
+    );
+    let red = SHT::<u64>::new(
+        ChannelRatios::OneBrightestChannel {
+            primary: ColourChannel::Red,
+            direction_blend: Some((ColourChannel::Green, Ratio::new(1, 5))),
+        },
+        Ratio::from_integer(1),
+        Ratio::from_integer(0),
+    )
+    .unwrap();
+    assert_eq!(
+        red.assert_spec_conformant(),
+        Err(SpecViolation::NonDuodecimal)
+    );
+}
+
+#[test]
+fn channel_ordering() {
+    use super::{ColourChannel, SecondaryColour};
+    use ::std::collections::BTreeMap;
+    assert!(ColourChannel::Red < ColourChannel::Green);
+    assert!(ColourChannel::Green < ColourChannel::Blue);
+    assert!(SecondaryColour::Cyan < SecondaryColour::Magenta);
+    assert!(SecondaryColour::Magenta < SecondaryColour::Yellow);
+    // each secondary colour sorts in the same place as its complement
+    let complements = [
+        (SecondaryColour::Yellow, ColourChannel::Blue),
+        (SecondaryColour::Cyan, ColourChannel::Red),
+        (SecondaryColour::Magenta, ColourChannel::Green),
+    ];
+    let mut by_secondary = complements;
+    by_secondary.sort_by_key(|&(secondary, _)| secondary);
+    let mut by_primary = complements;
+    by_primary.sort_by_key(|&(_, primary)| primary);
+    assert_eq!(by_secondary, by_primary);
+    let names = [
+        (ColourChannel::Blue, "blue"),
+        (ColourChannel::Red, "red"),
+        (ColourChannel::Green, "green"),
+    ]
+    .iter()
+    .copied()
+    .collect::<BTreeMap<_, _>>();
+    assert_eq!(
+        names.values().copied().collect::<Vec<_>>(),
+        ["red", "green", "blue"]
+    );
+}
+
+#[test]
+fn sort_by_lightness() {
+    use super::SHT;
+    let mut colours = [
+        "W", "y", "g", "r6b", "r", "6", "4c8", "0", "6r", "r3", "m", "rX",
+    ]
+    .iter()
+    .map(|code| code.parse::<SHT<u32>>().unwrap())
+    .collect::<Vec<_>>();
+    colours.sort();
+    let codes = colours
+        .iter()
+        .map(|colour| colour.to_string())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        codes,
+        ["0", "6r", "r", "r6b", "g", "m", "y", "r3", "6", "4c8", "rX", "W"]
+    );
+}
+
+#[test]
+fn const_channel_ratios() {
+    use super::{ChannelRatios, ColourChannel, SecondaryColour, SHT};
+    use ::num::rational::Ratio;
+    const RED: ChannelRatios<u8> = ChannelRatios::primary(ColourChannel::Red);
+    const CYAN: ChannelRatios<u8> = ChannelRatios::secondary(SecondaryColour::Cyan);
+    const GREY: ChannelRatios<u8> = ChannelRatios::grey();
+    let channel_ratios = |code: &str| code.parse::<SHT<u8>>().unwrap().components().0;
+    assert_eq!(RED, channel_ratios("r"));
+    assert_eq!(CYAN, channel_ratios("c"));
+    assert_eq!(GREY, channel_ratios("6"));
+    assert_eq!(
+        SHT::<u8>::grey(Ratio::from_integer(1)),
+        Ok("W".parse().unwrap())
+    );
+    assert!(SHT::<u8>::grey(Ratio::new(3, 2)).is_err());
+}
+
+#[test]
+fn desaturate_to_grey() {
+    use super::{ChannelRatios, SHT};
+    use ::num::rational::Ratio;
+    let red = "r".parse::<SHT<u32>>().unwrap().desaturate();
+    assert_eq!(
+        red.components(),
+        (
+            ChannelRatios::ThreeBrightestChannels,
+            Ratio::from_integer(1),
+            Ratio::new(299, 1000)
+        )
+    );
+    assert_eq!(red.to_rgb(2), "#4C4C4C".parse().unwrap());
+    for code in ["0", "4", "W"].iter() {
+        let grey = code.parse::<SHT<u32>>().unwrap();
+        assert_eq!(grey.desaturate(), grey);
+    }
+}
+
+#[test]
+fn complement_pure_hues() {
+    use super::SHT;
+    let pairs = [
+        ("r", "c"),
+        ("g", "m"),
+        ("b", "y"),
+        ("8r3", "8c3"),
+        ("4g", "4m"),
+    ];
+    for (first, second) in pairs.iter() {
+        let first = first.parse::<SHT<u32>>().unwrap();
+        let second = second.parse::<SHT<u32>>().unwrap();
+        assert_eq!(first.complement(), second);
+        assert_eq!(second.complement(), first);
+    }
+    for code in ["0", "6", "W"].iter() {
+        let grey = code.parse::<SHT<u32>>().unwrap();
+        assert_eq!(grey.complement(), grey);
+    }
+    let colour = "9r3g4".parse::<SHT<u32>>().unwrap();
+    assert_eq!(colour.complement(), "9b9g4".parse().unwrap());
+    assert_eq!(colour.complement().complement(), colour);
+    // each channel is reflected between the brightest and dimmest
+    let (red, green, blue) = colour.exact_channels();
+    let sum = red.max(green).max(blue) + red.min(green).min(blue);
+    assert_eq!(
+        colour.complement().exact_channels(),
+        (sum - red, sum - green, sum - blue)
+    );
+}
+
+#[test]
+fn format_duodecimal_two_thirds() {
+    use super::format_duodecimal;
+    use num::rational::Ratio;
+    assert_eq!(format_duodecimal(Ratio::new(11310_u32, 20736), 2), "67");
+    assert_eq!(format_duodecimal(Ratio::new(11310_u32, 20736), 4), "6666");
+    assert_eq!(format_duodecimal(Ratio::new(2_u32, 3), 4), "8");
+    assert_eq!(format_duodecimal(Ratio::new(10_u32, 12), 4), "X");
+    assert_eq!(format_duodecimal(Ratio::new(11_u32, 12), 4), "E");
+    assert_eq!(format_duodecimal(Ratio::new(0_u32, 1), 4), "0");
+    assert_eq!(format_duodecimal(Ratio::new(287_u32, 288), 2), "W");
+}
+
+#[test]
+fn parse_duodecimal_inverts_format() {
+    use super::{format_duodecimal, parse_duodecimal, ParsePropertyError};
+    use num::rational::Ratio;
+    assert_eq!(
+        parse_duodecimal::<u32>("6666"),
+        Ok(Ratio::new(11310, 20736))
+    );
+    assert_eq!(parse_duodecimal::<u32>("67"), Ok(Ratio::new(79, 144)));
+    assert_eq!(parse_duodecimal::<u32>("XE"), Ok(Ratio::new(131, 144)));
+    assert_eq!(parse_duodecimal::<u32>("W"), Ok(Ratio::from_integer(1)));
+    assert_eq!(
+        parse_duodecimal::<u32>("6W"),
+        Err(ParsePropertyError::InputRemaining {
+            parsed_len: 1,
+            remaining: "W".to_owned()
+        })
+    );
+    assert!(parse_duodecimal::<u32>("").is_err());
+    assert!(parse_duodecimal::<u32>("r").is_err());
+    for numerator in 0..=144_u32 {
+        let ratio = Ratio::new(numerator, 144);
+        assert_eq!(parse_duodecimal(&format_duodecimal(ratio, 2)), Ok(ratio));
+    }
+}
+
+#[test]
+fn rounding_modes_in_conversions() {
+    use super::{format_duodecimal_with_rounding, SHT};
+    use crate::{rgb::HexRGB, RoundingMode};
+    use num::rational::Ratio;
+    let modes = [
+        RoundingMode::HalfUp,
+        RoundingMode::HalfEven,
+        RoundingMode::Truncate,
+    ];
+    // shade 2/12 gives a red channel of 2.5 over 0xF
+    let dark_red = "2r".parse::<SHT<u32>>().unwrap();
+    let to_rgb = |mode| dark_red.to_rgb_with_rounding(1, mode).to_string();
+    assert_eq!(
+        modes.iter().map(|&mode| to_rgb(mode)).collect::<Vec<_>>(),
+        ["#330000", "#220000", "#220000"]
+    );
+    assert_eq!(
+        dark_red.to_rgb_with_rounding(1, RoundingMode::HalfUp),
+        dark_red.to_rgb(1)
+    );
+    // a red channel of 15/24 gives a shade of 7.5 twelfths
+    let red = HexRGB::<u32>::new(Ratio::new(15, 24), Ratio::new(0, 1), Ratio::new(0, 1));
+    let to_sht = |mode| red.to_sht_with_rounding(1, mode).to_string();
+    assert_eq!(
+        modes.iter().map(|&mode| to_sht(mode)).collect::<Vec<_>>(),
+        ["8r", "8r", "7r"]
+    );
+    // 0.76 in base 12 is halfway between 0.7 and 0.8
+    let ratio = Ratio::new(7 * 12 + 6, 144_u32);
+    assert_eq!(
+        modes
+            .iter()
+            .map(|&mode| format_duodecimal_with_rounding(ratio, 1, mode))
+            .collect::<Vec<_>>(),
+        ["8", "8", "7"]
+    );
+    let code = "6r76".parse::<SHT<u32>>().unwrap();
+    assert_eq!(
+        modes
+            .iter()
+            .map(|&mode| code.to_string_with_rounding(1, mode))
+            .collect::<Vec<_>>(),
+        ["6r8", "6r8", "6r7"]
+    );
+    assert_eq!(
+        code.to_string_with_rounding(1, RoundingMode::HalfUp),
+        format!("{:.1}", code)
+    );
+}
@@ -0,0 +1,49 @@
+//! Conversions that must keep working without the `parse` feature, checked by
+//! `cargo test --no-default-features --test conversion`.
+
+use sht_colour::{rgb::HexRGB, ChannelRatios, ColourChannel, Ratio, SecondaryColour, SHT};
+
+#[test]
+fn convert_without_parsing() {
+    let orange = SHT::<u32>::new(
+        ChannelRatios::OneBrightestChannel {
+            primary: ColourChannel::Red,
+            direction_blend: Some((ColourChannel::Green, Ratio::new(1, 2))),
+        },
+        Ratio::from_integer(1),
+        Ratio::from_integer(0),
+    )
+    .unwrap();
+    let orange_hex = HexRGB::new(
+        Ratio::from_integer(1),
+        Ratio::new(8, 15),
+        Ratio::from_integer(0),
+    );
+
+    assert_eq!(orange.to_rgb(1), orange_hex);
+    assert_eq!(orange_hex.to_sht(1), orange);
+    assert_eq!(orange.to_string(), "r6g");
+    assert_eq!(orange_hex.to_string(), "#FF8800");
+}
+
+#[test]
+fn construct_secondary_and_grey() {
+    let cyan = SHT::<u32>::new(
+        ChannelRatios::TwoBrightestChannels {
+            secondary: SecondaryColour::Cyan,
+        },
+        Ratio::new(1, 2),
+        Ratio::from_integer(0),
+    )
+    .unwrap();
+    let grey = SHT::<u32>::new(
+        ChannelRatios::ThreeBrightestChannels,
+        Ratio::from_integer(1),
+        Ratio::new(1, 2),
+    )
+    .unwrap();
+
+    assert_eq!(cyan.to_string(), "6c");
+    assert_eq!(grey.to_string(), "6");
+    assert_eq!(cyan.to_rgb(1).to_string(), "#008888");
+}
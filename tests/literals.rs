@@ -0,0 +1,9 @@
+#![cfg(feature = "parse")]
+
+#[test]
+fn invalid_literals_fail_to_compile() {
+    let cases = trybuild::TestCases::new();
+    cases.pass("tests/ui/valid_literals.rs");
+    cases.compile_fail("tests/ui/invalid_sht_literal.rs");
+    cases.compile_fail("tests/ui/invalid_hex_literal.rs");
+}
@@ -0,0 +1,5 @@
+use sht_colour::{hex, rgb::HexRGB};
+
+fn main() {
+    let _colour: HexRGB<u8> = hex!("#FF88");
+}
@@ -0,0 +1,5 @@
+use sht_colour::{sht, SHT};
+
+fn main() {
+    let _colour: SHT<u8> = sht!("8r6r3");
+}
@@ -0,0 +1,8 @@
+use sht_colour::{hex, rgb::HexRGB, sht, SHT};
+
+fn main() {
+    let colour: SHT<u8> = sht!("8r6g3");
+    assert_eq!(colour, "8r6g3".parse().unwrap());
+    let colour: HexRGB<u8> = hex!("#F81");
+    assert_eq!(colour, "#F81".parse().unwrap());
+}